@@ -0,0 +1,284 @@
+//!
+//! # Binary fields
+//!
+//! `binary_field!` defines the field GF(2^n) = GF(2)[x]/(f) for an
+//! irreducible reduction polynomial f = x^n + Σ x^e, given by its degree n and
+//! the exponents e < n.
+//! Elements are bit-packed into little-endian `u64` words where bit i is the
+//! coefficient of x^i.
+//!
+//! Multiplication is a carry-less multiplication of the words followed by
+//! reduction modulo f.
+//! Byte encodings are available in big-endian and little-endian order, and in
+//! the reflected bit order used by GCM where the most significant bit of the
+//! first byte is the coefficient of x^0.
+//!
+//! ```
+//! use hacspec::prelude::*;
+//!
+//! // The AES field with x^8 + x^4 + x^3 + x + 1
+//! binary_field!(Gf8, 8, [4, 3, 1, 0]);
+//!
+//! let a = Gf8::from_literal(0x57);
+//! assert_eq!(a * Gf8::from_literal(0x83), Gf8::from_literal(0xc1));
+//! assert_eq!(Gf8::from_literal(0x53).inv(), Gf8::from_literal(0xca));
+//! ```
+//!
+
+/// Carry-less multiplication of two words, returning the low and the high
+/// word of the product.
+pub fn clmul64(a: u64, b: u64) -> (u64, u64) {
+    let mut lo = 0u64;
+    let mut hi = 0u64;
+    for i in 0..64 {
+        // All ones if bit i of b is set.
+        let mask = 0u64.wrapping_sub((b >> i) & 1);
+        lo ^= (a << i) & mask;
+        if i > 0 {
+            hi ^= (a >> (64 - i)) & mask;
+        }
+    }
+    (lo, hi)
+}
+
+/// Carry-less multiplication of two polynomials over GF(2) packed into
+/// little-endian words.
+pub fn clmul_words(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut r = vec![0u64; a.len() + b.len()];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            let (lo, hi) = clmul64(*x, *y);
+            r[i + j] ^= lo;
+            r[i + j + 1] ^= hi;
+        }
+    }
+    r
+}
+
+/// Reduce the polynomial `a` packed into little-endian words modulo
+/// x^n + Σ x^e for the exponents e < n in `low_terms`.
+/// Returns ceil(n / 64) words.
+pub fn reduce_words(a: &[u64], n: usize, low_terms: &[usize]) -> Vec<u64> {
+    let mut r = a.to_vec();
+    for i in (n..r.len() * 64).rev() {
+        let mask = 0u64.wrapping_sub((r[i / 64] >> (i % 64)) & 1);
+        // x^i = x^(i - n) Σ x^e
+        r[i / 64] ^= (1u64 << (i % 64)) & mask;
+        for e in low_terms {
+            let j = i - n + e;
+            r[j / 64] ^= (1u64 << (j % 64)) & mask;
+        }
+    }
+    r.resize(n.div_ceil(64), 0);
+    r
+}
+
+/// Define the binary field `$name` = GF(2^`$bits`) with the reduction
+/// polynomial x^`$bits` + Σ x^e for the exponents e in `[$e, ...]`.
+#[macro_export]
+macro_rules! binary_field {
+    ($name:ident, $bits:literal, [$($e:literal),+]) => {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub struct $name([u64; ($bits as usize).div_ceil(64)]);
+
+        impl $name {
+            /// Exponents of the reduction polynomial below x^n.
+            const LOW_TERMS: &'static [usize] = &[$($e),+];
+
+            /// The degree n of the field.
+            pub fn bits() -> usize {
+                $bits
+            }
+
+            /// Number of bytes of the encodings, i.e. ceil(n / 8).
+            pub fn byte_len() -> usize {
+                ($bits as usize).div_ceil(8)
+            }
+
+            pub fn zero() -> $name {
+                $name([0; ($bits as usize).div_ceil(64)])
+            }
+
+            pub fn one() -> $name {
+                let mut r = $name::zero();
+                r.0[0] = 1;
+                r
+            }
+
+            /// The polynomial with the coefficients given by the bits of `x`,
+            /// reduced modulo f.
+            pub fn from_literal(x: u128) -> $name {
+                $name::from_words(&[x as u64, (x >> 64) as u64])
+            }
+
+            /// The polynomial packed into little-endian words, reduced modulo f.
+            pub fn from_words(words: &[u64]) -> $name {
+                let mut r = $name::zero();
+                r.0.copy_from_slice(&reduce_words(words, $bits, $name::LOW_TERMS));
+                r
+            }
+
+            /// The little-endian words of the canonical representation.
+            pub fn to_words(self) -> [u64; ($bits as usize).div_ceil(64)] {
+                self.0
+            }
+
+            /// The coefficient of x^i.
+            pub fn coefficient(self, i: usize) -> bool {
+                i < $bits && (self.0[i / 64] >> (i % 64)) & 1 == 1
+            }
+
+            pub fn square(self) -> $name {
+                // The cross terms cancel in characteristic 2.
+                let mut r = vec![0u64; 2 * self.0.len()];
+                for (i, w) in self.0.iter().enumerate() {
+                    let (lo, hi) = clmul64(*w, *w);
+                    r[2 * i] = lo;
+                    r[2 * i + 1] = hi;
+                }
+                $name::from_words(&r)
+            }
+
+            /// Compute `self^exp` with square-and-multiply.
+            pub fn pow(self, exp: u128) -> $name {
+                let mut r = $name::one();
+                for i in (0..128).rev() {
+                    r = r.square();
+                    if (exp >> i) & 1 == 1 {
+                        r = r * self;
+                    }
+                }
+                r
+            }
+
+            /// Multiplicative inverse self^(2^n - 2). The inverse of 0 is 0.
+            pub fn inv(self) -> $name {
+                // 2^n - 2 = 2 + 4 + ... + 2^(n - 1)
+                let mut t = self;
+                let mut r = $name::one();
+                for _ in 1..$bits {
+                    t = t.square();
+                    r = r * t;
+                }
+                r
+            }
+
+            /// The unique square root self^(2^(n - 1)).
+            pub fn sqrt(self) -> $name {
+                let mut r = self;
+                for _ in 1..$bits {
+                    r = r.square();
+                }
+                r
+            }
+
+            fn from_public_bytes_le(bytes: &[u8]) -> $name {
+                let words = bytes
+                    .chunks(8)
+                    .map(|c| c.iter().rev().fold(0u64, |acc, b| (acc << 8) | *b as u64))
+                    .collect::<Vec<u64>>();
+                $name::from_words(&words)
+            }
+
+            fn to_public_bytes_le(self) -> Vec<u8> {
+                let mut bytes = self.0.iter().flat_map(|w| w.to_le_bytes()).collect::<Vec<u8>>();
+                bytes.truncate($name::byte_len());
+                bytes
+            }
+
+            /// Decode little-endian bytes, i.e. bit j of byte k is the
+            /// coefficient of x^(8k + j). The value is reduced modulo f.
+            pub fn from_byte_seq_le<A: SeqTrait<U8>>(s: A) -> $name {
+                let bytes = s.iter().map(|x| U8::declassify(*x)).collect::<Vec<u8>>();
+                $name::from_public_bytes_le(&bytes)
+            }
+
+            /// Decode big-endian bytes. The value is reduced modulo f.
+            pub fn from_byte_seq_be<A: SeqTrait<U8>>(s: A) -> $name {
+                let bytes = s.iter().rev().map(|x| U8::declassify(*x)).collect::<Vec<u8>>();
+                $name::from_public_bytes_le(&bytes)
+            }
+
+            /// Decode bytes in GCM's reflected bit order, i.e. bit 7 - j of
+            /// byte k is the coefficient of x^(8k + j).
+            /// The value is reduced modulo f.
+            pub fn from_byte_seq_reflected<A: SeqTrait<U8>>(s: A) -> $name {
+                let bytes = s.iter().map(|x| U8::declassify(*x).reverse_bits()).collect::<Vec<u8>>();
+                $name::from_public_bytes_le(&bytes)
+            }
+
+            /// Little-endian encoding of `byte_len()` bytes.
+            pub fn to_byte_seq_le(self) -> Seq<U8> {
+                Seq::from(self.to_public_bytes_le().iter().map(|x| U8::classify(*x)).collect::<Vec<U8>>())
+            }
+
+            /// Big-endian encoding of `byte_len()` bytes.
+            pub fn to_byte_seq_be(self) -> Seq<U8> {
+                Seq::from(self.to_public_bytes_le().iter().rev().map(|x| U8::classify(*x)).collect::<Vec<U8>>())
+            }
+
+            /// Encoding of `byte_len()` bytes in GCM's reflected bit order.
+            pub fn to_byte_seq_reflected(self) -> Seq<U8> {
+                Seq::from(
+                    self.to_public_bytes_le()
+                        .iter()
+                        .map(|x| U8::classify(x.reverse_bits()))
+                        .collect::<Vec<U8>>(),
+                )
+            }
+
+            /// Little-endian encoding into a fixed-size byte array, e.g. one
+            /// defined with `bytes!`, of length `byte_len()`.
+            pub fn to_byte_array_le<A: From<Seq<U8>> + SeqTrait<U8>>(self) -> A {
+                let a = A::from(self.to_byte_seq_le());
+                debug_assert!(a.len() == $name::byte_len());
+                a
+            }
+
+            /// Big-endian encoding into a fixed-size byte array, e.g. one
+            /// defined with `bytes!`, of length `byte_len()`.
+            pub fn to_byte_array_be<A: From<Seq<U8>> + SeqTrait<U8>>(self) -> A {
+                let a = A::from(self.to_byte_seq_be());
+                debug_assert!(a.len() == $name::byte_len());
+                a
+            }
+
+            /// Encoding in GCM's reflected bit order into a fixed-size byte
+            /// array, e.g. one defined with `bytes!`, of length `byte_len()`.
+            pub fn to_byte_array_reflected<A: From<Seq<U8>> + SeqTrait<U8>>(self) -> A {
+                let a = A::from(self.to_byte_seq_reflected());
+                debug_assert!(a.len() == $name::byte_len());
+                a
+            }
+        }
+
+        /// Addition is the XOR of the coefficients.
+        impl Add for $name {
+            type Output = $name;
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            fn add(self, rhs: $name) -> $name {
+                let mut r = self;
+                for (a, b) in r.0.iter_mut().zip(rhs.0.iter()) {
+                    *a ^= b;
+                }
+                r
+            }
+        }
+
+        /// Subtraction is addition in characteristic 2.
+        impl Sub for $name {
+            type Output = $name;
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            fn sub(self, rhs: $name) -> $name {
+                self + rhs
+            }
+        }
+
+        impl Mul for $name {
+            type Output = $name;
+            fn mul(self, rhs: $name) -> $name {
+                $name::from_words(&clmul_words(&self.0, &rhs.0))
+            }
+        }
+    };
+}
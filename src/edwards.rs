@@ -0,0 +1,455 @@
+//!
+//! # Twisted Edwards curves
+//!
+//! Arithmetic on curves ax² + y² = 1 + dx²y² in extended coordinates,
+//! point compression as in RFC 8032, and the prime order groups
+//! ristretto255 and decaf448 from RFC 9496.
+//!
+//! A curve is defined by implementing `TwistedEdwardsCurve`.
+//! The `Ristretto` and `Decaf` traits provide the encoding, decoding, and
+//! equality functions for curves with cofactor 8 and p ≡ 5 mod 8, and
+//! cofactor 4 and p ≡ 3 mod 4 respectively.
+//!
+//! Edwards25519 and Edwards448 are provided as `Ed25519` and `Ed448`.
+//!
+//! ```
+//! use hacspec::prelude::*;
+//!
+//! let b = Ed25519::base_point();
+//! let b2 = Ed25519::scalar_mul(Ed25519Scalar::from_literal(2), b);
+//! assert!(Ed25519::point_eq(b2, Ed25519::double(b)));
+//! assert_eq!(
+//!     Ed25519::compress(b).to_hex(),
+//!     "5866666666666666666666666666666666666666666666666666666666666666"
+//! );
+//! assert_eq!(
+//!     Ed25519::ristretto_encode(b2).to_hex(),
+//!     "6a493210f7499cd17fecb510ae0cea23a110e8d5b901f8acadd3095c73a3b919"
+//! );
+//! ```
+//!
+
+use crate::prelude::*;
+
+/// A point (X : Y : Z : T) in extended twisted Edwards coordinates
+/// representing (X/Z, Y/Z) with T = XY/Z.
+#[derive(Debug, Clone, Copy)]
+pub struct Extended<F: FieldElement> {
+    pub x: F,
+    pub y: F,
+    pub z: F,
+    pub t: F,
+}
+
+pub trait TwistedEdwardsCurve {
+    type Field: PrimeField;
+    type Scalar: PrimeField;
+
+    /// log2 of the cofactor.
+    const COFACTOR_BITS: usize;
+
+    fn a() -> Self::Field;
+    fn d() -> Self::Field;
+    fn base_point() -> Extended<Self::Field>;
+
+    fn identity() -> Extended<Self::Field> {
+        Self::from_affine(Self::Field::zero(), Self::Field::one())
+    }
+
+    fn from_affine(x: Self::Field, y: Self::Field) -> Extended<Self::Field> {
+        Extended {
+            x,
+            y,
+            z: Self::Field::one(),
+            t: x * y,
+        }
+    }
+
+    fn to_affine(p: Extended<Self::Field>) -> (Self::Field, Self::Field) {
+        let z_inv = p.z.inv();
+        (p.x * z_inv, p.y * z_inv)
+    }
+
+    fn is_on_curve(p: Extended<Self::Field>) -> bool {
+        let (x, y) = Self::to_affine(p);
+        let xx = x.square();
+        let yy = y.square();
+        Self::a() * xx + yy == Self::Field::one() + Self::d() * xx * yy
+            && p.x * p.y == p.z * p.t
+    }
+
+    /// Check if two points are equal, i.e. X1 Z2 = X2 Z1 and Y1 Z2 = Y2 Z1.
+    fn point_eq(p: Extended<Self::Field>, q: Extended<Self::Field>) -> bool {
+        p.x * q.z == q.x * p.z && p.y * q.z == q.y * p.z
+    }
+
+    fn neg(p: Extended<Self::Field>) -> Extended<Self::Field> {
+        Extended {
+            x: Self::Field::zero() - p.x,
+            y: p.y,
+            z: p.z,
+            t: Self::Field::zero() - p.t,
+        }
+    }
+
+    /// Unified addition (add-2008-hwcd).
+    /// This is complete if `a` is a square and `d` is not.
+    fn add(p: Extended<Self::Field>, q: Extended<Self::Field>) -> Extended<Self::Field> {
+        let a = p.x * q.x;
+        let b = p.y * q.y;
+        let c = p.t * Self::d() * q.t;
+        let d = p.z * q.z;
+        let e = (p.x + p.y) * (q.x + q.y) - a - b;
+        let f = d - c;
+        let g = d + c;
+        let h = b - Self::a() * a;
+        Extended {
+            x: e * f,
+            y: g * h,
+            z: f * g,
+            t: e * h,
+        }
+    }
+
+    /// Doubling (dbl-2008-hwcd).
+    fn double(p: Extended<Self::Field>) -> Extended<Self::Field> {
+        let a = p.x.square();
+        let b = p.y.square();
+        let c = Self::Field::from_literal(2) * p.z.square();
+        let d = Self::a() * a;
+        let e = (p.x + p.y).square() - a - b;
+        let g = d + b;
+        let f = g - c;
+        let h = d - b;
+        Extended {
+            x: e * f,
+            y: g * h,
+            z: f * g,
+            t: e * h,
+        }
+    }
+
+    /// Scalar multiplication with double-and-always-add.
+    /// The sequence of field operations is independent of the scalar.
+    fn scalar_mul(k: Self::Scalar, p: Extended<Self::Field>) -> Extended<Self::Field> {
+        let mut r = Self::identity();
+        for i in (0..Self::Scalar::modulus_bits()).rev() {
+            r = Self::double(r);
+            let s = Self::add(r, p);
+            r = select(r, s, k.bit(i));
+        }
+        r
+    }
+
+    /// Multiply `p` by an arbitrary integer `k`, e.g. the group order.
+    /// **Note** that this is not constant-time.
+    fn mul_biguint(k: &BigUint, p: Extended<Self::Field>) -> Extended<Self::Field> {
+        let mut r = Self::identity();
+        for byte in k.to_bytes_be() {
+            for i in (0..8).rev() {
+                r = Self::double(r);
+                if (byte >> i) & 1 == 1 {
+                    r = Self::add(r, p);
+                }
+            }
+        }
+        r
+    }
+
+    fn mul_by_cofactor(p: Extended<Self::Field>) -> Extended<Self::Field> {
+        let mut r = p;
+        for _ in 0..Self::COFACTOR_BITS {
+            r = Self::double(r);
+        }
+        r
+    }
+
+    /// Returns `true` if `p` is in the small subgroup, i.e. if `p` times the
+    /// cofactor is the identity.
+    fn is_small_order(p: Extended<Self::Field>) -> bool {
+        Self::point_eq(Self::mul_by_cofactor(p), Self::identity())
+    }
+
+    /// Returns `true` if `p` is in the prime order subgroup.
+    fn is_torsion_free(p: Extended<Self::Field>) -> bool {
+        let l = Self::Scalar::modulus_biguint();
+        Self::point_eq(Self::mul_biguint(&l, p), Self::identity())
+    }
+
+    /// Length of compressed points, i.e. enough bytes for y and the sign of x.
+    fn compressed_len() -> usize {
+        (Self::Field::modulus_bits() + 1).div_ceil(8)
+    }
+
+    /// RFC 8032 point encoding: little-endian y with the least significant
+    /// bit of x in the most significant bit.
+    fn compress(p: Extended<Self::Field>) -> Seq<U8> {
+        let (x, y) = Self::to_affine(p);
+        let mut bytes = y.to_biguint().to_bytes_le();
+        bytes.resize(Self::compressed_len(), 0);
+        let last = bytes.len() - 1;
+        bytes[last] |= (x.sgn0() as u8) << 7;
+        Seq::from(bytes.iter().map(|x| U8::classify(*x)).collect::<Vec<U8>>())
+    }
+
+    /// RFC 8032 point decoding.
+    /// Rejects non-canonical y and x = 0 with the sign bit set.
+    fn decompress(s: &Seq<U8>) -> Result<Extended<Self::Field>, EcError> {
+        if s.len() != Self::compressed_len() {
+            return Err(EcError::InvalidEncoding);
+        }
+        let mut bytes = s.iter().map(|x| U8::declassify(*x)).collect::<Vec<u8>>();
+        let last = bytes.len() - 1;
+        let x_0 = bytes[last] >> 7 == 1;
+        bytes[last] &= 0x7f;
+        let y = BigUint::from_bytes_le(&bytes);
+        if y >= Self::Field::modulus_biguint() {
+            return Err(EcError::InvalidEncoding);
+        }
+        let y = Self::Field::from_biguint(&y);
+        let one = Self::Field::one();
+        let yy = y.square();
+        let xx = (yy - one) * (Self::d() * yy - Self::a()).inv();
+        let mut x = xx.sqrt().ok_or(EcError::NotOnCurve)?;
+        if x == Self::Field::zero() && x_0 {
+            return Err(EcError::InvalidEncoding);
+        }
+        if x.sgn0() != x_0 {
+            x = Self::Field::zero() - x;
+        }
+        Ok(Self::from_affine(x, y))
+    }
+}
+
+/// Returns `a` if `bit` is `false` and `b` otherwise without branching on
+/// `bit`.
+fn select<F: FieldElement>(a: Extended<F>, b: Extended<F>, bit: bool) -> Extended<F> {
+    Extended {
+        x: F::select(a.x, b.x, bit),
+        y: F::select(a.y, b.y, bit),
+        z: F::select(a.z, b.z, bit),
+        t: F::select(a.t, b.t, bit),
+    }
+}
+
+/// The absolute value, i.e. `-x` if `x` is negative (odd).
+fn abs<F: FieldElement>(x: F) -> F {
+    F::select(x, F::zero() - x, x.sgn0())
+}
+
+/// ristretto255 (RFC 9496, Section 4) for curves with a = -1, cofactor 8
+/// and p ≡ 5 mod 8.
+pub trait Ristretto: TwistedEdwardsCurve {
+    /// The non-negative square root of -1.
+    fn sqrt_m1() -> Self::Field {
+        abs((Self::Field::zero() - Self::Field::one()).sqrt().unwrap())
+    }
+
+    /// Returns `(was_square, r)` with r the non-negative square root of
+    /// u/v, or of i·u/v if u/v is not a square (`SQRT_RATIO_M1`).
+    fn sqrt_ratio_m1(u: Self::Field, v: Self::Field) -> (bool, Self::Field) {
+        let p = Self::Field::modulus_biguint();
+        let v3 = v.square() * v;
+        let v7 = v3.square() * v;
+        let mut r = (u * v3) * (u * v7).pow_biguint(&((p - 5u32) >> 3));
+        let check = v * r.square();
+        let correct_sign_sqrt = check == u;
+        let flipped_sign_sqrt = check == Self::Field::zero() - u;
+        let flipped_sign_sqrt_i = check == Self::Field::zero() - u * Self::sqrt_m1();
+        r = Self::Field::select(r, Self::sqrt_m1() * r, flipped_sign_sqrt | flipped_sign_sqrt_i);
+        (correct_sign_sqrt | flipped_sign_sqrt, abs(r))
+    }
+
+    fn ristretto_encode(p: Extended<Self::Field>) -> Seq<U8> {
+        let (x0, y0, z0, t0) = (p.x, p.y, p.z, p.t);
+        let u1 = (z0 + y0) * (z0 - y0);
+        let u2 = x0 * y0;
+        let (_, invsqrt) = Self::sqrt_ratio_m1(Self::Field::one(), u1 * u2.square());
+        let den1 = invsqrt * u1;
+        let den2 = invsqrt * u2;
+        let z_inv = den1 * den2 * t0;
+        let ix0 = x0 * Self::sqrt_m1();
+        let iy0 = y0 * Self::sqrt_m1();
+        let (_, invsqrt_a_minus_d) = Self::sqrt_ratio_m1(Self::Field::one(), Self::a() - Self::d());
+        let enchanted_denominator = den1 * invsqrt_a_minus_d;
+        let rotate = (t0 * z_inv).sgn0();
+        let x = Self::Field::select(x0, iy0, rotate);
+        let mut y = Self::Field::select(y0, ix0, rotate);
+        let den_inv = Self::Field::select(den2, enchanted_denominator, rotate);
+        y = Self::Field::select(y, Self::Field::zero() - y, (x * z_inv).sgn0());
+        let s = abs(den_inv * (z0 - y));
+        s.to_byte_seq_le()
+    }
+
+    fn ristretto_decode(s: &Seq<U8>) -> Result<Extended<Self::Field>, EcError> {
+        let s = Self::Field::from_canonical_byte_seq_le(s.clone())
+            .ok_or(EcError::InvalidEncoding)?;
+        if s.sgn0() {
+            return Err(EcError::InvalidEncoding);
+        }
+        let one = Self::Field::one();
+        let ss = s.square();
+        let u1 = one - ss;
+        let u2 = one + ss;
+        let u2_sqr = u2.square();
+        let v = Self::Field::zero() - Self::d() * u1.square() - u2_sqr;
+        let (was_square, invsqrt) = Self::sqrt_ratio_m1(one, v * u2_sqr);
+        let den_x = invsqrt * u2;
+        let den_y = invsqrt * den_x * v;
+        let x = abs(Self::Field::from_literal(2) * s * den_x);
+        let y = u1 * den_y;
+        let t = x * y;
+        if !was_square || t.sgn0() || y == Self::Field::zero() {
+            return Err(EcError::InvalidEncoding);
+        }
+        Ok(Extended { x, y, z: one, t })
+    }
+
+    fn ristretto_eq(p: Extended<Self::Field>, q: Extended<Self::Field>) -> bool {
+        p.x * q.y == p.y * q.x || p.y * q.y == p.x * q.x
+    }
+}
+
+/// decaf448 (RFC 9496, Section 5) for curves with a = 1, cofactor 4 and
+/// p ≡ 3 mod 4.
+pub trait Decaf: TwistedEdwardsCurve {
+    /// Returns `(was_square, r)` with r the non-negative square root of u/v
+    /// if it exists (`SQRT_RATIO_M1`).
+    fn sqrt_ratio_m1(u: Self::Field, v: Self::Field) -> (bool, Self::Field) {
+        let p = Self::Field::modulus_biguint();
+        let r = u * (u * v).pow_biguint(&((p - 3u32) >> 2));
+        let check = v * r.square();
+        (check == u, abs(r))
+    }
+
+    /// The non-negative square root of -d.
+    fn sqrt_minus_d() -> Self::Field {
+        abs((Self::Field::zero() - Self::d()).sqrt().unwrap())
+    }
+
+    fn decaf_encode(p: Extended<Self::Field>) -> Seq<U8> {
+        let (x0, z0, t0) = (p.x, p.z, p.t);
+        let one_minus_d = Self::Field::one() - Self::d();
+        let u1 = (x0 + t0) * (x0 - t0);
+        let (_, invsqrt) = Self::sqrt_ratio_m1(Self::Field::one(), u1 * one_minus_d * x0.square());
+        let ratio = abs(invsqrt * u1 * Self::sqrt_minus_d());
+        let u2 = Self::sqrt_minus_d().inv() * ratio * z0 - t0;
+        let s = abs(one_minus_d * invsqrt * x0 * u2);
+        s.to_byte_seq_le()
+    }
+
+    fn decaf_decode(s: &Seq<U8>) -> Result<Extended<Self::Field>, EcError> {
+        let s = Self::Field::from_canonical_byte_seq_le(s.clone())
+            .ok_or(EcError::InvalidEncoding)?;
+        if s.sgn0() {
+            return Err(EcError::InvalidEncoding);
+        }
+        let one = Self::Field::one();
+        let ss = s.square();
+        let u1 = one + ss;
+        let u2 = u1.square() - Self::Field::from_literal(4) * Self::d() * ss;
+        let (was_square, invsqrt) = Self::sqrt_ratio_m1(one, u2 * u1.square());
+        let u3 = abs(Self::Field::from_literal(2) * s * invsqrt * u1 * Self::sqrt_minus_d());
+        let x = u3 * invsqrt * u2 * Self::sqrt_minus_d().inv();
+        let y = (one - ss) * invsqrt * u1;
+        let t = x * y;
+        if !was_square {
+            return Err(EcError::InvalidEncoding);
+        }
+        Ok(Extended { x, y, z: one, t })
+    }
+
+    fn decaf_eq(p: Extended<Self::Field>, q: Extended<Self::Field>) -> bool {
+        p.x * q.y == p.y * q.x
+    }
+}
+
+unsigned_integer!(Ed25519Canvas, 256);
+field_integer!(
+    Ed25519FieldElement,
+    Ed25519Canvas,
+    Ed25519Canvas::from_bytes_be(&hex_string_to_bytes(
+        "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed"
+    ))
+);
+field_integer!(
+    Ed25519Scalar,
+    Ed25519Canvas,
+    Ed25519Canvas::from_bytes_be(&hex_string_to_bytes(
+        "1000000000000000000000000000000014def9dea2f79cd65812631a5cf5d3ed"
+    ))
+);
+
+/// edwards25519 from RFC 7748 and RFC 8032.
+pub struct Ed25519;
+
+impl TwistedEdwardsCurve for Ed25519 {
+    type Field = Ed25519FieldElement;
+    type Scalar = Ed25519Scalar;
+    const COFACTOR_BITS: usize = 3;
+
+    fn a() -> Ed25519FieldElement {
+        Ed25519FieldElement::from_literal(0) - Ed25519FieldElement::from_literal(1)
+    }
+
+    /// -121665/121666
+    fn d() -> Ed25519FieldElement {
+        Ed25519FieldElement::from_hex("52036cee2b6ffe738cc740797779e89800700a4d4141d8ab75eb4dca135978a3")
+    }
+
+    fn base_point() -> Extended<Ed25519FieldElement> {
+        Ed25519::from_affine(
+            Ed25519FieldElement::from_hex("216936d3cd6e53fec0a4e231fdd6dc5c692cc7609525a7b2c9562d608f25d51a"),
+            Ed25519FieldElement::from_hex("6666666666666666666666666666666666666666666666666666666666666658"),
+        )
+    }
+}
+
+impl Ristretto for Ed25519 {}
+
+unsigned_integer!(Ed448Canvas, 448);
+field_integer!(
+    Ed448FieldElement,
+    Ed448Canvas,
+    Ed448Canvas::from_bytes_be(&hex_string_to_bytes(
+        "fffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffffffffffffffffffffffffffffffffffffffffffffffffffff"
+    ))
+);
+field_integer!(
+    Ed448Scalar,
+    Ed448Canvas,
+    Ed448Canvas::from_bytes_be(&hex_string_to_bytes(
+        "3fffffffffffffffffffffffffffffffffffffffffffffffffffffff7cca23e9c44edb49aed63690216cc2728dc58f552378c292ab5844f3"
+    ))
+);
+
+/// edwards448 from RFC 7748 and RFC 8032.
+pub struct Ed448;
+
+impl TwistedEdwardsCurve for Ed448 {
+    type Field = Ed448FieldElement;
+    type Scalar = Ed448Scalar;
+    const COFACTOR_BITS: usize = 2;
+
+    fn a() -> Ed448FieldElement {
+        Ed448FieldElement::from_literal(1)
+    }
+
+    /// -39081
+    fn d() -> Ed448FieldElement {
+        Ed448FieldElement::from_literal(0) - Ed448FieldElement::from_literal(39081)
+    }
+
+    fn base_point() -> Extended<Ed448FieldElement> {
+        Ed448::from_affine(
+            Ed448FieldElement::from_hex(
+                "4f1970c66bed0ded221d15a622bf36da9e146570470f1767ea6de324a3d3a46412ae1af72ab66511433b80e18b00938e2626a82bc70cc05e"
+            ),
+            Ed448FieldElement::from_hex(
+                "693f46716eb6bc248876203756c9c7624bea73736ca3984087789c1e05a0c2d73ad3ff1ce67c39c4fdbd132c4ed7c8ad9808795bf230fa14"
+            ),
+        )
+    }
+}
+
+impl Decaf for Ed448 {}
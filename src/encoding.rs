@@ -0,0 +1,235 @@
+//!
+//! # Coefficient compression and encoding
+//!
+//! Serialization of coefficient vectors, e.g. the `poly` array of a `poly!`
+//! polynomial, as they are specified in lattice-based schemes.
+//!
+//! * `compress` and `decompress` map between ℤq and ℤ(2^d) with rounding
+//!   (`Compress_d` and `Decompress_d` in ML-KEM).
+//! * `byte_encode` and `byte_decode` pack coefficients in [0, 2^d) into d bits
+//!   each (`ByteEncode_d` and `ByteDecode_d` in ML-KEM).
+//! * `bit_pack` and `bit_unpack` pack signed coefficients in [-a, b] as
+//!   b - x like `BitPack` and `BitUnpack` in ML-DSA.
+//!
+//! Bits are packed little-endian, i.e. the lowest bit of the first
+//! coefficient is the lowest bit of the first byte.
+//! Decoding rejects non-canonical encodings, i.e. coefficients that aren't
+//! smaller than q or outside of [-a, b].
+//!
+//! ```
+//! use hacspec::prelude::*;
+//!
+//! let x = random_poly::<u128>(256, 0, 3329);
+//! let c = compress(x.raw(), 3329, 10);
+//! let bytes = byte_encode(c.raw(), 10).unwrap();
+//! assert_eq!(bytes.len(), 320);
+//! assert_eq!(byte_decode::<u128>(&bytes, 256, 10, 0).unwrap(), c);
+//! ```
+//!
+//! **Note** the encoded bytes are classified, the coefficients aren't.
+//!
+
+use crate::prelude::*;
+
+/// Errors returned when encoding or decoding coefficients.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EncodingError {
+    /// The number of bytes doesn't match the number of coefficients.
+    InvalidLength,
+    /// A coefficient is out of range.
+    NonCanonical,
+    /// The bit width is 0 or larger than 64.
+    InvalidParameters,
+}
+
+/// The value of `x` in (-q/2, q/2] or in ℤ for q = 0.
+fn centered<T: TRestrictions<T>>(x: T, q: T) -> i128 {
+    let zero = T::default();
+    if x < zero {
+        return -(to_u128(zero - x) as i128);
+    }
+    let v = to_u128(x) as i128;
+    let q = to_u128(q) as i128;
+    if q != 0 && v > q / 2 {
+        v - q
+    } else {
+        v
+    }
+}
+
+/// The number of bytes needed for `n` coefficients with `d` bits.
+fn encoded_len(n: usize, d: usize) -> usize {
+    (n * d).div_ceil(8)
+}
+
+/// Pack the `d`-bit values `v` little-endian.
+fn pack(v: &[u128], d: usize) -> ByteSeq {
+    let mut out = Vec::with_capacity(encoded_len(v.len(), d));
+    let mut acc = 0u128;
+    let mut bits = 0;
+    for &x in v.iter() {
+        acc |= x << bits;
+        bits += d;
+        while bits >= 8 {
+            out.push(U8::classify(acc as u8));
+            acc >>= 8;
+            bits -= 8;
+        }
+    }
+    if bits > 0 {
+        out.push(U8::classify(acc as u8));
+    }
+    out.into()
+}
+
+/// Unpack `n` values of `d` bits. Trailing bits have to be zero.
+fn unpack(bytes: &ByteSeq, n: usize, d: usize) -> Result<Vec<u128>, EncodingError> {
+    if bytes.len() != encoded_len(n, d) {
+        return Err(EncodingError::InvalidLength);
+    }
+    let mask = (1u128 << d) - 1;
+    let mut out = Vec::with_capacity(n);
+    let mut acc = 0u128;
+    let mut bits = 0;
+    for &b in bytes.iter() {
+        acc |= (U8::declassify(b) as u128) << bits;
+        bits += 8;
+        while bits >= d && out.len() < n {
+            out.push(acc & mask);
+            acc >>= d;
+            bits -= d;
+        }
+    }
+    if acc != 0 {
+        return Err(EncodingError::NonCanonical);
+    }
+    Ok(out)
+}
+
+fn check_width(d: usize) -> Result<(), EncodingError> {
+    if d == 0 || d > 64 {
+        return Err(EncodingError::InvalidParameters);
+    }
+    Ok(())
+}
+
+/// Compress the coefficients in [0, q) to d bits with ⌈(2^d / q)·x⌋ mod 2^d.
+///
+/// **Panics** if d > 63 or q ≥ 2^64.
+pub fn compress<T: TRestrictions<T>>(x: &[T], q: T, d: usize) -> Seq<T> {
+    let q = to_u128(q);
+    assert!(d < 64 && q >> 64 == 0, "compress only supports d < 64 and q < 2^64");
+    x.iter()
+        .map(|&c| {
+            let c = to_u128(c);
+            T::from_literal((((c << (d + 1)) + q) / (2 * q)) & ((1 << d) - 1))
+        })
+        .collect::<Vec<T>>()
+        .into()
+}
+
+/// Decompress d-bit coefficients to ℤq with ⌈(q / 2^d)·y⌋.
+///
+/// **Panics** if d > 63 or q ≥ 2^64.
+pub fn decompress<T: TRestrictions<T>>(y: &[T], q: T, d: usize) -> Seq<T> {
+    let q = to_u128(q);
+    assert!(d < 64 && q >> 64 == 0, "decompress only supports d < 64 and q < 2^64");
+    y.iter()
+        .map(|&c| T::from_literal((to_u128(c) * q + (1 << d >> 1)) >> d))
+        .collect::<Vec<T>>()
+        .into()
+}
+
+/// Encode the coefficients in [0, 2^d) with d bits each.
+///
+/// Returns `NonCanonical` if a coefficient doesn't fit into d bits.
+pub fn byte_encode<T: TRestrictions<T>>(x: &[T], d: usize) -> Result<ByteSeq, EncodingError> {
+    check_width(d)?;
+    let mut v = Vec::with_capacity(x.len());
+    for &c in x.iter() {
+        if c < T::default() || to_u128(c) >> d != 0 {
+            return Err(EncodingError::NonCanonical);
+        }
+        v.push(to_u128(c));
+    }
+    Ok(pack(&v, d))
+}
+
+/// Decode `n` coefficients of d bits each.
+/// If q isn't 0 the coefficients have to be smaller than q, e.g. for the
+/// modulus check of ML-KEM encapsulation keys with d = 12.
+///
+/// Returns `InvalidLength` if `bytes` doesn't have ⌈n·d / 8⌉ bytes and
+/// `NonCanonical` if a coefficient isn't smaller than q or the padding bits
+/// aren't zero.
+pub fn byte_decode<T: TRestrictions<T>>(
+    bytes: &ByteSeq,
+    n: usize,
+    d: usize,
+    q: T,
+) -> Result<Seq<T>, EncodingError> {
+    check_width(d)?;
+    let q = to_u128(q);
+    let v = unpack(bytes, n, d)?;
+    if q != 0 && v.iter().any(|&c| c >= q) {
+        return Err(EncodingError::NonCanonical);
+    }
+    Ok(v.iter().map(|&c| T::from_literal(c)).collect::<Vec<T>>().into())
+}
+
+/// The number of bits needed for coefficients in [-a, b].
+fn signed_width(a: u128, b: u128) -> usize {
+    (u128::BITS - (a + b).leading_zeros()) as usize
+}
+
+/// Pack the coefficients in [-a, b] as b - x with bitlen(a + b) bits each.
+/// The coefficients are taken in (-q/2, q/2] if q isn't 0, or as signed
+/// integers for q = 0.
+///
+/// Returns `NonCanonical` if a coefficient is outside of [-a, b].
+pub fn bit_pack<T: TRestrictions<T>>(x: &[T], a: u128, b: u128, q: T) -> Result<ByteSeq, EncodingError> {
+    let d = signed_width(a, b);
+    check_width(d)?;
+    let mut v = Vec::with_capacity(x.len());
+    for &c in x.iter() {
+        let c = centered(c, q);
+        if c < -(a as i128) || c > b as i128 {
+            return Err(EncodingError::NonCanonical);
+        }
+        v.push((b as i128 - c) as u128);
+    }
+    Ok(pack(&v, d))
+}
+
+/// Unpack `n` coefficients packed with `bit_pack`.
+/// The coefficients are returned in [0, q) or as signed integers for q = 0.
+///
+/// Returns `InvalidLength` if `bytes` has the wrong length and
+/// `NonCanonical` if a coefficient is outside of [-a, b], e.g. 2 - 7 for
+/// ML-DSA with η = 2, or the padding bits aren't zero.
+pub fn bit_unpack<T: TRestrictions<T>>(
+    bytes: &ByteSeq,
+    n: usize,
+    a: u128,
+    b: u128,
+    q: T,
+) -> Result<Seq<T>, EncodingError> {
+    let d = signed_width(a, b);
+    check_width(d)?;
+    let q = to_u128(q) as i128;
+    let v = unpack(bytes, n, d)?;
+    if v.iter().any(|&c| c > a + b) {
+        return Err(EncodingError::NonCanonical);
+    }
+    Ok(v.iter()
+        .map(|&c| {
+            let c = b as i128 - c as i128;
+            if q == 0 {
+                T::from_signed_literal(c)
+            } else {
+                T::from_literal(c.rem_euclid(q) as u128)
+            }
+        })
+        .collect::<Vec<T>>()
+        .into())
+}
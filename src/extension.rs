@@ -0,0 +1,326 @@
+//!
+//! # Extension fields
+//!
+//! `quadratic_extension!` defines the field F[u]/(u² - β) and
+//! `cubic_extension!` the field F[v]/(v³ - ξ) over any base field F
+//! implementing `FieldElement`, i.e. a prime field defined with
+//! `field_integer!` or another extension field.
+//! Towers such as Fp2 ⊂ Fp6 ⊂ Fp12 for pairing-friendly curves are built by
+//! nesting them.
+//! The non-residue β (or ξ) is given as expression of the base field type.
+//!
+//! All extension fields implement `FieldElement` including the Frobenius
+//! endomorphism, inversion and square roots.
+//!
+//! ```
+//! use hacspec::prelude::*;
+//!
+//! unsigned_integer!(Canvas, 64);
+//! field_integer!(Fp, Canvas, Canvas::from_literal(103));
+//! // u² = -1
+//! quadratic_extension!(Fp2, Fp, Fp::from_literal(0) - Fp::from_literal(1));
+//! // v³ = u + 1
+//! cubic_extension!(Fp6, Fp2, Fp2::new(Fp::from_literal(1), Fp::from_literal(1)));
+//!
+//! let a = Fp6::new(Fp2::from_literal(5), Fp2::new(Fp::from_literal(3), Fp::from_literal(7)), Fp2::one());
+//! assert_eq!(a * a.inv(), Fp6::one());
+//! assert_eq!(a.frobenius(1), a.pow_biguint(&Fp6::characteristic()));
+//! ```
+//!
+
+/// Define the quadratic extension `$name` = `$base`[u]/(u² - β) for the
+/// non-square β given as `$non_residue`.
+#[macro_export]
+macro_rules! quadratic_extension {
+    ($name:ident, $base:ty, $non_residue:expr) => {
+        /// The element c0 + c1·u.
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        pub struct $name {
+            pub c0: $base,
+            pub c1: $base,
+        }
+
+        impl $name {
+            pub fn new(c0: $base, c1: $base) -> $name {
+                $name { c0, c1 }
+            }
+
+            /// The non-residue β = u².
+            pub fn non_residue() -> $base {
+                $non_residue
+            }
+
+            pub fn from_base(c0: $base) -> $name {
+                $name::new(c0, <$base as FieldElement>::zero())
+            }
+
+            /// Multiply with an element of the base field.
+            pub fn mul_base(self, b: $base) -> $name {
+                $name::new(self.c0 * b, self.c1 * b)
+            }
+
+            /// The conjugate c0 - c1·u.
+            pub fn conjugate(self) -> $name {
+                $name::new(self.c0, <$base as FieldElement>::zero() - self.c1)
+            }
+
+            /// The norm c0² - β·c1² in the base field.
+            pub fn norm(self) -> $base {
+                self.c0.square() - $name::non_residue() * self.c1.square()
+            }
+
+            /// u^(p - 1) = β^((p - 1) / 2)
+            fn frobenius_coefficient() -> $base {
+                static COEFFICIENT: std::sync::OnceLock<$base> = std::sync::OnceLock::new();
+                *COEFFICIENT.get_or_init(|| {
+                    let e = (<$base as FieldElement>::characteristic() - 1u32) / 2u32;
+                    $name::non_residue().pow_biguint(&e)
+                })
+            }
+        }
+
+        impl Add for $name {
+            type Output = $name;
+            fn add(self, rhs: $name) -> $name {
+                $name::new(self.c0 + rhs.c0, self.c1 + rhs.c1)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = $name;
+            fn sub(self, rhs: $name) -> $name {
+                $name::new(self.c0 - rhs.c0, self.c1 - rhs.c1)
+            }
+        }
+
+        impl Mul for $name {
+            type Output = $name;
+            fn mul(self, rhs: $name) -> $name {
+                let v0 = self.c0 * rhs.c0;
+                let v1 = self.c1 * rhs.c1;
+                $name::new(
+                    v0 + $name::non_residue() * v1,
+                    (self.c0 + self.c1) * (rhs.c0 + rhs.c1) - v0 - v1,
+                )
+            }
+        }
+
+        impl FieldElement for $name {
+            fn zero() -> Self {
+                $name::from_base(<$base as FieldElement>::zero())
+            }
+            fn one() -> Self {
+                $name::from_base(<$base as FieldElement>::one())
+            }
+            fn from_literal(x: u128) -> Self {
+                $name::from_base(<$base as FieldElement>::from_literal(x))
+            }
+            fn inv(self) -> Self {
+                self.conjugate().mul_base(self.norm().inv())
+            }
+            fn sqrt(self) -> Option<Self> {
+                if !self.is_square() {
+                    return None;
+                }
+                // All elements of the base field are squares.
+                let mut k = 0;
+                let z = loop {
+                    let z = $name::new(
+                        <$base as FieldElement>::from_literal(k),
+                        <$base as FieldElement>::one(),
+                    );
+                    if !z.is_square() {
+                        break z;
+                    }
+                    k += 1;
+                };
+                sqrt_tonelli_shanks(self, z)
+            }
+            fn is_square(self) -> bool {
+                self.norm().is_square()
+            }
+            fn sgn0(self) -> bool {
+                self.c0.sgn0() || (self.c0 == <$base as FieldElement>::zero() && self.c1.sgn0())
+            }
+            fn characteristic() -> BigUint {
+                <$base as FieldElement>::characteristic()
+            }
+            fn degree() -> usize {
+                2 * <$base as FieldElement>::degree()
+            }
+            fn frobenius(self, power: usize) -> Self {
+                let mut r = self;
+                for _ in 0..power % Self::degree() {
+                    r = $name::new(
+                        r.c0.frobenius(1),
+                        r.c1.frobenius(1) * $name::frobenius_coefficient(),
+                    );
+                }
+                r
+            }
+        }
+    };
+}
+
+/// Define the cubic extension `$name` = `$base`[v]/(v³ - ξ) for the
+/// non-cube ξ given as `$non_residue`.
+#[macro_export]
+macro_rules! cubic_extension {
+    ($name:ident, $base:ty, $non_residue:expr) => {
+        /// The element c0 + c1·v + c2·v².
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        pub struct $name {
+            pub c0: $base,
+            pub c1: $base,
+            pub c2: $base,
+        }
+
+        impl $name {
+            pub fn new(c0: $base, c1: $base, c2: $base) -> $name {
+                $name { c0, c1, c2 }
+            }
+
+            /// The non-residue ξ = v³.
+            pub fn non_residue() -> $base {
+                $non_residue
+            }
+
+            pub fn from_base(c0: $base) -> $name {
+                let zero = <$base as FieldElement>::zero();
+                $name::new(c0, zero, zero)
+            }
+
+            /// Multiply with an element of the base field.
+            pub fn mul_base(self, b: $base) -> $name {
+                $name::new(self.c0 * b, self.c1 * b, self.c2 * b)
+            }
+
+            /// The norm c0³ + ξ·c1³ + ξ²·c2³ - 3ξ·c0·c1·c2 in the base field.
+            pub fn norm(self) -> $base {
+                let xi = $name::non_residue();
+                let three = <$base as FieldElement>::from_literal(3);
+                self.c0.square() * self.c0 + xi * self.c1.square() * self.c1
+                    + xi.square() * self.c2.square() * self.c2
+                    - three * xi * self.c0 * self.c1 * self.c2
+            }
+
+            /// The coefficients of v^p and v^(2p) and whether they are
+            /// multiples of v² and v (p = 2 mod 3) instead of v and v²
+            /// (p = 1 mod 3).
+            fn frobenius_coefficients() -> ($base, $base, bool) {
+                static COEFFICIENTS: std::sync::OnceLock<($base, $base, bool)> =
+                    std::sync::OnceLock::new();
+                *COEFFICIENTS.get_or_init(|| {
+                    let p = <$base as FieldElement>::characteristic();
+                    let xi = $name::non_residue();
+                    if ((&p + 1u32) % 3u32).is_zero() {
+                        // v^p = ξ^((p - 2) / 3) v², v^(2p) = ξ^((2p - 1) / 3) v
+                        let e1 = (&p - 2u32) / 3u32;
+                        let e2 = (&p * 2u32 - 1u32) / 3u32;
+                        (xi.pow_biguint(&e1), xi.pow_biguint(&e2), true)
+                    } else {
+                        // v^p = ξ^((p - 1) / 3) v, v^(2p) = ξ^((2p - 2) / 3) v²
+                        let gamma = xi.pow_biguint(&((p - 1u32) / 3u32));
+                        (gamma, gamma.square(), false)
+                    }
+                })
+            }
+        }
+
+        impl Add for $name {
+            type Output = $name;
+            fn add(self, rhs: $name) -> $name {
+                $name::new(self.c0 + rhs.c0, self.c1 + rhs.c1, self.c2 + rhs.c2)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = $name;
+            fn sub(self, rhs: $name) -> $name {
+                $name::new(self.c0 - rhs.c0, self.c1 - rhs.c1, self.c2 - rhs.c2)
+            }
+        }
+
+        impl Mul for $name {
+            type Output = $name;
+            fn mul(self, rhs: $name) -> $name {
+                let xi = $name::non_residue();
+                let v0 = self.c0 * rhs.c0;
+                let v1 = self.c1 * rhs.c1;
+                let v2 = self.c2 * rhs.c2;
+                $name::new(
+                    v0 + xi * ((self.c1 + self.c2) * (rhs.c1 + rhs.c2) - v1 - v2),
+                    (self.c0 + self.c1) * (rhs.c0 + rhs.c1) - v0 - v1 + xi * v2,
+                    (self.c0 + self.c2) * (rhs.c0 + rhs.c2) - v0 + v1 - v2,
+                )
+            }
+        }
+
+        impl FieldElement for $name {
+            fn zero() -> Self {
+                $name::from_base(<$base as FieldElement>::zero())
+            }
+            fn one() -> Self {
+                $name::from_base(<$base as FieldElement>::one())
+            }
+            fn from_literal(x: u128) -> Self {
+                $name::from_base(<$base as FieldElement>::from_literal(x))
+            }
+            fn inv(self) -> Self {
+                let xi = $name::non_residue();
+                let t0 = self.c0.square() - xi * self.c1 * self.c2;
+                let t1 = xi * self.c2.square() - self.c0 * self.c1;
+                let t2 = self.c1.square() - self.c0 * self.c2;
+                let det = self.c0 * t0 + xi * (self.c2 * t1 + self.c1 * t2);
+                $name::new(t0, t1, t2).mul_base(det.inv())
+            }
+            fn sqrt(self) -> Option<Self> {
+                if !self.is_square() {
+                    return None;
+                }
+                let mut k = 0;
+                let z = loop {
+                    let z = $name::new(
+                        <$base as FieldElement>::from_literal(k),
+                        <$base as FieldElement>::one(),
+                        <$base as FieldElement>::zero(),
+                    );
+                    if !z.is_square() {
+                        break z;
+                    }
+                    k += 1;
+                };
+                sqrt_tonelli_shanks(self, z)
+            }
+            fn is_square(self) -> bool {
+                self.norm().is_square()
+            }
+            fn sgn0(self) -> bool {
+                let zero = <$base as FieldElement>::zero();
+                self.c0.sgn0()
+                    || (self.c0 == zero && (self.c1.sgn0() || (self.c1 == zero && self.c2.sgn0())))
+            }
+            fn characteristic() -> BigUint {
+                <$base as FieldElement>::characteristic()
+            }
+            fn degree() -> usize {
+                3 * <$base as FieldElement>::degree()
+            }
+            fn frobenius(self, power: usize) -> Self {
+                let (gamma1, gamma2, swapped) = $name::frobenius_coefficients();
+                let mut r = self;
+                for _ in 0..power % Self::degree() {
+                    let c0 = r.c0.frobenius(1);
+                    let c1 = r.c1.frobenius(1) * gamma1;
+                    let c2 = r.c2.frobenius(1) * gamma2;
+                    r = if swapped {
+                        $name::new(c0, c2, c1)
+                    } else {
+                        $name::new(c0, c1, c2)
+                    };
+                }
+                r
+            }
+        }
+    };
+}
@@ -0,0 +1,237 @@
+//!
+//! # Irreducibility and factorization over ℤp
+//!
+//! This module tests polynomials over a prime field ℤp for irreducibility
+//! and factors them, e.g. to check the modulus of an extension field or to
+//! find roots.
+//!
+//! * `poly_is_irreducible` is Rabin's irreducibility test.
+//! * `poly_ddf` is the distinct-degree factorization of a squarefree
+//!   polynomial into the products of all irreducible factors of equal degree.
+//! * `poly_edf` is the equal-degree factorization of Cantor and Zassenhaus.
+//! * `poly_roots` finds all roots in ℤp.
+//!
+//! ```
+//! use hacspec::prelude::*;
+//!
+//! // x^3 + 2x + 1 is irreducible over ℤ3, x^3 + 2x = x(x + 1)(x + 2) isn't.
+//! assert!(poly_is_irreducible(&[1u128, 2, 0, 1], 3).unwrap());
+//! assert!(!poly_is_irreducible(&[0u128, 2, 0, 1], 3).unwrap());
+//! assert_eq!(poly_roots(&[0u128, 2, 0, 1], 3).unwrap(), vec![0, 1, 2]);
+//! ```
+//!
+//! **Note** p has to be prime. The equal-degree factorization and
+//! root finding are randomized and use `thread_rng`.
+//!
+
+use crate::prelude::*;
+
+/// `a mod f` over ℤp for a monic f without trailing zeros.
+fn rem_monic<T: TRestrictions<T>>(a: &[T], f: &[T], p: T) -> Vec<T> {
+    let zero = T::default();
+    let d = f.len() - 1;
+    let mut r = a.to_vec();
+    for i in (d..r.len()).rev() {
+        let c = r[i];
+        if c != zero {
+            for j in 0..d {
+                r[i - d + j] = r[i - d + j].sub_mod(c.mul_mod(f[j], p), p);
+            }
+            r[i] = zero;
+        }
+    }
+    r.truncate(std::cmp::max(d, 1));
+    r
+}
+
+/// `a·b mod f` over ℤp for a monic f.
+fn mul_mod_poly<T: TRestrictions<T>>(a: &[T], b: &[T], f: &[T], p: T) -> Vec<T> {
+    rem_monic(&poly_mul(a, b, p), f, p)
+}
+
+/// `a^e mod f` over ℤp for a monic f with square-and-multiply.
+fn pow_mod_poly<T: TRestrictions<T>>(a: &[T], e: T, f: &[T], p: T) -> Vec<T> {
+    let (zero, one, two) = (T::default(), T::from_literal(1), T::from_literal(2));
+    let mut result = rem_monic(&[one], f, p);
+    let mut base = rem_monic(a, f, p);
+    let mut e = e;
+    while e != zero {
+        if e - (e / two) * two == one {
+            result = mul_mod_poly(&result, &base, f, p);
+        }
+        base = mul_mod_poly(&base, &base, f, p);
+        e = e / two;
+    }
+    result
+}
+
+/// The polynomial x.
+fn x<T: TRestrictions<T>>() -> Vec<T> {
+    vec![T::default(), T::from_literal(1)]
+}
+
+fn is_one<T: TRestrictions<T>>(a: &[T]) -> bool {
+    truncate(a) == [T::from_literal(1)]
+}
+
+fn degree<T: TRestrictions<T>>(a: &[T]) -> usize {
+    leading_coefficient(a).0
+}
+
+/// The distinct prime factors of `n`.
+fn prime_factors(mut n: usize) -> Vec<usize> {
+    let mut factors = vec![];
+    let mut q = 2;
+    while q * q <= n {
+        if n.is_multiple_of(q) {
+            factors.push(q);
+            while n.is_multiple_of(q) {
+                n /= q;
+            }
+        }
+        q += 1;
+    }
+    if n > 1 {
+        factors.push(n);
+    }
+    factors
+}
+
+/// Rabin's irreducibility test for f of degree n over ℤp: f is irreducible
+/// if and only if x^(p^n) = x mod f and gcd(x^(p^(n/q)) - x, f) = 1 for all
+/// prime divisors q of n.
+///
+/// Constants aren't irreducible.
+pub fn poly_is_irreducible<T: TRestrictions<T>>(f: &[T], p: T) -> Result<bool, PolyError> {
+    let n = degree(f);
+    if n == 0 {
+        return Ok(false);
+    }
+    let f = poly_monic(f, p)?;
+    // x^(p^i) mod f for i = 0, ..., n
+    let mut powers = vec![rem_monic(&x(), &f, p)];
+    for i in 0..n {
+        powers.push(pow_mod_poly(&powers[i], p, &f, p));
+    }
+    for q in prime_factors(n) {
+        let h = poly_sub(&powers[n / q], &x(), p);
+        if !is_one(&poly_gcd(&h, &f, p)?) {
+            return Ok(false);
+        }
+    }
+    Ok(truncate(&powers[n]) == truncate(&powers[0]))
+}
+
+/// Distinct-degree factorization of a squarefree polynomial f over ℤp.
+/// Returns the pairs `(g, d)` where g is the monic product of all
+/// irreducible factors of f with degree d.
+///
+/// Returns `NotSquarefree` if f has a repeated factor.
+pub fn poly_ddf<T: TRestrictions<T>>(f: &[T], p: T) -> Result<Vec<(Vec<T>, usize)>, PolyError> {
+    let mut f = poly_monic(f, p)?;
+    if degree(&f) > 0 && !is_one(&poly_gcd(&f, &poly_derivative(&f, p), p)?) {
+        return Err(PolyError::NotSquarefree);
+    }
+    let mut factors = vec![];
+    let mut h = x();
+    let mut d = 1;
+    while degree(&f) >= 2 * d {
+        // h = x^(p^d) mod f
+        h = pow_mod_poly(&h, p, &f, p);
+        let g = poly_gcd(&f, &poly_sub(&h, &x(), p), p)?;
+        if !is_one(&g) {
+            f = poly_divrem(&f, &g, p)?.0;
+            h = rem_monic(&h, &f, p);
+            factors.push((g, d));
+        }
+        d += 1;
+    }
+    if degree(&f) > 0 {
+        let d = degree(&f);
+        factors.push((f, d));
+    }
+    Ok(factors)
+}
+
+/// A random polynomial of degree less than `n` over ℤp.
+fn random_element<T: TRestrictions<T>>(n: usize, p: T) -> Vec<T> {
+    random_poly::<T>(n, 0, to_u128(p) as i128).raw().to_vec()
+}
+
+/// Equal-degree factorization (Cantor–Zassenhaus) of f over ℤp, which has
+/// to be the product of distinct irreducible factors of degree d, e.g. the
+/// output of `poly_ddf`.
+/// Returns the monic irreducible factors in no particular order.
+///
+/// Returns `NotEqualDegree` if f isn't such a product, which is checked with
+/// `poly_ddf` first.
+pub fn poly_edf<T: TRestrictions<T>>(f: &[T], d: usize, p: T) -> Result<Vec<Vec<T>>, PolyError> {
+    let f = poly_monic(f, p)?;
+    let n = degree(&f);
+    // Otherwise no random splitting would ever find n/d factors.
+    match poly_ddf(&f, p)?.as_slice() {
+        [(_, e)] if *e == d => (),
+        _ => return Err(PolyError::NotEqualDegree),
+    }
+    let one = T::from_literal(1);
+    let two = T::from_literal(2);
+    let odd = p - (p / two) * two == one;
+
+    let mut factors = vec![f.clone()];
+    while factors.len() < n / d {
+        let a = random_element(n, p);
+        let b = if odd {
+            // a^((p^d - 1)/2) = Π_(j < d) (a^((p - 1)/2))^(p^j)
+            let mut c = pow_mod_poly(&a, (p - one) / two, &f, p);
+            let mut b = c.clone();
+            for _ in 1..d {
+                c = pow_mod_poly(&c, p, &f, p);
+                b = mul_mod_poly(&b, &c, &f, p);
+            }
+            poly_sub(&b, &[one], p)
+        } else {
+            // The trace a + a^2 + ... + a^(2^(d - 1)) for p = 2
+            let mut c = rem_monic(&a, &f, p);
+            let mut b = c.clone();
+            for _ in 1..d {
+                c = mul_mod_poly(&c, &c, &f, p);
+                b = poly_add(&b, &c, p);
+            }
+            b
+        };
+        let mut next = vec![];
+        for u in factors.into_iter() {
+            if degree(&u) > d {
+                let g = poly_gcd(&b, &u, p)?;
+                if !is_one(&g) && degree(&g) < degree(&u) {
+                    next.push(poly_divrem(&u, &g, p)?.0);
+                    next.push(g);
+                    continue;
+                }
+            }
+            next.push(u);
+        }
+        factors = next;
+    }
+    Ok(factors)
+}
+
+/// All distinct roots of f in ℤp in ascending order.
+pub fn poly_roots<T: TRestrictions<T>>(f: &[T], p: T) -> Result<Vec<T>, PolyError> {
+    let f = poly_monic(f, p)?;
+    if degree(&f) == 0 {
+        return Ok(vec![]);
+    }
+    // The product of all distinct linear factors gcd(f, x^p - x)
+    let xp = pow_mod_poly(&x(), p, &f, p);
+    let g = poly_gcd(&f, &poly_sub(&xp, &x(), p), p)?;
+    if degree(&g) == 0 {
+        return Ok(vec![]);
+    }
+    let mut roots: Vec<T> = poly_edf(&g, 1, p)?
+        .iter()
+        .map(|l| T::default().sub_mod(l[0], p))
+        .collect();
+    roots.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    Ok(roots)
+}
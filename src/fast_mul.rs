@@ -0,0 +1,235 @@
+//!
+//! # Sub-quadratic polynomial multiplication
+//!
+//! This module implements Karatsuba and Toom-Cook multiplication of
+//! polynomials over ℤn (or ℤ for n = 0) for any coefficient type implementing
+//! `Integer`, i.e. also for moduli that don't allow an NTT such as the
+//! powers of two used in Saber and NTRU.
+//!
+//! Toom-k evaluates at ∞ and the 2k - 2 consecutive points -(k - 2), ..., k - 1
+//! and interpolates with divided differences.
+//! All divisions in the interpolation are exact over ℤ. To also make them
+//! exact modulo n, where the divisors don't have to be invertible, the
+//! evaluation and interpolation are computed modulo n·(2k - 3)! and the
+//! result is reduced modulo n at the end.
+//! If n·(2k - 3)! doesn't fit into the coefficient type, e.g. in deeper
+//! recursion levels over `u16`, the divisions are multiplications with the
+//! inverses modulo n if they exist and Karatsuba is used otherwise.
+//!
+//! `MulStrategy` selects the algorithm and can be passed to `poly!`.
+//! `poly_mul_with` cross-checks the fast algorithms against the schoolbook
+//! multiplication in debug builds.
+//!
+//! ```
+//! use hacspec::prelude::*;
+//!
+//! let a = random_poly::<u128>(256, 0, 8192);
+//! let b = random_poly::<u128>(256, 0, 8192);
+//! let expected = poly_mul(a.raw(), b.raw(), 8192);
+//! assert_eq!(poly_mul_with(a.raw(), b.raw(), 8192, MulStrategy::ToomCook4), expected);
+//! ```
+//!
+//! **Note** over ℤ (n = 0) Toom-Cook needs signed coefficients because the
+//! interpolation has negative intermediate values.
+//!
+
+use crate::prelude::*;
+
+/// Below this length Karatsuba uses schoolbook multiplication.
+pub const KARATSUBA_THRESHOLD: usize = 16;
+
+/// Below this length Toom-Cook uses Karatsuba.
+pub const TOOM_COOK_THRESHOLD: usize = 48;
+
+/// The polynomial multiplication algorithm.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MulStrategy {
+    /// The sparse multiplication `poly_mul`.
+    Sparse,
+    /// Operand scanning.
+    Schoolbook,
+    Karatsuba,
+    ToomCook3,
+    ToomCook4,
+}
+
+/// Multiply `x` and `y` over ℤn with the given `strategy`.
+/// The result has `x.len() + y.len()` coefficients like `poly_mul`.
+///
+/// In debug builds the result of Karatsuba and Toom-Cook is checked against
+/// the schoolbook multiplication.
+pub fn poly_mul_with<T: TRestrictions<T>>(x: &[T], y: &[T], n: T, strategy: MulStrategy) -> Vec<T> {
+    let r = match strategy {
+        MulStrategy::Sparse => return poly_mul(x, y, n),
+        MulStrategy::Schoolbook => return poly_mul_op_scanning(x, y, n),
+        MulStrategy::Karatsuba => poly_mul_karatsuba(x, y, n),
+        MulStrategy::ToomCook3 => poly_mul_toom_cook(x, y, n, 3),
+        MulStrategy::ToomCook4 => poly_mul_toom_cook(x, y, n, 4),
+    };
+    debug_assert_eq!(r, poly_mul_op_scanning(x, y, n), "{:?} multiplication is wrong", strategy);
+    r
+}
+
+/// Karatsuba multiplication over ℤn.
+pub fn poly_mul_karatsuba<T: TRestrictions<T>>(x: &[T], y: &[T], n: T) -> Vec<T> {
+    let l = x.len() + y.len();
+    let m = std::cmp::max(x.len(), y.len());
+    if m < KARATSUBA_THRESHOLD {
+        return poly_mul_op_scanning(x, y, n);
+    }
+    let h = m.div_ceil(2);
+    let (x0, x1) = split(x, h);
+    let (y0, y1) = split(y, h);
+
+    let z0 = poly_mul_karatsuba(&x0, &y0, n);
+    let z2 = poly_mul_karatsuba(&x1, &y1, n);
+    let z1 = poly_mul_karatsuba(&poly_add(&x0, &x1, n), &poly_add(&y0, &y1, n), n);
+    // (x0 + x1)(y0 + y1) - x0·y0 - x1·y1 = x0·y1 + x1·y0
+    let z1 = poly_sub(&poly_sub(&z1, &z0, n), &z2, n);
+
+    let mut out = vec![T::default(); 2 * m + 2];
+    add_shifted(&mut out, &z0, 0, n);
+    add_shifted(&mut out, &z1, h, n);
+    add_shifted(&mut out, &z2, 2 * h, n);
+    out.truncate(l);
+    out
+}
+
+/// Toom-k multiplication over ℤn for k ≥ 2.
+///
+/// Falls back to Karatsuba if n·(2k - 3)! doesn't fit into `T` and
+/// 1, ..., 2k - 3 aren't invertible modulo n.
+pub fn poly_mul_toom_cook<T: TRestrictions<T>>(x: &[T], y: &[T], n: T, k: usize) -> Vec<T> {
+    assert!(k >= 2, "Toom-Cook needs at least two limbs");
+    if std::cmp::max(x.len(), y.len()) < TOOM_COOK_THRESHOLD {
+        return poly_mul_karatsuba(x, y, n);
+    }
+    let zero = T::default();
+    let one = T::from_literal(1);
+    let factorial = T::from_literal((1..2 * k - 2).product::<usize>() as u128);
+    if n <= T::max() / factorial {
+        // All divisions of the interpolation are exact modulo n·(2k - 3)!.
+        let out = toom_cook(x, y, n * factorial, k, None);
+        if n == zero {
+            return out;
+        }
+        out.iter().map(|&c| c.rem(n)).collect()
+    } else if gcd(factorial, n) == one {
+        let inverses: Vec<T> = (1..2 * k - 2)
+            .map(|j| extended_euclid_invert(T::from_literal(j as u128), n, false))
+            .collect();
+        toom_cook(x, y, n, k, Some(&inverses))
+    } else {
+        poly_mul_karatsuba(x, y, n)
+    }
+}
+
+/// Toom-k multiplication modulo `m`, where the divisions by j in the
+/// interpolation are either exact or multiplications with `inverses[j - 1]`.
+fn toom_cook<T: TRestrictions<T>>(x: &[T], y: &[T], m: T, k: usize, inverses: Option<&[T]>) -> Vec<T> {
+    let l = x.len() + y.len();
+    let zero = T::default();
+    let h = std::cmp::max(x.len(), y.len()).div_ceil(k);
+    let xs = limbs(x, h, k);
+    let ys = limbs(y, h, k);
+
+    let points: Vec<i128> = (0..2 * k - 2).map(|i| i as i128 - (k as i128 - 2)).collect();
+    let w_inf = poly_mul_toom_cook(&xs[k - 1], &ys[k - 1], m, k);
+    // w(p) - w(∞)·p^(2k - 2) has degree 2k - 3.
+    let mut values: Vec<Vec<T>> = points
+        .iter()
+        .map(|&p| {
+            let w = poly_mul_toom_cook(&evaluate(&xs, p, m), &evaluate(&ys, p, m), m, k);
+            poly_sub(&w, &scale(&w_inf, p.pow(2 * k as u32 - 2), m), m)
+        })
+        .collect();
+
+    // Divided differences with the denominators p_(i + j) - p_i = j.
+    let mut newton = vec![values[0].clone()];
+    for j in 1..values.len() {
+        for i in 0..values.len() - j {
+            values[i] = poly_sub(&values[i + 1], &values[i], m)
+                .iter()
+                .map(|&c| match inverses {
+                    Some(inverses) => c.mul_mod(inverses[j - 1], m),
+                    None => c / T::from_literal(j as u128),
+                })
+                .collect();
+        }
+        newton.push(values[0].clone());
+    }
+
+    // Newton to monomial basis with Horner's rule.
+    let mut coefficients = vec![newton[newton.len() - 1].clone()];
+    for j in (0..newton.len() - 1).rev() {
+        // Multiply with (x - p_j) and add the j-th divided difference.
+        let mut next = vec![vec![zero; 2 * h]; coefficients.len() + 1];
+        for (t, c) in coefficients.iter().enumerate() {
+            next[t + 1] = poly_add(&next[t + 1], c, m);
+            next[t] = poly_sub(&next[t], &scale(c, points[j], m), m);
+        }
+        next[0] = poly_add(&next[0], &newton[j], m);
+        coefficients = next;
+    }
+    coefficients.push(w_inf);
+
+    let mut out = vec![zero; (2 * k) * h];
+    for (i, c) in coefficients.iter().enumerate() {
+        add_shifted(&mut out, c, i * h, m);
+    }
+    out.truncate(l);
+    out
+}
+
+/// Split `x` into the low `h` coefficients and the rest, both of length
+/// `h` (or longer for the high part if `x` has more than 2h coefficients).
+fn split<T: TRestrictions<T>>(x: &[T], h: usize) -> (Vec<T>, Vec<T>) {
+    let mut low = x[..std::cmp::min(h, x.len())].to_vec();
+    low.resize(h, T::default());
+    let mut high = if x.len() > h { x[h..].to_vec() } else { vec![] };
+    high.resize(h, T::default());
+    (low, high)
+}
+
+/// Split `x` into `k` limbs of length `h`.
+fn limbs<T: TRestrictions<T>>(x: &[T], h: usize, k: usize) -> Vec<Vec<T>> {
+    (0..k)
+        .map(|i| {
+            let mut limb = x.iter().skip(i * h).take(h).copied().collect::<Vec<T>>();
+            limb.resize(h, T::default());
+            limb
+        })
+        .collect()
+}
+
+/// `out[shift + i] += v[i]` modulo n.
+fn add_shifted<T: TRestrictions<T>>(out: &mut [T], v: &[T], shift: usize, n: T) {
+    for (i, &c) in v.iter().enumerate() {
+        out[shift + i] = out[shift + i].add_mod(c, n);
+    }
+}
+
+/// Multiply `v` with the small integer `c` modulo n.
+fn scale<T: TRestrictions<T>>(v: &[T], c: i128, n: T) -> Vec<T> {
+    let zero = T::default();
+    let abs = T::from_literal(c.unsigned_abs());
+    v.iter()
+        .map(|&x| {
+            let r = x.mul_mod(abs, n);
+            if c < 0 {
+                zero.sub_mod(r, n)
+            } else {
+                r
+            }
+        })
+        .collect()
+}
+
+/// Evaluate the polynomial with the polynomial coefficients `limbs` at `p`.
+fn evaluate<T: TRestrictions<T>>(limbs: &[Vec<T>], p: i128, n: T) -> Vec<T> {
+    let mut r = vec![T::default(); limbs[0].len()];
+    for limb in limbs.iter().rev() {
+        r = poly_add(&scale(&r, p, n), limb, n);
+    }
+    r
+}
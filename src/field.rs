@@ -0,0 +1,143 @@
+//!
+//! # Field traits
+//!
+//! `FieldElement` is the interface for arithmetic in a finite field that the
+//! generic curve code is written against.
+//! `PrimeField` additionally gives access to the integer representation and
+//! the byte encodings of elements of prime fields.
+//!
+//! Both traits are implemented by all types defined with `field_integer!`.
+//! `FieldElement` is also implemented by the extension fields defined with
+//! `quadratic_extension!` and `cubic_extension!`.
+//!
+
+use crate::prelude::*;
+
+pub trait FieldElement:
+    Copy + Clone + PartialEq + fmt::Debug + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn from_literal(x: u128) -> Self;
+
+    /// Multiplicative inverse. The inverse of 0 is 0.
+    fn inv(self) -> Self;
+
+    /// A square root of `self` if it exists.
+    fn sqrt(self) -> Option<Self>;
+
+    fn is_square(self) -> bool;
+
+    /// `sgn0` as defined in RFC 9380.
+    fn sgn0(self) -> bool;
+
+    /// The characteristic p of the field.
+    fn characteristic() -> BigUint;
+
+    /// The degree of the field over its prime field, i.e. 1 for prime fields.
+    fn degree() -> usize {
+        1
+    }
+
+    /// The number of elements p^degree of the field.
+    fn order() -> BigUint {
+        num::pow(Self::characteristic(), Self::degree())
+    }
+
+    /// The Frobenius endomorphism x ↦ x^(p^power).
+    fn frobenius(self, power: usize) -> Self {
+        self.pow_biguint(&num::pow(Self::characteristic(), power))
+    }
+
+    fn square(self) -> Self {
+        self * self
+    }
+
+    /// Compute `self^exp` with square-and-multiply.
+    fn pow_biguint(self, exp: &BigUint) -> Self {
+        let mut r = Self::one();
+        for byte in exp.to_bytes_be() {
+            for i in (0..8).rev() {
+                r = r.square();
+                if (byte >> i) & 1 == 1 {
+                    r = r * self;
+                }
+            }
+        }
+        r
+    }
+
+    /// Returns `a` if `bit` is `false` and `b` otherwise without branching
+    /// on `bit`.
+    fn select(a: Self, b: Self, bit: bool) -> Self {
+        let bit = Self::from_literal(bit as u128);
+        a + (b - a) * bit
+    }
+}
+
+pub trait PrimeField: FieldElement {
+    fn modulus_biguint() -> BigUint;
+    fn to_biguint(self) -> BigUint;
+    fn from_biguint(x: &BigUint) -> Self;
+
+    /// Number of bytes of the fixed-size encoding.
+    fn byte_len() -> usize;
+    fn to_byte_seq_be(self) -> Seq<U8>;
+    fn to_byte_seq_le(self) -> Seq<U8>;
+    fn from_canonical_byte_seq_be(s: Seq<U8>) -> Option<Self>;
+    fn from_canonical_byte_seq_le(s: Seq<U8>) -> Option<Self>;
+
+    /// Get bit `i` of the integer representation of `self`.
+    fn bit(self, i: usize) -> bool {
+        let bytes = self.to_biguint().to_bytes_le();
+        match bytes.get(i / 8) {
+            Some(b) => (b >> (i % 8)) & 1 == 1,
+            None => false,
+        }
+    }
+
+    /// Number of bits of the modulus.
+    fn modulus_bits() -> usize {
+        Self::modulus_biguint().bits()
+    }
+}
+
+/// Compute a square root of `x` with the Tonelli-Shanks algorithm for any
+/// field, given a non-square `z`.
+/// Returns `None` if `x` is not a square.
+pub fn sqrt_tonelli_shanks<F: FieldElement>(x: F, z: F) -> Option<F> {
+    if x == F::zero() {
+        return Some(x);
+    }
+    // order - 1 = 2^s * q with q odd
+    let mut q = F::order() - 1u32;
+    let mut s = 0;
+    while (&q % 2u32).is_zero() {
+        q >>= 1;
+        s += 1;
+    }
+    let mut m = s;
+    let mut c = z.pow_biguint(&q);
+    let mut t = x.pow_biguint(&q);
+    let mut r = x.pow_biguint(&((q + 1u32) >> 1));
+    while t != F::one() {
+        let mut i = 0;
+        let mut t2i = t;
+        while t2i != F::one() {
+            t2i = t2i.square();
+            i += 1;
+            if i == m {
+                return None;
+            }
+        }
+        let mut b = c;
+        for _ in 0..m - i - 1 {
+            b = b.square();
+        }
+        m = i;
+        c = b.square();
+        t = t * c;
+        r = r * b;
+    }
+    Some(r)
+}
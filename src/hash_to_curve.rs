@@ -0,0 +1,375 @@
+//!
+//! # Hashing to elliptic curves
+//!
+//! This module implements the building blocks of RFC 9380:
+//! `expand_message_xmd` and `expand_message_xof` over a pluggable hash
+//! function or XOF, `hash_to_field`, the simplified SWU and Elligator 2 maps,
+//! and isogeny maps.
+//!
+//! Suites are defined declaratively by implementing `WeierstrassSuite` or
+//! `EdwardsSuite`, i.e. by choosing the curve, the expander, the security
+//! level, and the map.
+//! `hash_to_curve` and `encode_to_curve` are then provided by the trait.
+//!
+//! ```
+//! use hacspec::prelude::*;
+//!
+//! fn xor_hash(msg: &[u8]) -> Vec<u8> {
+//!     // Not a hash function!
+//!     let mut out = vec![0u8; 32];
+//!     for (i, b) in msg.iter().enumerate() {
+//!         out[i % 32] ^= b;
+//!     }
+//!     out
+//! }
+//!
+//! let uniform_bytes = expand_message_xmd(
+//!     &ByteSeq::from("6d7367"),
+//!     &ByteSeq::from("445354"),
+//!     48,
+//!     &xor_hash,
+//!     32,
+//!     64,
+//! )
+//! .unwrap();
+//! assert_eq!(uniform_bytes.len(), 48);
+//! ```
+//!
+
+use crate::prelude::*;
+
+/// Errors returned by `expand_message` and the functions using it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HashToCurveError {
+    /// The requested output is too long for the expander.
+    InvalidLength,
+}
+
+/// The `expand_message` variant of a suite.
+#[derive(Debug, Clone, Copy)]
+pub enum Expander {
+    /// `expand_message_xmd` with a hash function with `b_in_bytes` bytes
+    /// output and input block size `s_in_bytes`, e.g. SHA-256 with 32 and 64.
+    Xmd {
+        hash: fn(&[u8]) -> Vec<u8>,
+        b_in_bytes: usize,
+        s_in_bytes: usize,
+    },
+    /// `expand_message_xof` with an extendable-output function taking the
+    /// input and the output length in bytes.
+    Xof {
+        xof: fn(&[u8], usize) -> Vec<u8>,
+        k: usize,
+    },
+}
+
+fn to_public(s: &ByteSeq) -> Vec<u8> {
+    s.iter().map(|x| U8::declassify(*x)).collect()
+}
+
+fn to_secret(v: &[u8]) -> ByteSeq {
+    ByteSeq::from(v.iter().map(|x| U8::classify(*x)).collect::<Vec<U8>>())
+}
+
+const OVERSIZE_DST_PREFIX: &[u8] = b"H2C-OVERSIZE-DST-";
+
+/// `expand_message_xmd` (RFC 9380, Section 5.3.1).
+/// DSTs longer than 255 bytes are hashed as described in Section 5.3.3.
+pub fn expand_message_xmd<H: Fn(&[u8]) -> Vec<u8>>(
+    msg: &ByteSeq,
+    dst: &ByteSeq,
+    len_in_bytes: usize,
+    hash: &H,
+    b_in_bytes: usize,
+    s_in_bytes: usize,
+) -> Result<ByteSeq, HashToCurveError> {
+    let ell = len_in_bytes.div_ceil(b_in_bytes);
+    if ell > 255 || len_in_bytes > 65535 {
+        return Err(HashToCurveError::InvalidLength);
+    }
+    let mut dst = to_public(dst);
+    if dst.len() > 255 {
+        dst = hash(&[OVERSIZE_DST_PREFIX, &dst].concat());
+    }
+    let mut dst_prime = dst.clone();
+    dst_prime.push(dst.len() as u8);
+
+    let mut msg_prime = vec![0u8; s_in_bytes];
+    msg_prime.extend_from_slice(&to_public(msg));
+    msg_prime.extend_from_slice(&(len_in_bytes as u16).to_be_bytes());
+    msg_prime.push(0);
+    msg_prime.extend_from_slice(&dst_prime);
+
+    let b_0 = hash(&msg_prime);
+    let mut b_i = hash(&[&b_0[..], &[1u8], &dst_prime].concat());
+    let mut uniform_bytes = b_i.clone();
+    for i in 2..=ell {
+        let x = b_0.iter().zip(b_i.iter()).map(|(a, b)| a ^ b).collect::<Vec<u8>>();
+        b_i = hash(&[&x[..], &[i as u8], &dst_prime].concat());
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+    uniform_bytes.truncate(len_in_bytes);
+    Ok(to_secret(&uniform_bytes))
+}
+
+/// `expand_message_xof` (RFC 9380, Section 5.3.2) for the target security
+/// level `k`.
+/// DSTs longer than 255 bytes are hashed as described in Section 5.3.3.
+pub fn expand_message_xof<X: Fn(&[u8], usize) -> Vec<u8>>(
+    msg: &ByteSeq,
+    dst: &ByteSeq,
+    len_in_bytes: usize,
+    xof: &X,
+    k: usize,
+) -> Result<ByteSeq, HashToCurveError> {
+    if len_in_bytes > 65535 {
+        return Err(HashToCurveError::InvalidLength);
+    }
+    let mut dst = to_public(dst);
+    if dst.len() > 255 {
+        dst = xof(&[OVERSIZE_DST_PREFIX, &dst].concat(), (2 * k).div_ceil(8));
+    }
+    let mut msg_prime = to_public(msg);
+    msg_prime.extend_from_slice(&(len_in_bytes as u16).to_be_bytes());
+    msg_prime.extend_from_slice(&dst);
+    msg_prime.push(dst.len() as u8);
+    Ok(to_secret(&xof(&msg_prime, len_in_bytes)))
+}
+
+pub fn expand_message(
+    expander: &Expander,
+    msg: &ByteSeq,
+    dst: &ByteSeq,
+    len_in_bytes: usize,
+) -> Result<ByteSeq, HashToCurveError> {
+    match *expander {
+        Expander::Xmd {
+            hash,
+            b_in_bytes,
+            s_in_bytes,
+        } => expand_message_xmd(msg, dst, len_in_bytes, &hash, b_in_bytes, s_in_bytes),
+        Expander::Xof { xof, k } => expand_message_xof(msg, dst, len_in_bytes, &xof, k),
+    }
+}
+
+/// `hash_to_field` (RFC 9380, Section 5.2) returning `count` elements of
+/// an extension of degree `m` of the prime field `F`, each as a vector of its
+/// `m` coordinates, for the security level `k`.
+pub fn hash_to_field<F: PrimeField>(
+    msg: &ByteSeq,
+    dst: &ByteSeq,
+    count: usize,
+    m: usize,
+    k: usize,
+    expander: &Expander,
+) -> Result<Vec<Vec<F>>, HashToCurveError> {
+    let l = (F::modulus_bits() + k).div_ceil(8);
+    let uniform_bytes = to_public(&expand_message(expander, msg, dst, count * m * l)?);
+    Ok((0..count)
+        .map(|i| {
+            (0..m)
+                .map(|j| {
+                    let offset = l * (j + i * m);
+                    F::from_biguint(&BigUint::from_bytes_be(&uniform_bytes[offset..offset + l]))
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// The simplified Shallue-van de Woestijne-Ulas map (RFC 9380,
+/// Section 6.6.2) to the curve y² = x³ + ax + b with a, b ≠ 0 and the
+/// non-square `z`.
+pub fn map_to_curve_simple_swu<F: FieldElement>(u: F, a: F, b: F, z: F) -> (F, F) {
+    let one = F::one();
+    let uu = u.square();
+    let tv1 = (z.square() * uu.square() + z * uu).inv();
+    let mut x1 = (F::zero() - b) * a.inv() * (one + tv1);
+    if tv1 == F::zero() {
+        x1 = b * (z * a).inv();
+    }
+    let gx1 = x1.square() * x1 + a * x1 + b;
+    let x2 = z * uu * x1;
+    let gx2 = x2.square() * x2 + a * x2 + b;
+    let (x, mut y) = if gx1.is_square() {
+        (x1, gx1.sqrt().unwrap())
+    } else {
+        (x2, gx2.sqrt().unwrap())
+    };
+    if u.sgn0() != y.sgn0() {
+        y = F::zero() - y;
+    }
+    (x, y)
+}
+
+/// The Elligator 2 map (RFC 9380, Section 6.7.1) to the Montgomery curve
+/// K t² = s³ + J s² + s with the non-square `z`, returning `(s, t)`.
+pub fn map_to_curve_elligator2<F: FieldElement>(u: F, j: F, k: F, z: F) -> (F, F) {
+    let c1 = j * k.inv();
+    let c2 = k.square().inv();
+    let mut tv1 = z * u.square();
+    if tv1 == F::zero() - F::one() {
+        tv1 = F::zero();
+    }
+    let x1 = (F::zero() - c1) * (tv1 + F::one()).inv();
+    let gx1 = (x1.square() + c1 * x1 + c2) * x1;
+    let x2 = F::zero() - x1 - c1;
+    let gx2 = tv1 * gx1;
+    let (x, mut y, odd) = if gx1.is_square() {
+        (x1, gx1.sqrt().unwrap(), true)
+    } else {
+        (x2, gx2.sqrt().unwrap(), false)
+    };
+    if y.sgn0() != odd {
+        y = F::zero() - y;
+    }
+    (x * k, y * k)
+}
+
+/// Rational map from a Montgomery curve point `(s, t)` to the twisted
+/// Edwards point (c·s/t, (s - 1)/(s + 1)) (RFC 9380, Appendix D.1).
+/// The exceptional cases are mapped to the identity (0, 1).
+pub fn montgomery_to_edwards<F: FieldElement>(s: F, t: F, c: F) -> (F, F) {
+    let tv1 = s + F::one();
+    if t == F::zero() || tv1 == F::zero() {
+        return (F::zero(), F::one());
+    }
+    (c * s * t.inv(), (s - F::one()) * tv1.inv())
+}
+
+/// An isogeny map (x, y) ↦ (x_num(x) / x_den(x), y · y_num(x) / y_den(x))
+/// with the polynomial coefficients in ascending order of degree
+/// (RFC 9380, Section 6.6.3).
+#[derive(Debug, Clone)]
+pub struct IsogenyMap<F: FieldElement> {
+    pub x_num: Vec<F>,
+    pub x_den: Vec<F>,
+    pub y_num: Vec<F>,
+    pub y_den: Vec<F>,
+}
+
+fn horner<F: FieldElement>(coefficients: &[F], x: F) -> F {
+    coefficients.iter().rev().fold(F::zero(), |acc, c| acc * x + *c)
+}
+
+impl<F: FieldElement> IsogenyMap<F> {
+    /// Map `(x, y)`. Poles of the map are sent to the point at infinity.
+    pub fn map(&self, x: F, y: F) -> Affine<F> {
+        let x_den = horner(&self.x_den, x);
+        let y_den = horner(&self.y_den, x);
+        if x_den == F::zero() || y_den == F::zero() {
+            return Affine::Infinity;
+        }
+        Affine::Point(
+            horner(&self.x_num, x) * x_den.inv(),
+            y * horner(&self.y_num, x) * y_den.inv(),
+        )
+    }
+}
+
+/// The map used by a `WeierstrassSuite`.
+#[derive(Debug, Clone)]
+pub enum WeierstrassMap<F: FieldElement> {
+    /// Simplified SWU with the non-square `z` directly on the curve.
+    Sswu { z: F },
+    /// Simplified SWU with the non-square `z` on the isogenous curve
+    /// y² = x³ + ax + b followed by `iso`, e.g. for curves with a = 0.
+    SswuIsogeny {
+        a: F,
+        b: F,
+        z: F,
+        iso: IsogenyMap<F>,
+    },
+}
+
+/// A hash-to-curve suite for a short Weierstrass curve.
+pub trait WeierstrassSuite {
+    type Field: PrimeField;
+    type Curve: WeierstrassCurve<Field = Self::Field>;
+
+    /// Target security level in bits.
+    const K: usize;
+
+    fn expander() -> Expander;
+    fn map() -> WeierstrassMap<Self::Field>;
+
+    /// Clear the cofactor. The default is for prime order curves.
+    fn clear_cofactor(p: Affine<Self::Field>) -> Affine<Self::Field> {
+        p
+    }
+
+    fn map_to_curve(u: Self::Field) -> Affine<Self::Field> {
+        match Self::map() {
+            WeierstrassMap::Sswu { z } => {
+                let (x, y) = map_to_curve_simple_swu(u, Self::Curve::a(), Self::Curve::b(), z);
+                Affine::Point(x, y)
+            }
+            WeierstrassMap::SswuIsogeny { a, b, z, iso } => {
+                let (x, y) = map_to_curve_simple_swu(u, a, b, z);
+                iso.map(x, y)
+            }
+        }
+    }
+
+    /// Random oracle encoding (`hash_to_curve`).
+    fn hash_to_curve(msg: &ByteSeq, dst: &ByteSeq) -> Result<Affine<Self::Field>, HashToCurveError> {
+        let u = hash_to_field::<Self::Field>(msg, dst, 2, 1, Self::K, &Self::expander())?;
+        let q0 = Self::map_to_curve(u[0][0]);
+        let q1 = Self::map_to_curve(u[1][0]);
+        Ok(Self::clear_cofactor(Self::Curve::add(q0, q1)))
+    }
+
+    /// Nonuniform encoding (`encode_to_curve`).
+    fn encode_to_curve(msg: &ByteSeq, dst: &ByteSeq) -> Result<Affine<Self::Field>, HashToCurveError> {
+        let u = hash_to_field::<Self::Field>(msg, dst, 1, 1, Self::K, &Self::expander())?;
+        Ok(Self::clear_cofactor(Self::map_to_curve(u[0][0])))
+    }
+}
+
+/// Parameters of Elligator 2 for twisted Edwards curves: the Montgomery
+/// curve K t² = s³ + J s² + s, the non-square `z`, and the constant `c` of the
+/// rational map to the Edwards curve.
+#[derive(Debug, Clone, Copy)]
+pub struct Elligator2Map<F: FieldElement> {
+    pub j: F,
+    pub k: F,
+    pub z: F,
+    pub c: F,
+}
+
+/// A hash-to-curve suite for a twisted Edwards curve using Elligator 2.
+pub trait EdwardsSuite {
+    type Field: PrimeField;
+    type Curve: TwistedEdwardsCurve<Field = Self::Field>;
+
+    /// Target security level in bits.
+    const K: usize;
+
+    fn expander() -> Expander;
+    fn map() -> Elligator2Map<Self::Field>;
+
+    fn clear_cofactor(p: Extended<Self::Field>) -> Extended<Self::Field> {
+        Self::Curve::mul_by_cofactor(p)
+    }
+
+    fn map_to_curve(u: Self::Field) -> Extended<Self::Field> {
+        let map = Self::map();
+        let (s, t) = map_to_curve_elligator2(u, map.j, map.k, map.z);
+        let (x, y) = montgomery_to_edwards(s, t, map.c);
+        Self::Curve::from_affine(x, y)
+    }
+
+    /// Random oracle encoding (`hash_to_curve`).
+    fn hash_to_curve(msg: &ByteSeq, dst: &ByteSeq) -> Result<Extended<Self::Field>, HashToCurveError> {
+        let u = hash_to_field::<Self::Field>(msg, dst, 2, 1, Self::K, &Self::expander())?;
+        let q0 = Self::map_to_curve(u[0][0]);
+        let q1 = Self::map_to_curve(u[1][0]);
+        Ok(Self::clear_cofactor(Self::Curve::add(q0, q1)))
+    }
+
+    /// Nonuniform encoding (`encode_to_curve`).
+    fn encode_to_curve(msg: &ByteSeq, dst: &ByteSeq) -> Result<Extended<Self::Field>, HashToCurveError> {
+        let u = hash_to_field::<Self::Field>(msg, dst, 1, 1, Self::K, &Self::expander())?;
+        Ok(Self::clear_cofactor(Self::map_to_curve(u[0][0])))
+    }
+}
@@ -3,9 +3,11 @@ use std::fmt::Debug;
 use std::ops::{Add, Div, Mul, Sub};
 
 use crate::poly::*;
+use num::integer::Integer as NumInteger;
+use num::{BigInt, BigUint, Zero};
 
 /// Trait that needs to be implemented by all integers that are used as coefficients.
-/// This is done here for ℤn over `i128` and `u128`.
+/// This is done here for ℤn over all signed and unsigned Rust integer types.
 pub trait Integer<T> {
     fn from_literal(x: u128) -> T;
     fn from_signed_literal(x: i128) -> T;
@@ -22,6 +24,427 @@ pub trait Integer<T> {
     /// `self % n`
     fn rem(self, n: T) -> T;
     fn abs(self) -> T;
+
+    /// `(self + rhs) % n` for `self` and `rhs` in [0, n) without branching
+    /// on their values.
+    /// **Note** the default implementation calls `add_mod` and is only
+    /// constant-time if `add_mod` is.
+    fn add_mod_ct(self, rhs: T, n: T) -> T
+    where
+        Self: Sized,
+    {
+        self.add_mod(rhs, n)
+    }
+
+    /// `(self - rhs) % n` for `self` and `rhs` in [0, n) without branching
+    /// on their values.
+    /// **Note** the default implementation calls `sub_mod`.
+    fn sub_mod_ct(self, rhs: T, n: T) -> T
+    where
+        Self: Sized,
+    {
+        self.sub_mod(rhs, n)
+    }
+
+    /// `(self * rhs) % n` for `self` and `rhs` in [0, n) without branching
+    /// on their values.
+    /// **Note** the default implementation calls `mul_mod`.
+    fn mul_mod_ct(self, rhs: T, n: T) -> T
+    where
+        Self: Sized,
+    {
+        self.mul_mod(rhs, n)
+    }
+
+    /// `x^e mod n` using square-and-multiply.
+    /// The running time depends on `e`. Use `pow_mod_ct` for secret exponents.
+    fn pow_mod(x: T, e: T, n: T) -> T
+    where
+        T: TRestrictions<T>,
+    {
+        let zero = T::default();
+        let one = T::from_literal(1);
+        let two = T::from_literal(2);
+        let mut base = x.add_mod(zero, n);
+        let mut result = one.add_mod(zero, n);
+        let mut e = e;
+        while e > zero {
+            if e - (e / two) * two == one {
+                result = result.mul_mod(base, n);
+            }
+            base = base.mul_mod(base, n);
+            e = e / two;
+        }
+        result
+    }
+
+    /// `x^e mod n` using a Montgomery ladder.
+    /// The sequence of operations only depends on the bit width of `T`, not
+    /// on the value of `e`, and the ladder only uses the `_ct` operations.
+    ///
+    /// **Note** `n` must not be 0.
+    fn pow_mod_ct(x: T, e: T, n: T) -> T
+    where
+        T: TRestrictions<T>,
+    {
+        debug_assert!(n != T::default());
+        let zero = T::default();
+        let mut r0 = T::from_literal(1).rem(n);
+        let mut r1 = x.add_mod(zero, n);
+        for &bit in bits(e, bit_width::<T>()).iter().rev() {
+            // Swap r0 and r1 if the bit is set without branching on it.
+            let d = bit.mul_mod_ct(r0.sub_mod_ct(r1, n), n);
+            r0 = r0.sub_mod_ct(d, n);
+            r1 = r1.add_mod_ct(d, n);
+
+            r1 = r0.mul_mod_ct(r1, n);
+            r0 = r0.mul_mod_ct(r0, n);
+
+            let d = bit.mul_mod_ct(r0.sub_mod_ct(r1, n), n);
+            r0 = r0.sub_mod_ct(d, n);
+            r1 = r1.add_mod_ct(d, n);
+        }
+        r0
+    }
+
+    /// Legendre symbol (a/p) for an odd prime `p`, computed with Euler's
+    /// criterion. Returns 0, 1, or -1.
+    fn legendre(a: T, p: T) -> i8
+    where
+        T: TRestrictions<T>,
+    {
+        let zero = T::default();
+        let one = T::from_literal(1);
+        let r = T::pow_mod(a, (p - one) / T::from_literal(2), p);
+        if r == zero {
+            0
+        } else if r == one {
+            1
+        } else {
+            -1
+        }
+    }
+
+    /// Jacobi symbol (a/n) for an odd positive `n`. Returns 0, 1, or -1.
+    fn jacobi(a: T, n: T) -> i8
+    where
+        T: TRestrictions<T>,
+    {
+        let zero = T::default();
+        let one = T::from_literal(1);
+        let two = T::from_literal(2);
+        let (three, four, five, eight) = (
+            T::from_literal(3),
+            T::from_literal(4),
+            T::from_literal(5),
+            T::from_literal(8),
+        );
+        let mut a = a.add_mod(zero, n);
+        let mut n = n;
+        let mut result = 1;
+        while a != zero {
+            while (a - (a / two) * two) == zero {
+                a = a / two;
+                let r = n.rem(eight);
+                if r == three || r == five {
+                    result = -result;
+                }
+            }
+            std::mem::swap(&mut a, &mut n);
+            if a.rem(four) == three && n.rem(four) == three {
+                result = -result;
+            }
+            a = a.rem(n);
+        }
+        if n == one {
+            result
+        } else {
+            0
+        }
+    }
+
+    /// Square root of `a` modulo an odd prime `p` using Tonelli–Shanks.
+    /// Returns the smaller of the two roots or `None` if `a` is not a
+    /// quadratic residue.
+    fn sqrt_mod(a: T, p: T) -> Option<T>
+    where
+        T: TRestrictions<T>,
+    {
+        let zero = T::default();
+        let one = T::from_literal(1);
+        let two = T::from_literal(2);
+        let four = T::from_literal(4);
+        let a = a.add_mod(zero, p);
+        if a == zero {
+            return Some(zero);
+        }
+        if T::legendre(a, p) != 1 {
+            return None;
+        }
+
+        let root = if p.rem(four) == T::from_literal(3) {
+            // (p + 1) / 4 without overflowing
+            T::pow_mod(a, p / four + one, p)
+        } else {
+            // p - 1 = q * 2^s
+            let mut q = p - one;
+            let mut s = 0;
+            while q - (q / two) * two == zero {
+                q = q / two;
+                s += 1;
+            }
+            let mut z = two;
+            while T::legendre(z, p) != -1 {
+                z = z + one;
+            }
+
+            let mut m = s;
+            let mut c = T::pow_mod(z, q, p);
+            let mut t = T::pow_mod(a, q, p);
+            let mut r = T::pow_mod(a, (q + one) / two, p);
+            while t != one {
+                // Find the least i with t^(2^i) = 1.
+                let mut i = 0;
+                let mut t2i = t;
+                while t2i != one {
+                    t2i = t2i.mul_mod(t2i, p);
+                    i += 1;
+                }
+                let mut b = c;
+                for _ in 0..(m - i - 1) {
+                    b = b.mul_mod(b, p);
+                }
+                m = i;
+                c = b.mul_mod(b, p);
+                t = t.mul_mod(c, p);
+                r = r.mul_mod(b, p);
+            }
+            r
+        };
+
+        let other = p - root;
+        if other < root {
+            Some(other)
+        } else {
+            Some(root)
+        }
+    }
+}
+
+/// Errors returned by the Chinese remainder theorem functions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CrtError {
+    /// The moduli at these two positions are not coprime.
+    NotCoprime(usize, usize),
+    /// The number of residues and moduli differ or they are empty.
+    LengthMismatch,
+}
+
+/// Greatest common divisor of `|a|` and `|b|`.
+pub fn gcd<T: TRestrictions<T>>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != T::default() {
+        let r = a - (a / b) * b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+/// Chinese remainder theorem.
+/// Find the `x` in [0, m) with `x = residues[i] mod moduli[i]` for all `i`,
+/// where `m` is the product of all moduli.
+///
+/// **Note** `m` must fit into `T`.
+pub fn crt<T: TRestrictions<T>>(residues: &[T], moduli: &[T]) -> Result<T, CrtError> {
+    if residues.len() != moduli.len() || moduli.is_empty() {
+        return Err(CrtError::LengthMismatch);
+    }
+    let one = T::from_literal(1);
+    for i in 0..moduli.len() {
+        for j in i + 1..moduli.len() {
+            if gcd(moduli[i], moduli[j]) != one {
+                return Err(CrtError::NotCoprime(i, j));
+            }
+        }
+    }
+
+    // Garner's algorithm: x = x + m * ((r_i - x) * m^-1 mod m_i)
+    let mut x = residues[0].add_mod(T::default(), moduli[0]);
+    let mut m = moduli[0];
+    for (&r, &n) in residues.iter().zip(moduli.iter()).skip(1) {
+        let m_inv = extended_euclid_invert(m.rem(n), n, false);
+        let k = r.sub_mod(x, n).mul_mod(m_inv, n);
+        x = x + m * k;
+        m = m * n;
+    }
+    Ok(x)
+}
+
+/// Inverse of `a` modulo `n` over `BigUint` or `None` if it doesn't exist.
+pub fn inv_biguint(a: &BigUint, n: &BigUint) -> Option<BigUint> {
+    let n_int = BigInt::from(n.clone());
+    let (mut t, mut new_t) = (BigInt::zero(), BigInt::from(1));
+    let (mut r, mut new_r) = (n_int.clone(), BigInt::from(a % n));
+    while !new_r.is_zero() {
+        let q = &r / &new_r;
+        let tmp = &t - &q * &new_t;
+        t = std::mem::replace(&mut new_t, tmp);
+        let tmp = &r - &q * &new_r;
+        r = std::mem::replace(&mut new_r, tmp);
+    }
+    if r != BigInt::from(1) {
+        return None;
+    }
+    if t < BigInt::zero() {
+        t += &n_int;
+    }
+    t.to_biguint()
+}
+
+/// Chinese remainder theorem over `BigUint`, see `crt`.
+pub fn crt_biguint(residues: &[BigUint], moduli: &[BigUint]) -> Result<BigUint, CrtError> {
+    if residues.len() != moduli.len() || moduli.is_empty() {
+        return Err(CrtError::LengthMismatch);
+    }
+    let one = BigUint::from(1u32);
+    for i in 0..moduli.len() {
+        for j in i + 1..moduli.len() {
+            if moduli[i].gcd(&moduli[j]) != one {
+                return Err(CrtError::NotCoprime(i, j));
+            }
+        }
+    }
+
+    let mut x = &residues[0] % &moduli[0];
+    let mut m = moduli[0].clone();
+    for (r, n) in residues.iter().zip(moduli.iter()).skip(1) {
+        // The moduli are coprime so the inverse exists.
+        let m_inv = inv_biguint(&m, n).unwrap();
+        let k = (((r % n) + n - (&x % n)) * m_inv) % n;
+        x += &m * k;
+        m *= n;
+    }
+    Ok(x)
+}
+
+/// Number of bits of the representation of `T`, which is at least the bit
+/// length of `T::max()`.
+/// This doesn't compare values such that it doesn't show up in the
+/// operations of `pow_mod_ct`.
+fn bit_width<T: TRestrictions<T>>() -> usize {
+    8 * std::mem::size_of::<T>()
+}
+
+/// The lowest `width` bits of `x` (least significant first) as 0 or 1 in `T`.
+fn bits<T: TRestrictions<T>>(x: T, width: usize) -> Vec<T> {
+    let two = T::from_literal(2);
+    let mut x = x;
+    let mut out = Vec::with_capacity(width);
+    for _ in 0..width {
+        out.push(x - (x / two) * two);
+        x = x / two;
+    }
+    out
+}
+
+/// The value of a non-negative `x`.
+///
+/// **Panics** if `x` doesn't fit into 128 bits.
+pub(crate) fn to_u128<T: TRestrictions<T>>(x: T) -> u128 {
+    let (zero, one, two) = (T::default(), T::from_literal(1), T::from_literal(2));
+    let mut x = x;
+    let mut r = 0u128;
+    for i in 0..u128::BITS {
+        if x == zero {
+            break;
+        }
+        if x - (x / two) * two == one {
+            r |= 1 << i;
+        }
+        x = x / two;
+    }
+    assert!(x == zero, "the value doesn't fit into 128 bits");
+    r
+}
+
+/// Multiply two `u128` into a 256-bit result `(hi, lo)`.
+#[inline]
+pub(crate) fn widening_mul_u128(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = 0xFFFF_FFFF_FFFF_FFFF;
+    let (a0, a1) = (a & MASK, a >> 64);
+    let (b0, b1) = (b & MASK, b >> 64);
+
+    let p00 = a0 * b0;
+    let p01 = a0 * b1;
+    let p10 = a1 * b0;
+    let p11 = a1 * b1;
+
+    let mid = (p00 >> 64) + (p01 & MASK) + (p10 & MASK);
+    let lo = (p00 & MASK) | (mid << 64);
+    let hi = p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64);
+    (hi, lo)
+}
+
+/// `(a + b) % n` for `a, b < n` without overflowing.
+#[inline]
+pub(crate) fn add_mod_u128(a: u128, b: u128, n: u128) -> u128 {
+    if a >= n - b {
+        a - (n - b)
+    } else {
+        a + b
+    }
+}
+
+/// `(a * b) % n` for any `n > 0` without overflowing.
+/// The 256-bit product is reduced bit by bit unless it fits into 128 bits.
+#[inline]
+pub(crate) fn mul_mod_u128(a: u128, b: u128, n: u128) -> u128 {
+    let (hi, lo) = widening_mul_u128(a, b);
+    if hi == 0 {
+        return lo % n;
+    }
+    let mut r = hi % n;
+    for i in (0..128).rev() {
+        r = add_mod_u128(r, r, n);
+        r = add_mod_u128(r, ((lo >> i) & 1) % n, n);
+    }
+    r
+}
+
+/// All ones if `bit` is 1 and 0 if it is 0.
+#[inline]
+fn mask_u128(bit: u128) -> u128 {
+    0u128.wrapping_sub(bit)
+}
+
+/// `(a + b) % n` for `a, b < n` without branching on `a` and `b`.
+#[inline]
+pub(crate) fn add_mod_ct_u128(a: u128, b: u128, n: u128) -> u128 {
+    let (s, carry) = a.overflowing_add(b);
+    let (t, borrow) = s.overflowing_sub(n);
+    // a + b is the result if it neither overflows nor is at least n.
+    let keep = mask_u128((borrow & !carry) as u128);
+    (s & keep) | (t & !keep)
+}
+
+/// `(a - b) % n` for `a, b < n` without branching on `a` and `b`.
+#[inline]
+pub(crate) fn sub_mod_ct_u128(a: u128, b: u128, n: u128) -> u128 {
+    let (d, borrow) = a.overflowing_sub(b);
+    d.wrapping_add(n & mask_u128(borrow as u128))
+}
+
+/// `(a * b) % n` for `a, b < n` with double-and-add over the bit length of
+/// `n` such that the sequence of operations only depends on `n`.
+#[inline]
+pub(crate) fn mul_mod_ct_u128(a: u128, b: u128, n: u128) -> u128 {
+    let mut r = 0;
+    for i in (0..u128::BITS - n.leading_zeros()).rev() {
+        r = add_mod_ct_u128(r, r, n);
+        r = add_mod_ct_u128(r, a & mask_u128((b >> i) & 1), n);
+    }
+    r
 }
 
 #[macro_export]
@@ -46,22 +469,26 @@ macro_rules! impl_unsigned_integer {
             fn sub_lift(self, rhs: $t, n: $t) -> $t {
                 self.sub_mod(rhs, n)
             }
+            /// Both operands are reduced first such that this takes the
+            /// same number of steps independent of the size of `rhs`.
             #[inline]
             fn sub_mod(self, rhs: $t, n: $t) -> $t {
                 if n == 0 {
                     return self - rhs;
                 }
-        
-                let mut lhs = self;
-                while lhs < rhs {
-                    lhs += n;
+
+                let lhs = self % n;
+                let rhs = rhs % n;
+                if lhs >= rhs {
+                    lhs - rhs
+                } else {
+                    n - (rhs - lhs)
                 }
-                lhs - rhs
             }
             #[inline]
             fn add_mod(self, rhs: $t, n: $t) -> $t {
                 if n != 0 {
-                    (self + rhs) % n
+                    add_mod_u128((self % n) as u128, (rhs % n) as u128, n as u128) as $t
                 } else {
                     self + rhs
                 }
@@ -71,7 +498,7 @@ macro_rules! impl_unsigned_integer {
                 if n == 0 {
                     self * rhs
                 } else {
-                    (self * rhs) % n
+                    mul_mod_u128(self as u128, rhs as u128, n as u128) as $t
                 }
             }
             #[inline]
@@ -82,6 +509,28 @@ macro_rules! impl_unsigned_integer {
             fn max() -> $t {
                 <$t>::max_value()
             }
+            /// Computed in `u128` without branching on the operands.
+            #[inline]
+            fn add_mod_ct(self, rhs: $t, n: $t) -> $t {
+                if n == 0 {
+                    return self + rhs;
+                }
+                add_mod_ct_u128(self as u128, rhs as u128, n as u128) as $t
+            }
+            #[inline]
+            fn sub_mod_ct(self, rhs: $t, n: $t) -> $t {
+                if n == 0 {
+                    return self - rhs;
+                }
+                sub_mod_ct_u128(self as u128, rhs as u128, n as u128) as $t
+            }
+            #[inline]
+            fn mul_mod_ct(self, rhs: $t, n: $t) -> $t {
+                if n == 0 {
+                    return self * rhs;
+                }
+                mul_mod_ct_u128(self as u128, rhs as u128, n as u128) as $t
+            }
             #[inline]
             fn abs(self) -> $t {
                 self
@@ -97,6 +546,102 @@ impl_unsigned_integer!(u32);
 impl_unsigned_integer!(u64);
 impl_unsigned_integer!(u128);
 
+/// Signed integers narrower than `i128` do all modular arithmetic in `i128`
+/// such that intermediate values can't overflow.
+/// Results of `sub_mod`, `add_mod`, and `mul_mod` are lifted into [0, n).
+#[macro_export]
+macro_rules! impl_signed_integer {
+    ($t:ty) => {
+        impl Integer<$t> for $t {
+            /// **Warning** might be lossy
+            #[inline]
+            fn from_literal(x: u128) -> $t {
+                x as $t
+            }
+            /// **Warning** might be lossy
+            #[inline]
+            fn from_signed_literal(x: i128) -> $t {
+                x as $t
+            }
+            /// **Panics**
+            #[inline]
+            fn inv(x: $t, n: $t) -> $t {
+                // |MIN| doesn't fit into the type itself.
+                let n = (n as i128).abs();
+                extended_euclid_invert(signed_mod(x as i128, n), n, false) as $t
+            }
+            #[inline]
+            fn sub_lift(self, rhs: $t, _n: $t) -> $t {
+                self - rhs
+            }
+            #[inline]
+            fn sub_mod(self, rhs: $t, n: $t) -> $t {
+                if n != 0 {
+                    signed_mod((self as i128) - (rhs as i128), n as i128) as $t
+                } else {
+                    self - rhs
+                }
+            }
+            #[inline]
+            fn add_mod(self, rhs: $t, n: $t) -> $t {
+                if n != 0 {
+                    signed_mod((self as i128) + (rhs as i128), n as i128) as $t
+                } else {
+                    self + rhs
+                }
+            }
+            #[inline]
+            fn mul_mod(self, rhs: $t, n: $t) -> $t {
+                if n != 0 {
+                    signed_mod((self as i128) * (rhs as i128), n as i128) as $t
+                } else {
+                    self * rhs
+                }
+            }
+            #[inline]
+            fn rem(self, n: $t) -> $t {
+                self % n
+            }
+            #[inline]
+            fn max() -> $t {
+                <$t>::max_value()
+            }
+            /// Computed in `u128` without branching on the operands.
+            #[inline]
+            fn add_mod_ct(self, rhs: $t, n: $t) -> $t {
+                if n == 0 {
+                    return self + rhs;
+                }
+                add_mod_ct_u128(self as u128, rhs as u128, n as u128) as $t
+            }
+            #[inline]
+            fn sub_mod_ct(self, rhs: $t, n: $t) -> $t {
+                if n == 0 {
+                    return self - rhs;
+                }
+                sub_mod_ct_u128(self as u128, rhs as u128, n as u128) as $t
+            }
+            #[inline]
+            fn mul_mod_ct(self, rhs: $t, n: $t) -> $t {
+                if n == 0 {
+                    return self * rhs;
+                }
+                mul_mod_ct_u128(self as u128, rhs as u128, n as u128) as $t
+            }
+            #[inline]
+            fn abs(self) -> $t {
+                self.abs()
+            }
+        }
+    };
+}
+
+impl_signed_integer!(isize);
+impl_signed_integer!(i8);
+impl_signed_integer!(i16);
+impl_signed_integer!(i32);
+impl_signed_integer!(i64);
+
 impl Integer<i128> for i128 {
     /// **Warning** might be lossy
     #[inline]
@@ -118,7 +663,13 @@ impl Integer<i128> for i128 {
     #[inline]
     fn sub_mod(self, rhs: i128, n: i128) -> i128 {
         if n != 0 {
-            signed_mod(self - rhs, n)
+            let lhs = signed_mod(self, n);
+            let rhs = signed_mod(rhs, n);
+            if lhs >= rhs {
+                lhs - rhs
+            } else {
+                n - (rhs - lhs)
+            }
         } else {
             self - rhs
         }
@@ -126,17 +677,18 @@ impl Integer<i128> for i128 {
     #[inline]
     fn add_mod(self, rhs: i128, n: i128) -> i128 {
         if n != 0 {
-            signed_mod(self + rhs, n)
+            add_mod_u128(signed_mod(self, n) as u128, signed_mod(rhs, n) as u128, n as u128) as i128
         } else {
             self + rhs
         }
     }
+    /// The result is lifted into [0, n).
     #[inline]
     fn mul_mod(self, rhs: i128, n: i128) -> i128 {
         if n == 0 {
             self * rhs
         } else {
-            (self * rhs) % n
+            mul_mod_u128(signed_mod(self, n) as u128, signed_mod(rhs, n) as u128, n as u128) as i128
         }
     }
     #[inline]
@@ -147,6 +699,28 @@ impl Integer<i128> for i128 {
     fn max() -> i128 {
         i128::max_value()
     }
+    /// Computed in `u128` without branching on the operands.
+    #[inline]
+    fn add_mod_ct(self, rhs: i128, n: i128) -> i128 {
+        if n == 0 {
+            return self + rhs;
+        }
+        add_mod_ct_u128(self as u128, rhs as u128, n as u128) as i128
+    }
+    #[inline]
+    fn sub_mod_ct(self, rhs: i128, n: i128) -> i128 {
+        if n == 0 {
+            return self - rhs;
+        }
+        sub_mod_ct_u128(self as u128, rhs as u128, n as u128) as i128
+    }
+    #[inline]
+    fn mul_mod_ct(self, rhs: i128, n: i128) -> i128 {
+        if n == 0 {
+            return self * rhs;
+        }
+        mul_mod_ct_u128(self as u128, rhs as u128, n as u128) as i128
+    }
     #[inline]
     fn abs(self) -> i128 {
         self.abs()
@@ -0,0 +1,203 @@
+//!
+//! # Polynomial evaluation and interpolation
+//!
+//! Evaluation, interpolation and derivatives of polynomials given as
+//! coefficient slices over ℤn (or ℤ for n = 0), lowest coefficient first,
+//! as they are needed for Shamir secret sharing, FROST or Reed–Solomon codes.
+//!
+//! * `poly_eval` evaluates at a single point with Horner's rule.
+//! * `SubproductTree` evaluates at many points by reducing the polynomial
+//!   modulo the products of (x - x_i) down a binary tree.
+//! * `lagrange_interpolate` reconstructs the polynomial through given
+//!   points and `lagrange_coefficient` computes λ_i(0), e.g. to recover a
+//!   Shamir secret from its shares.
+//! * `poly_derivative` computes the formal derivative.
+//!
+//! ```
+//! use hacspec::prelude::*;
+//!
+//! // Shamir secret sharing of 1234 with threshold 3 over ℤp
+//! let p = 7919u128;
+//! let f = [1234u128, 166, 94];
+//! let xs = [1u128, 3, 5];
+//! let shares: Vec<u128> = xs.iter().map(|&x| poly_eval(&f, x, p)).collect();
+//! let secret = (0..3).fold(0, |acc, i| {
+//!     acc.add_mod(shares[i].mul_mod(lagrange_coefficient(&xs, i, p).unwrap(), p), p)
+//! });
+//! assert_eq!(secret, 1234);
+//! ```
+//!
+//! **Note** the interpolation divides and is therefore only defined over ℤn.
+//! Subproduct trees over ℤ need signed coefficients.
+//!
+
+use crate::prelude::*;
+
+/// Errors returned by the interpolation functions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InterpolationError {
+    /// The points at these two positions are equal modulo n.
+    DuplicatePoint(usize, usize),
+    /// A difference of two points isn't invertible modulo n, or n is 0.
+    NotInvertible,
+    /// The number of points and values differ or there are no points.
+    LengthMismatch,
+}
+
+/// Evaluate `p` at `x` with Horner's rule.
+pub fn poly_eval<T: TRestrictions<T>>(p: &[T], x: T, n: T) -> T {
+    p.iter()
+        .rev()
+        .fold(T::default(), |acc, &c| acc.mul_mod(x, n).add_mod(c, n))
+}
+
+/// The formal derivative of `p`, i.e. Σ i·p_i·x^(i - 1).
+pub fn poly_derivative<T: TRestrictions<T>>(p: &[T], n: T) -> Vec<T> {
+    if p.len() < 2 {
+        return vec![T::default()];
+    }
+    p.iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, &c)| c.mul_mod(T::from_literal(i as u128), n))
+        .collect()
+}
+
+/// Invert `a` modulo n or fail if it isn't invertible.
+fn invert<T: TRestrictions<T>>(a: T, n: T) -> Result<T, InterpolationError> {
+    let a = a.add_mod(T::default(), n);
+    if n == T::default() || gcd(a, n) != T::from_literal(1) {
+        return Err(InterpolationError::NotInvertible);
+    }
+    Ok(extended_euclid_invert(a, n, false))
+}
+
+/// Check that all points are distinct modulo n and n isn't 0.
+fn check_points<T: TRestrictions<T>>(xs: &[T], n: T) -> Result<(), InterpolationError> {
+    let zero = T::default();
+    if n == zero {
+        return Err(InterpolationError::NotInvertible);
+    }
+    if xs.is_empty() {
+        return Err(InterpolationError::LengthMismatch);
+    }
+    for i in 0..xs.len() {
+        for j in i + 1..xs.len() {
+            if xs[i].sub_mod(xs[j], n) == zero {
+                return Err(InterpolationError::DuplicatePoint(i, j));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Evaluate the i-th Lagrange basis polynomial
+/// λ_i(x) = Π_(j ≠ i) (x - x_j) / (x_i - x_j) at `x`.
+pub fn lagrange_basis_eval<T: TRestrictions<T>>(xs: &[T], i: usize, x: T, n: T) -> Result<T, InterpolationError> {
+    check_points(xs, n)?;
+    if i >= xs.len() {
+        return Err(InterpolationError::LengthMismatch);
+    }
+    let mut numerator = T::from_literal(1);
+    let mut denominator = T::from_literal(1);
+    for (j, &x_j) in xs.iter().enumerate() {
+        if j != i {
+            numerator = numerator.mul_mod(x.sub_mod(x_j, n), n);
+            denominator = denominator.mul_mod(xs[i].sub_mod(x_j, n), n);
+        }
+    }
+    Ok(numerator.mul_mod(invert(denominator, n)?, n))
+}
+
+/// The Lagrange coefficient λ_i(0) of the i-th point, which recovers
+/// f(0) = Σ λ_i(0)·f(x_i) from the points `xs`.
+pub fn lagrange_coefficient<T: TRestrictions<T>>(xs: &[T], i: usize, n: T) -> Result<T, InterpolationError> {
+    lagrange_basis_eval(xs, i, T::default(), n)
+}
+
+/// The polynomial of degree less than `xs.len()` with f(xs[i]) = ys[i].
+pub fn lagrange_interpolate<T: TRestrictions<T>>(xs: &[T], ys: &[T], n: T) -> Result<Vec<T>, InterpolationError> {
+    if xs.len() != ys.len() {
+        return Err(InterpolationError::LengthMismatch);
+    }
+    check_points(xs, n)?;
+    let zero = T::default();
+    let one = T::from_literal(1);
+
+    // m(x) = Π (x - x_j)
+    let mut m = vec![one];
+    for &x_j in xs.iter() {
+        m = truncate(&poly_mul(&m, &[zero.sub_mod(x_j, n), one], n));
+    }
+
+    let mut out = vec![zero; xs.len()];
+    for (&x_i, &y_i) in xs.iter().zip(ys.iter()) {
+        // m(x) / (x - x_i) by synthetic division
+        let mut q = vec![zero; xs.len()];
+        let mut carry = zero;
+        for k in (0..xs.len()).rev() {
+            carry = m[k + 1].add_mod(carry.mul_mod(x_i, n), n);
+            q[k] = carry;
+        }
+        let scale = y_i.mul_mod(invert(poly_eval(&q, x_i, n), n)?, n);
+        for (o, &c) in out.iter_mut().zip(q.iter()) {
+            *o = o.add_mod(c.mul_mod(scale, n), n);
+        }
+    }
+    Ok(out)
+}
+
+/// The subproduct tree of the points x_0, ..., x_(k-1).
+/// The leaves are x - x_i and every node is the product of its children.
+#[derive(Clone, Debug)]
+pub struct SubproductTree<T> {
+    levels: Vec<Vec<Vec<T>>>,
+    n: T,
+}
+
+impl<T: TRestrictions<T>> SubproductTree<T> {
+    /// **Panics** if there are no points.
+    pub fn new(xs: &[T], n: T) -> Self {
+        let zero = T::default();
+        let one = T::from_literal(1);
+        assert!(!xs.is_empty(), "a subproduct tree needs at least one point");
+        let mut levels = vec![xs.iter().map(|&x| vec![zero.sub_mod(x, n), one]).collect::<Vec<Vec<T>>>()];
+        while levels[levels.len() - 1].len() > 1 {
+            let level = levels[levels.len() - 1]
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => truncate(&poly_mul(a, b, n)),
+                    _ => pair[0].clone(),
+                })
+                .collect();
+            levels.push(level);
+        }
+        Self { levels, n }
+    }
+
+    /// The product Π (x - x_i) of all leaves.
+    pub fn product(&self) -> &[T] {
+        &self.levels[self.levels.len() - 1][0]
+    }
+
+    /// Evaluate `p` at all points by reducing it down the tree.
+    pub fn evaluate(&self, p: &[T]) -> Vec<T> {
+        let mut remainders = vec![euclid_div(p, self.product(), self.n).1];
+        for level in self.levels.iter().rev().skip(1) {
+            remainders = level
+                .iter()
+                .enumerate()
+                .map(|(i, m)| euclid_div(&remainders[i / 2], m, self.n).1)
+                .collect();
+        }
+        remainders.iter().map(|r| r[0]).collect()
+    }
+}
+
+/// Evaluate `p` at all points `xs` with a subproduct tree.
+pub fn poly_eval_multi<T: TRestrictions<T>>(p: &[T], xs: &[T], n: T) -> Vec<T> {
+    if xs.is_empty() {
+        return vec![];
+    }
+    SubproductTree::new(xs, n).evaluate(p)
+}
@@ -25,12 +25,29 @@ use std::convert::AsMut;
 use std::ops::{Index, IndexMut, Range, RangeFull};
 
 pub mod array;
+pub mod binary_field;
+pub mod edwards;
+pub mod encoding;
+pub mod extension;
+pub mod factorization;
+pub mod fast_mul;
+pub mod field;
+pub mod hash_to_curve;
+pub mod montgomery;
+pub mod ntt;
+pub mod pairing;
 pub mod poly;
+pub mod poly_matrix;
 pub mod prelude;
+pub mod primes;
+pub mod reduction;
+pub mod sampling;
 pub mod seq;
 pub mod test_vectors;
 pub mod util;
+pub mod weierstrass;
 pub mod integer;
+pub mod interpolation;
 
 use crate::prelude::*;
 
@@ -144,6 +161,106 @@ macro_rules! assert_bytes_eq {
 macro_rules! unsigned_integer {
     ($name:ident, $bits:literal) => {
         define_abstract_integer_checked!($name, $bits);
+
+        impl $name {
+            /// The maximal bit length of values of this type.
+            pub fn bits() -> usize {
+                $bits
+            }
+
+            /// Number of bytes needed for the largest value, i.e. ceil(bits / 8).
+            pub fn byte_len() -> usize {
+                ($bits as usize).div_ceil(8)
+            }
+
+            /// Parse a big-endian hex string.
+            /// **Panics** if `s` isn't valid hex or the value doesn't fit.
+            pub fn from_hex(s: &str) -> $name {
+                $name::from_biguint(&BigUint::from_str_radix(s, 16).expect("invalid hex string"))
+            }
+
+            pub fn to_biguint(self) -> BigUint {
+                BigUint::from_bytes_le(&self.to_bytes_le())
+            }
+
+            /// **Panics** if `x` doesn't fit.
+            pub fn from_biguint(x: &BigUint) -> $name {
+                $name::from_bytes_le(&x.to_bytes_le())
+            }
+
+            /// Decode little-endian bytes.
+            /// **Panics** if the value doesn't fit.
+            pub fn from_byte_seq_le<A: SeqTrait<U8>>(s: A) -> $name {
+                $name::from_bytes_le(
+                    s.iter()
+                        .map(|x| U8::declassify(*x))
+                        .collect::<Vec<_>>()
+                        .as_slice(),
+                )
+            }
+
+            /// Decode big-endian bytes (OS2IP).
+            /// **Panics** if the value doesn't fit.
+            pub fn from_byte_seq_be<A: SeqTrait<U8>>(s: A) -> $name {
+                $name::from_bytes_be(
+                    s.iter()
+                        .map(|x| U8::declassify(*x))
+                        .collect::<Vec<_>>()
+                        .as_slice(),
+                )
+            }
+
+            /// Little-endian encoding padded to `len` bytes.
+            /// **Panics** if `self` ≥ 256^len.
+            pub fn to_byte_seq_le(self, len: usize) -> Seq<U8> {
+                let mut bytes = self.to_biguint().to_bytes_le();
+                assert!(self.bit_length() <= 8 * len, "integer too large");
+                bytes.resize(len, 0);
+                Seq::from(bytes.iter().map(|x| U8::classify(*x)).collect::<Vec<U8>>())
+            }
+
+            /// Big-endian encoding padded to `len` bytes (I2OSP).
+            /// **Panics** if `self` ≥ 256^len.
+            pub fn to_byte_seq_be(self, len: usize) -> Seq<U8> {
+                let mut bytes = self.to_biguint().to_bytes_le();
+                assert!(self.bit_length() <= 8 * len, "integer too large");
+                bytes.resize(len, 0);
+                bytes.reverse();
+                Seq::from(bytes.iter().map(|x| U8::classify(*x)).collect::<Vec<U8>>())
+            }
+
+            /// Bit `i` of `self`, where bit 0 is the least significant one.
+            pub fn get_bit(self, i: usize) -> bool {
+                let bytes = self.to_bytes_le();
+                i / 8 < bytes.len() && (bytes[i / 8] >> (i % 8)) & 1 == 1
+            }
+
+            /// Set bit `i` of `self` to `b`.
+            /// **Panics** if `i` ≥ bits.
+            pub fn set_bit(self, i: usize, b: bool) -> $name {
+                assert!(i < $bits, "bit index out of range");
+                let mut bytes = self.to_bytes_le();
+                if bytes.len() <= i / 8 {
+                    bytes.resize(i / 8 + 1, 0);
+                }
+                bytes[i / 8] &= !(1 << (i % 8));
+                bytes[i / 8] |= (b as u8) << (i % 8);
+                $name::from_bytes_le(&bytes)
+            }
+
+            /// Number of bits needed to represent `self`, i.e. 0 for 0.
+            pub fn bit_length(self) -> usize {
+                self.to_biguint().bits()
+            }
+
+            /// Compute `self^exp mod modulus`.
+            /// **Panics** if `modulus` is 0.
+            pub fn pow_mod(self, exp: $name, modulus: $name) -> $name {
+                let m = modulus.to_biguint();
+                assert!(!m.is_zero(), "modulus is zero");
+                $name::from_biguint(&self.to_biguint().modpow(&exp.to_biguint(), &m))
+            }
+        }
     };
 }
 
@@ -153,6 +270,26 @@ macro_rules! field_integer {
         define_refined_modular_integer!($name, $base, $max);
 
         impl $name {
+            /// The field modulus p.
+            pub fn modulus_biguint() -> BigUint {
+                let p_minus_one = $name::from_literal(0) - $name::from_literal(1);
+                BigUint::from_bytes_le(&p_minus_one.to_bytes_le()) + 1u32
+            }
+
+            /// Number of bytes of the fixed-size encoding, i.e. ceil(bits(p) / 8).
+            pub fn byte_len() -> usize {
+                $name::modulus_biguint().bits().div_ceil(8)
+            }
+
+            pub fn to_biguint(self) -> BigUint {
+                BigUint::from_bytes_le(&self.to_bytes_le())
+            }
+
+            /// Get the field element `x mod p`.
+            pub fn from_biguint(x: &BigUint) -> $name {
+                $name::from_bytes_le(&(x % $name::modulus_biguint()).to_bytes_le())
+            }
+
             pub fn from_byte_seq_le<A: SeqTrait<U8>>(s: A) -> $name {
                 $name::from_bytes_le(
                     s.iter()
@@ -162,19 +299,257 @@ macro_rules! field_integer {
                 )
             }
 
-            pub fn to_byte_seq_le(self) -> Seq<U8> {
-                Seq::from(
-                    self.to_bytes_le()
-                        .iter()
-                        .map(|x| U8::classify(*x))
-                        .collect::<Vec<U8>>(),
+            pub fn from_byte_seq_be<A: SeqTrait<U8>>(s: A) -> $name {
+                $name::from_bytes_be(
+                    s.iter()
+                        .map(|x| U8::declassify(*x))
+                        .collect::<Vec<_>>()
+                        .as_slice(),
                 )
             }
 
+            /// Decode `byte_len()` little-endian bytes.
+            /// Returns `None` if the length is wrong or the value isn't
+            /// reduced, i.e. ≥ p.
+            pub fn from_canonical_byte_seq_le<A: SeqTrait<U8>>(s: A) -> Option<$name> {
+                let bytes = s.iter().map(|x| U8::declassify(*x)).collect::<Vec<_>>();
+                if bytes.len() != $name::byte_len()
+                    || BigUint::from_bytes_le(&bytes) >= $name::modulus_biguint()
+                {
+                    return None;
+                }
+                Some($name::from_bytes_le(&bytes))
+            }
+
+            /// Decode `byte_len()` big-endian bytes.
+            /// Returns `None` if the length is wrong or the value isn't
+            /// reduced, i.e. ≥ p.
+            pub fn from_canonical_byte_seq_be<A: SeqTrait<U8>>(s: A) -> Option<$name> {
+                let bytes = s.iter().map(|x| U8::declassify(*x)).collect::<Vec<_>>();
+                if bytes.len() != $name::byte_len()
+                    || BigUint::from_bytes_be(&bytes) >= $name::modulus_biguint()
+                {
+                    return None;
+                }
+                Some($name::from_bytes_be(&bytes))
+            }
+
+            /// Little-endian encoding padded to `byte_len()` bytes.
+            pub fn to_byte_seq_le(self) -> Seq<U8> {
+                let mut bytes = self.to_bytes_le();
+                bytes.resize($name::byte_len(), 0);
+                Seq::from(bytes.iter().map(|x| U8::classify(*x)).collect::<Vec<U8>>())
+            }
+
+            /// Big-endian encoding padded to `byte_len()` bytes.
+            pub fn to_byte_seq_be(self) -> Seq<U8> {
+                let mut bytes = self.to_bytes_le();
+                bytes.resize($name::byte_len(), 0);
+                bytes.reverse();
+                Seq::from(bytes.iter().map(|x| U8::classify(*x)).collect::<Vec<U8>>())
+            }
+
+            /// Little-endian encoding into a fixed-size byte array, e.g. one
+            /// defined with `bytes!`, of length `byte_len()`.
+            pub fn to_byte_array_le<A: From<Seq<U8>> + SeqTrait<U8>>(self) -> A {
+                let a = A::from(self.to_byte_seq_le());
+                debug_assert!(a.len() == $name::byte_len());
+                a
+            }
+
+            /// Big-endian encoding into a fixed-size byte array, e.g. one
+            /// defined with `bytes!`, of length `byte_len()`.
+            pub fn to_byte_array_be<A: From<Seq<U8>> + SeqTrait<U8>>(self) -> A {
+                let a = A::from(self.to_byte_seq_be());
+                debug_assert!(a.len() == $name::byte_len());
+                a
+            }
+
             pub fn from_secret_literal(x: U128) -> $name {
                 $name::from_literal(U128::declassify(x))
             }
         }
+
+        impl $name {
+            /// Compute `self^exp` for an arbitrary exponent.
+            pub fn pow_biguint(self, exp: &BigUint) -> $name {
+                $name::from_biguint(&self.to_biguint().modpow(exp, &$name::modulus_biguint()))
+            }
+
+            /// Compute `self^exp`.
+            pub fn pow_felem(self, exp: $name) -> $name {
+                self.pow_biguint(&exp.to_biguint())
+            }
+
+            /// Invert using Fermat's little theorem, i.e. compute `self^(p-2)`.
+            /// **Note** that the inverse of 0 is 0 (`inv0` in RFC 9380).
+            pub fn inv(self) -> $name {
+                let p = $name::modulus_biguint();
+                self.pow_biguint(&(p - 2u32))
+            }
+
+            /// Returns `true` if `self` is 0 or a quadratic residue mod p.
+            pub fn is_square(self) -> bool {
+                let p = $name::modulus_biguint();
+                let e = (p - 1u32) >> 1;
+                let c = self.pow_biguint(&e);
+                c == $name::from_literal(0) || c == $name::from_literal(1)
+            }
+
+            /// `sgn0` for prime fields as defined in RFC 9380, i.e. `true` if
+            /// `self` is odd.
+            pub fn sgn0(self) -> bool {
+                !(self.to_biguint() % 2u32).is_zero()
+            }
+
+            /// Compute a square root of `self`, or `None` if `self` isn't a square.
+            /// Uses the shortcuts for p ≡ 3 mod 4 and p ≡ 5 mod 8 (Atkin) and
+            /// Tonelli-Shanks otherwise.
+            /// **Note** that no particular root of the two is returned.
+            pub fn sqrt(self) -> Option<$name> {
+                let p = $name::modulus_biguint();
+                let zero = $name::from_literal(0);
+                let one = $name::from_literal(1);
+                let p_mod_8 = (&p % 8u32).to_bytes_le()[0];
+                let c = if p_mod_8 % 4 == 3 {
+                    self.pow_biguint(&((p + 1u32) >> 2))
+                } else if p_mod_8 == 5 {
+                    let two_x = self + self;
+                    let t = two_x.pow_biguint(&((p - 5u32) >> 3));
+                    let i = two_x * t * t;
+                    self * t * (i - one)
+                } else {
+                    if self == zero {
+                        return Some(zero);
+                    }
+                    if !self.is_square() {
+                        return None;
+                    }
+                    // p - 1 = q * 2^s
+                    let mut q = p - 1u32;
+                    let mut s = 0usize;
+                    while (&q % 2u32).is_zero() {
+                        q >>= 1;
+                        s += 1;
+                    }
+                    let mut z = $name::from_literal(2);
+                    while z.is_square() {
+                        z = z + one;
+                    }
+                    let mut m = s;
+                    let mut c = z.pow_biguint(&q);
+                    let mut t = self.pow_biguint(&q);
+                    let mut r = self.pow_biguint(&((q + 1u32) >> 1));
+                    while t != one {
+                        let mut i = 0;
+                        let mut t2 = t;
+                        while t2 != one {
+                            t2 = t2 * t2;
+                            i += 1;
+                        }
+                        let mut b = c;
+                        for _ in 0..m - i - 1 {
+                            b = b * b;
+                        }
+                        m = i;
+                        c = b * b;
+                        t = t * c;
+                        r = r * b;
+                    }
+                    r
+                };
+                if c * c == self {
+                    Some(c)
+                } else {
+                    None
+                }
+            }
+
+            /// Invert all elements of `xs` with a single field inversion
+            /// (Montgomery's trick). Zero elements are mapped to 0.
+            pub fn batch_inv(xs: &Seq<$name>) -> Seq<$name> {
+                let zero = $name::from_literal(0);
+                let mut prefix = Seq::new(xs.len());
+                let mut acc = $name::from_literal(1);
+                for i in 0..xs.len() {
+                    prefix[i] = acc;
+                    if xs[i] != zero {
+                        acc = acc * xs[i];
+                    }
+                }
+                let mut acc_inv = acc.inv();
+                let mut out = Seq::new(xs.len());
+                for i in (0..xs.len()).rev() {
+                    if xs[i] == zero {
+                        out[i] = zero;
+                    } else {
+                        out[i] = acc_inv * prefix[i];
+                        acc_inv = acc_inv * xs[i];
+                    }
+                }
+                out
+            }
+        }
+
+        impl FieldElement for $name {
+            fn zero() -> Self {
+                $name::from_literal(0)
+            }
+            fn one() -> Self {
+                $name::from_literal(1)
+            }
+            fn from_literal(x: u128) -> Self {
+                $name::from_literal(x)
+            }
+            fn inv(self) -> Self {
+                $name::inv(self)
+            }
+            fn sqrt(self) -> Option<Self> {
+                $name::sqrt(self)
+            }
+            fn is_square(self) -> bool {
+                $name::is_square(self)
+            }
+            fn sgn0(self) -> bool {
+                $name::sgn0(self)
+            }
+            fn characteristic() -> BigUint {
+                $name::modulus_biguint()
+            }
+            fn frobenius(self, _power: usize) -> Self {
+                self
+            }
+            fn pow_biguint(self, exp: &BigUint) -> Self {
+                $name::pow_biguint(self, exp)
+            }
+        }
+
+        impl PrimeField for $name {
+            fn modulus_biguint() -> BigUint {
+                $name::modulus_biguint()
+            }
+            fn to_biguint(self) -> BigUint {
+                $name::to_biguint(self)
+            }
+            fn from_biguint(x: &BigUint) -> Self {
+                $name::from_biguint(x)
+            }
+            fn byte_len() -> usize {
+                $name::byte_len()
+            }
+            fn to_byte_seq_be(self) -> Seq<U8> {
+                $name::to_byte_seq_be(self)
+            }
+            fn to_byte_seq_le(self) -> Seq<U8> {
+                $name::to_byte_seq_le(self)
+            }
+            fn from_canonical_byte_seq_be(s: Seq<U8>) -> Option<Self> {
+                $name::from_canonical_byte_seq_be(s)
+            }
+            fn from_canonical_byte_seq_le(s: Seq<U8>) -> Option<Self> {
+                $name::from_canonical_byte_seq_le(s)
+            }
+        }
     };
 }
 
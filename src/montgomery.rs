@@ -0,0 +1,157 @@
+//!
+//! # Montgomery curves
+//!
+//! x-only arithmetic on Montgomery curves v² = u³ + Au² + u as used by
+//! X25519 and X448 (RFC 7748).
+//! A curve is defined by implementing `MontgomeryCurve` for a prime field
+//! implementing `PrimeField`.
+//!
+//! ```
+//! use hacspec::prelude::*;
+//!
+//! unsigned_integer!(X25519Canvas, 256);
+//! field_integer!(
+//!     X25519FieldElement,
+//!     X25519Canvas,
+//!     X25519Canvas::from_bytes_be(&hex_string_to_bytes(
+//!         "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed"
+//!     ))
+//! );
+//!
+//! struct X25519;
+//! impl MontgomeryCurve for X25519 {
+//!     type Field = X25519FieldElement;
+//!     const BITS: usize = 255;
+//!     const COFACTOR_BITS: usize = 3;
+//!     fn a24() -> X25519FieldElement {
+//!         X25519FieldElement::from_literal(121665)
+//!     }
+//!     fn base_point() -> X25519FieldElement {
+//!         X25519FieldElement::from_literal(9)
+//!     }
+//! }
+//!
+//! let sk = ByteSeq::from("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+//! let pk = X25519::scalarmult_base(&sk);
+//! assert_eq!(pk.to_hex(), "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a");
+//! ```
+//!
+
+use crate::prelude::*;
+
+pub trait MontgomeryCurve {
+    type Field: PrimeField;
+
+    /// Number of bits of the scalars and u-coordinates, e.g. 255 for X25519
+    /// and 448 for X448.
+    const BITS: usize;
+
+    /// log2 of the cofactor, i.e. the number of low scalar bits cleared when
+    /// clamping.
+    const COFACTOR_BITS: usize;
+
+    /// (A - 2) / 4
+    fn a24() -> Self::Field;
+
+    /// The u-coordinate of the base point.
+    fn base_point() -> Self::Field;
+
+    /// Number of bytes of scalars and u-coordinates.
+    fn byte_len() -> usize {
+        Self::BITS.div_ceil(8)
+    }
+
+    /// Clamp the little-endian scalar `k` (`decodeScalar` in RFC 7748):
+    /// clear the low cofactor bits, clear all bits above `BITS - 1`, and set
+    /// bit `BITS - 1`.
+    fn clamp(k: &Seq<U8>) -> Seq<U8> {
+        debug_assert!(k.len() == Self::byte_len());
+        let mut out = k.clone();
+        let top = Self::BITS - 1;
+        out[0] &= U8::classify(0xffu8 << Self::COFACTOR_BITS);
+        let last = Self::byte_len() - 1;
+        out[last] &= U8::classify(0xffu8 >> (7 - top % 8));
+        out[top / 8] |= U8::classify(1u8 << (top % 8));
+        out
+    }
+
+    /// Decode a little-endian u-coordinate (`decodeUCoordinate` in RFC 7748).
+    /// Unused top bits are masked and non-canonical values are reduced.
+    fn decode_u_coordinate(u: &Seq<U8>) -> Self::Field {
+        debug_assert!(u.len() == Self::byte_len());
+        let mut bytes = u.iter().map(|x| U8::declassify(*x)).collect::<Vec<u8>>();
+        if Self::BITS % 8 != 0 {
+            let last = bytes.len() - 1;
+            bytes[last] &= (1u8 << (Self::BITS % 8)) - 1;
+        }
+        Self::Field::from_biguint(&BigUint::from_bytes_le(&bytes))
+    }
+
+    /// Encode a u-coordinate as little-endian bytes (`encodeUCoordinate` in
+    /// RFC 7748).
+    fn encode_u_coordinate(u: Self::Field) -> Seq<U8> {
+        let mut bytes = u.to_biguint().to_bytes_le();
+        bytes.resize(Self::byte_len(), 0);
+        Seq::from(bytes.iter().map(|x| U8::classify(*x)).collect::<Vec<U8>>())
+    }
+
+    /// The x-only Montgomery ladder from RFC 7748 computing the u-coordinate
+    /// of `k * P` for the clamped little-endian scalar `k` and the
+    /// u-coordinate `u` of `P`.
+    fn ladder(k: &Seq<U8>, u: Self::Field) -> Self::Field {
+        let x_1 = u;
+        let mut x_2 = Self::Field::one();
+        let mut z_2 = Self::Field::zero();
+        let mut x_3 = u;
+        let mut z_3 = Self::Field::one();
+        let mut swap = false;
+        for t in (0..Self::BITS).rev() {
+            let k_t = (U8::declassify(k[t / 8]) >> (t % 8)) & 1 == 1;
+            swap ^= k_t;
+            let (a, b) = cswap(x_2, x_3, swap);
+            x_2 = a;
+            x_3 = b;
+            let (a, b) = cswap(z_2, z_3, swap);
+            z_2 = a;
+            z_3 = b;
+            swap = k_t;
+
+            let a = x_2 + z_2;
+            let aa = a.square();
+            let b = x_2 - z_2;
+            let bb = b.square();
+            let e = aa - bb;
+            let c = x_3 + z_3;
+            let d = x_3 - z_3;
+            let da = d * a;
+            let cb = c * b;
+            x_3 = (da + cb).square();
+            z_3 = x_1 * (da - cb).square();
+            x_2 = aa * bb;
+            z_2 = e * (aa + Self::a24() * e);
+        }
+        let (x_2, _) = cswap(x_2, x_3, swap);
+        let (z_2, _) = cswap(z_2, z_3, swap);
+        x_2 * z_2.inv()
+    }
+
+    /// Compute the encoded u-coordinate of `k * P` from the encoded scalar
+    /// `k` and the encoded u-coordinate `u` of `P`, e.g. the X25519
+    /// function.
+    fn scalarmult(k: &Seq<U8>, u: &Seq<U8>) -> Seq<U8> {
+        let k = Self::clamp(k);
+        let u = Self::decode_u_coordinate(u);
+        Self::encode_u_coordinate(Self::ladder(&k, u))
+    }
+
+    /// `scalarmult` with the base point.
+    fn scalarmult_base(k: &Seq<U8>) -> Seq<U8> {
+        let k = Self::clamp(k);
+        Self::encode_u_coordinate(Self::ladder(&k, Self::base_point()))
+    }
+}
+
+/// Swap `a` and `b` if `bit` is set without branching on `bit`.
+fn cswap<F: FieldElement>(a: F, b: F, bit: bool) -> (F, F) {
+    (F::select(a, b, bit), F::select(b, a, bit))
+}
@@ -0,0 +1,235 @@
+//!
+//! # Number-theoretic transform
+//!
+//! This module implements the NTT over ℤq[x]/(x^n + 1) as it is used in
+//! lattice-based schemes such as Kyber and Dilithium.
+//!
+//! An `NttCtx` holds the twiddle factors ("zetas") computed from a given root
+//! of unity ζ. The forward transform uses Cooley–Tukey butterflies and
+//! produces its output in bit-reversed order, the inverse transform uses
+//! Gentleman–Sande butterflies and takes its input in bit-reversed order.
+//!
+//! With k layers the transform splits x^n + 1 into the 2^k factors
+//! x^d - ζ^(2 brv(i) + 1) of degree d = n / 2^k where ζ is a primitive
+//! 2^(k+1)-th root of unity.
+//! The complete NTT (d = 1, e.g. Dilithium) multiplies pointwise, the
+//! incomplete NTT (d > 1, e.g. Kyber with d = 2) multiplies the residues
+//! with a base-case multiplication modulo x^d - γ.
+//!
+//! ```
+//! use hacspec::prelude::*;
+//!
+//! // Kyber: n = 256, q = 3329, ζ = 17 with 7 layers
+//! let ctx = NttCtx::new_incomplete(256, 3329u128, 17, 7).unwrap();
+//! let a = random_poly::<u128>(256, 0, 3329);
+//! let b = random_poly::<u128>(256, 0, 3329);
+//! let c = ctx.inv_ntt(&ctx.mul_ntt(&ctx.ntt(a.raw()), &ctx.ntt(b.raw())));
+//! assert_eq!(c, ctx.negacyclic_mul(a.raw(), b.raw()));
+//! ```
+//!
+
+use crate::prelude::*;
+
+/// Errors returned when creating an `NttCtx`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NttError {
+    /// The degree n isn't divisible by 2^k for k layers.
+    InvalidLength,
+    /// The root doesn't have order 2^(k+1) for k layers.
+    InvalidRoot,
+}
+
+/// Reverse the lowest `bits` bits of `x`.
+pub fn bit_reverse(x: usize, bits: usize) -> usize {
+    if bits == 0 {
+        return 0;
+    }
+    x.reverse_bits() >> (usize::BITS as usize - bits)
+}
+
+/// Permute `a` into bit-reversed order, i.e. `out[i] = a[brv(i)]`.
+/// This is an involution.
+///
+/// **Panics** if the length of `a` isn't a power of two.
+pub fn bit_reverse_permutation<T: Copy>(a: &[T]) -> Vec<T> {
+    assert!(a.len().is_power_of_two(), "length must be a power of two");
+    let bits = a.len().trailing_zeros() as usize;
+    (0..a.len()).map(|i| a[bit_reverse(i, bits)]).collect()
+}
+
+/// Pointwise multiplication of two vectors in ℤq.
+pub fn pointwise_mul<T: TRestrictions<T>>(a: &[T], b: &[T], q: T) -> Vec<T> {
+    a.iter().zip(b.iter()).map(|(&x, &y)| x.mul_mod(y, q)).collect()
+}
+
+/// Multiply `a` and `b` in ℤq[x]/(x^d - γ) for d = `a.len()` = `b.len()`.
+pub fn basecase_mul<T: TRestrictions<T>>(a: &[T], b: &[T], gamma: T, q: T) -> Vec<T> {
+    debug_assert!(a.len() == b.len());
+    let d = a.len();
+    let mut out = vec![T::default(); d];
+    for i in 0..d {
+        for j in 0..d {
+            let c = a[i].mul_mod(b[j], q);
+            if i + j < d {
+                out[i + j] = out[i + j].add_mod(c, q);
+            } else {
+                // x^d = γ
+                out[i + j - d] = out[i + j - d].add_mod(c.mul_mod(gamma, q), q);
+            }
+        }
+    }
+    out
+}
+
+/// Precomputed tables for the NTT over ℤq[x]/(x^n + 1).
+#[derive(Clone, Debug, PartialEq)]
+pub struct NttCtx<T> {
+    /// The degree n of the ring.
+    pub n: usize,
+    /// The coefficient modulus q.
+    pub q: T,
+    layers: usize,
+    /// `zetas[k]` = ζ^brv(k) for 0 < k < 2^layers.
+    zetas: Vec<T>,
+    /// The inverses of `zetas`.
+    zetas_inv: Vec<T>,
+    /// `gammas[i]` = ζ^(2 brv(i) + 1), the constants of the factors
+    /// x^d - γ.
+    gammas: Vec<T>,
+    /// (2^layers)^-1 mod q
+    scale: T,
+}
+
+impl<T: TRestrictions<T>> NttCtx<T> {
+    /// The complete NTT with log2(n) layers for a primitive 2n-th root of
+    /// unity `root` mod the prime `q`.
+    pub fn new(n: usize, q: T, root: T) -> Result<Self, NttError> {
+        if !n.is_power_of_two() {
+            return Err(NttError::InvalidLength);
+        }
+        Self::new_incomplete(n, q, root, n.trailing_zeros() as usize)
+    }
+
+    /// The NTT with `layers` layers for a primitive 2^(layers + 1)-th root
+    /// of unity `root` mod the prime `q`.
+    pub fn new_incomplete(n: usize, q: T, root: T, layers: usize) -> Result<Self, NttError> {
+        if layers >= usize::BITS as usize || n & ((1 << layers) - 1) != 0 {
+            return Err(NttError::InvalidLength);
+        }
+        let zero = T::default();
+        let one = T::from_literal(1);
+        let half = T::from_literal(1 << layers);
+        // ζ^(2^layers) = -1 if ζ has order 2^(layers + 1).
+        if T::pow_mod(root, half, q).add_mod(one, q) != zero {
+            return Err(NttError::InvalidRoot);
+        }
+
+        let mut powers = Vec::with_capacity(2 << layers);
+        let mut p = one.add_mod(zero, q);
+        for _ in 0..2 << layers {
+            powers.push(p);
+            p = p.mul_mod(root, q);
+        }
+        let zetas = (0..1 << layers).map(|k| powers[bit_reverse(k, layers)]).collect();
+        // ζ^-j = ζ^(2^(layers + 1) - j)
+        let zetas_inv = (0..1 << layers)
+            .map(|k| powers[((2 << layers) - bit_reverse(k, layers)) % (2 << layers)])
+            .collect();
+        let gammas = (0..1 << layers)
+            .map(|i| powers[2 * bit_reverse(i, layers) + 1])
+            .collect();
+        Ok(Self {
+            n,
+            q,
+            layers,
+            zetas,
+            zetas_inv,
+            gammas,
+            // Fermat's little theorem
+            scale: T::pow_mod(half, q - T::from_literal(2), q),
+        })
+    }
+
+    /// The number of layers k.
+    pub fn layers(&self) -> usize {
+        self.layers
+    }
+
+    /// The twiddle factors ζ^brv(k) in the order they are used by the
+    /// forward transform, starting with ζ^0 = 1.
+    pub fn zetas(&self) -> &[T] {
+        &self.zetas
+    }
+
+    /// The constants γ_i of the factors x^d - γ_i in the order of the NTT
+    /// output.
+    pub fn gammas(&self) -> &[T] {
+        &self.gammas
+    }
+
+    /// Forward NTT using Cooley–Tukey butterflies.
+    /// The output is in bit-reversed order.
+    pub fn ntt(&self, a: &[T]) -> Vec<T> {
+        debug_assert!(a.len() == self.n);
+        let q = self.q;
+        let mut a = a.to_vec();
+        let mut k = 1;
+        for layer in 0..self.layers {
+            let len = self.n >> (layer + 1);
+            for start in (0..self.n).step_by(2 * len) {
+                let zeta = self.zetas[k];
+                k += 1;
+                for j in start..start + len {
+                    let t = zeta.mul_mod(a[j + len], q);
+                    a[j + len] = a[j].sub_mod(t, q);
+                    a[j] = a[j].add_mod(t, q);
+                }
+            }
+        }
+        a
+    }
+
+    /// Inverse NTT using Gentleman–Sande butterflies.
+    /// The input is in bit-reversed order.
+    pub fn inv_ntt(&self, a: &[T]) -> Vec<T> {
+        debug_assert!(a.len() == self.n);
+        let q = self.q;
+        let mut a = a.to_vec();
+        let mut k = (1 << self.layers) - 1;
+        for layer in (0..self.layers).rev() {
+            let len = self.n >> (layer + 1);
+            for start in (0..self.n).step_by(2 * len).rev() {
+                let zeta_inv = self.zetas_inv[k];
+                k -= 1;
+                for j in start..start + len {
+                    let t = a[j];
+                    a[j] = t.add_mod(a[j + len], q);
+                    a[j + len] = t.sub_mod(a[j + len], q).mul_mod(zeta_inv, q);
+                }
+            }
+        }
+        a.iter().map(|&x| x.mul_mod(self.scale, q)).collect()
+    }
+
+    /// Multiply two polynomials in NTT domain.
+    /// This is the pointwise product for the complete NTT and the base-case
+    /// multiplication of the degree d residues otherwise.
+    pub fn mul_ntt(&self, a: &[T], b: &[T]) -> Vec<T> {
+        debug_assert!(a.len() == self.n && b.len() == self.n);
+        let d = self.n >> self.layers;
+        if d == 1 {
+            return pointwise_mul(a, b, self.q);
+        }
+        a.chunks(d)
+            .zip(b.chunks(d))
+            .zip(self.gammas.iter())
+            .flat_map(|((x, y), &gamma)| basecase_mul(x, y, gamma, self.q))
+            .collect()
+    }
+
+    /// Multiply `a` and `b` in ℤq[x]/(x^n + 1) with the quadratic `poly_mul`.
+    /// This is the reference the NTT has to agree with.
+    pub fn negacyclic_mul(&self, a: &[T], b: &[T]) -> Vec<T> {
+        negacyclic_reduce(&poly_mul(a, b, self.q), self.n, self.q)
+    }
+}
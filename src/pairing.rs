@@ -0,0 +1,393 @@
+//!
+//! # Pairings
+//!
+//! The ate pairing e: G1 × G2 → Gt on pairing-friendly curves with embedding
+//! degree 12 and a sextic twist such as BLS12 and BN curves.
+//!
+//! A curve is defined by implementing `PairingCurve`: G1 is the curve over
+//! Fp, G2 the twist over Fp2, both as `WeierstrassCurve`, and Gt is a
+//! subgroup of Fp12 built with `quadratic_extension!` and `cubic_extension!`.
+//! The Miller loop and the final exponentiation are provided by the trait.
+//!
+//! BLS12-381 is provided as `Bls12_381` with the groups `Bls12_381G1` and
+//! `Bls12_381G2`, and BN254 as `Bn254` with `Bn254G1` and `Bn254G2`.
+//!
+//! ```
+//! use hacspec::prelude::*;
+//!
+//! let p = Bls12_381G1::generator();
+//! let q = Bls12_381G2::generator();
+//! let e = Bls12_381::pairing(p, q);
+//! assert!(e != Bls12_381Fp12::one());
+//! assert_eq!(Bls12_381::pairing(Bls12_381G1::neg(p), q), e.inv());
+//! ```
+//!
+
+use crate::prelude::*;
+
+pub trait PairingCurve {
+    type Fp: PrimeField;
+    type Fp2: FieldElement;
+    type Gt: FieldElement;
+    type G1: WeierstrassCurve<Field = Self::Fp>;
+    type G2: WeierstrassCurve<Field = Self::Fp2, Scalar = <Self::G1 as WeierstrassCurve>::Scalar>;
+
+    /// Whether t - 1 is negative for the trace of Frobenius t.
+    const ATE_LOOP_NEGATIVE: bool;
+
+    /// |t - 1| for the trace of Frobenius t, i.e. |x| for BLS12 curves and
+    /// 6x² for BN curves.
+    fn ate_loop_count() -> BigUint;
+
+    /// Evaluate at `p` the line with slope `lambda` through the point `t` on
+    /// the twist, mapped into Gt.
+    /// The result only has to be correct up to a factor in a proper subfield
+    /// of Fp12 because those factors are removed by the final exponentiation.
+    fn line(lambda: Self::Fp2, t: (Self::Fp2, Self::Fp2), p: (Self::Fp, Self::Fp)) -> Self::Gt;
+
+    /// The Miller loop f_{|t - 1|, Q}(P) with affine coordinates on the twist.
+    fn miller_loop(p: Affine<Self::Fp>, q: Affine<Self::Fp2>) -> Self::Gt {
+        let (p, (qx, qy)) = match (p, q) {
+            (Affine::Point(px, py), Affine::Point(qx, qy)) => ((px, py), (qx, qy)),
+            _ => return Self::Gt::one(),
+        };
+        let three = Self::Fp2::from_literal(3);
+        let loop_count = Self::ate_loop_count().to_bytes_le();
+        let mut f = Self::Gt::one();
+        let (mut tx, mut ty) = (qx, qy);
+        for i in (0..Self::ate_loop_count().bits() - 1).rev() {
+            let lambda = (three * tx.square() + Self::G2::a()) * (ty + ty).inv();
+            f = f.square() * Self::line(lambda, (tx, ty), p);
+            let x3 = lambda.square() - tx - tx;
+            ty = lambda * (tx - x3) - ty;
+            tx = x3;
+            if (loop_count[i / 8] >> (i % 8)) & 1 == 1 {
+                // T ≠ ±Q because |t - 1| < r.
+                let lambda = (qy - ty) * (qx - tx).inv();
+                f = f * Self::line(lambda, (tx, ty), p);
+                let x3 = lambda.square() - tx - qx;
+                ty = lambda * (tx - x3) - ty;
+                tx = x3;
+            }
+        }
+        if Self::ATE_LOOP_NEGATIVE {
+            f = f.inv();
+        }
+        f
+    }
+
+    /// f^((p⁶ - 1)(p² + 1))
+    fn final_exponentiation_easy(f: Self::Gt) -> Self::Gt {
+        let f = f.frobenius(6) * f.inv();
+        f.frobenius(2) * f
+    }
+
+    /// f^((p⁴ - p² + 1) / r) for f in the cyclotomic subgroup.
+    /// Curves should override this with a faster addition chain.
+    fn final_exponentiation_hard(f: Self::Gt) -> Self::Gt {
+        let p = Self::Fp::modulus_biguint();
+        let r = <Self::G1 as WeierstrassCurve>::Scalar::modulus_biguint();
+        let p2 = &p * &p;
+        f.pow_biguint(&((&p2 * &p2 - &p2 + 1u32) / r))
+    }
+
+    fn final_exponentiation(f: Self::Gt) -> Self::Gt {
+        Self::final_exponentiation_hard(Self::final_exponentiation_easy(f))
+    }
+
+    fn pairing(p: Affine<Self::Fp>, q: Affine<Self::Fp2>) -> Self::Gt {
+        Self::final_exponentiation(Self::miller_loop(p, q))
+    }
+}
+
+unsigned_integer!(Bls12_381Canvas, 384);
+field_integer!(
+    Bls12_381Fp,
+    Bls12_381Canvas,
+    Bls12_381Canvas::from_bytes_be(&hex_string_to_bytes(
+        "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab"
+    ))
+);
+field_integer!(
+    Bls12_381Scalar,
+    Bls12_381Canvas,
+    Bls12_381Canvas::from_bytes_be(&hex_string_to_bytes(
+        "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001"
+    ))
+);
+// Fp2 = Fp[u]/(u² + 1)
+quadratic_extension!(
+    Bls12_381Fp2,
+    Bls12_381Fp,
+    Bls12_381Fp::from_literal(0) - Bls12_381Fp::from_literal(1)
+);
+// Fp6 = Fp2[v]/(v³ - (u + 1))
+cubic_extension!(
+    Bls12_381Fp6,
+    Bls12_381Fp2,
+    Bls12_381Fp2::new(Bls12_381Fp::from_literal(1), Bls12_381Fp::from_literal(1))
+);
+// Fp12 = Fp6[w]/(w² - v)
+quadratic_extension!(
+    Bls12_381Fp12,
+    Bls12_381Fp6,
+    Bls12_381Fp6::new(Bls12_381Fp2::zero(), Bls12_381Fp2::one(), Bls12_381Fp2::zero())
+);
+
+/// The group G1 of BLS12-381 on y² = x³ + 4 over Fp.
+pub struct Bls12_381G1;
+
+impl WeierstrassCurve for Bls12_381G1 {
+    type Field = Bls12_381Fp;
+    type Scalar = Bls12_381Scalar;
+
+    fn a() -> Bls12_381Fp {
+        Bls12_381Fp::from_literal(0)
+    }
+
+    fn b() -> Bls12_381Fp {
+        Bls12_381Fp::from_literal(4)
+    }
+
+    fn generator() -> Affine<Bls12_381Fp> {
+        Affine::Point(
+            Bls12_381Fp::from_hex(
+                "17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb"
+            ),
+            Bls12_381Fp::from_hex(
+                "08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1"
+            ),
+        )
+    }
+}
+
+/// The group G2 of BLS12-381 on the twist y² = x³ + 4(u + 1) over Fp2.
+pub struct Bls12_381G2;
+
+impl WeierstrassCurve for Bls12_381G2 {
+    type Field = Bls12_381Fp2;
+    type Scalar = Bls12_381Scalar;
+
+    fn a() -> Bls12_381Fp2 {
+        Bls12_381Fp2::zero()
+    }
+
+    fn b() -> Bls12_381Fp2 {
+        Bls12_381Fp2::new(Bls12_381Fp::from_literal(4), Bls12_381Fp::from_literal(4))
+    }
+
+    fn generator() -> Affine<Bls12_381Fp2> {
+        Affine::Point(
+            Bls12_381Fp2::new(
+                Bls12_381Fp::from_hex(
+                    "024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8"
+                ),
+                Bls12_381Fp::from_hex(
+                    "13e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e"
+                ),
+            ),
+            Bls12_381Fp2::new(
+                Bls12_381Fp::from_hex(
+                    "0ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801"
+                ),
+                Bls12_381Fp::from_hex(
+                    "0606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be"
+                ),
+            ),
+        )
+    }
+}
+
+/// The optimal ate pairing on BLS12-381.
+pub struct Bls12_381;
+
+impl Bls12_381 {
+    /// f^x for f in the cyclotomic subgroup, where the inverse is the
+    /// conjugate.
+    fn cyclotomic_exp_by_x(f: Bls12_381Fp12) -> Bls12_381Fp12 {
+        f.pow_biguint(&Bls12_381::ate_loop_count()).conjugate()
+    }
+}
+
+impl PairingCurve for Bls12_381 {
+    type Fp = Bls12_381Fp;
+    type Fp2 = Bls12_381Fp2;
+    type Gt = Bls12_381Fp12;
+    type G1 = Bls12_381G1;
+    type G2 = Bls12_381G2;
+
+    const ATE_LOOP_NEGATIVE: bool = true;
+
+    /// |x| = 0xd201000000010000
+    fn ate_loop_count() -> BigUint {
+        BigUint::from(0xd201000000010000u64)
+    }
+
+    /// The twist point (x, y) corresponds to (x/w², y/w³) on the curve over
+    /// Fp12 with w⁶ = u + 1, i.e. the line is
+    /// y_P - λx_P/w + (λx - y)/w³, which is multiplied by w³ ∈ Fp4.
+    fn line(
+        lambda: Bls12_381Fp2,
+        t: (Bls12_381Fp2, Bls12_381Fp2),
+        p: (Bls12_381Fp, Bls12_381Fp),
+    ) -> Bls12_381Fp12 {
+        let (tx, ty) = t;
+        let (px, py) = p;
+        let zero = Bls12_381Fp2::zero();
+        Bls12_381Fp12::new(
+            Bls12_381Fp6::new(
+                lambda * tx - ty,
+                zero - lambda.mul_base(px),
+                zero,
+            ),
+            Bls12_381Fp6::new(zero, Bls12_381Fp2::from_base(py), zero),
+        )
+    }
+
+    /// The hard part with (p⁴ - p² + 1) / r = λ0 + λ1·p + λ2·p² + λ3·p³ for
+    /// λ3 = (x - 1)² / 3, λ2 = λ3·x, λ1 = λ2·x - λ3, and λ0 = λ1·x + 1.
+    fn final_exponentiation_hard(f: Bls12_381Fp12) -> Bls12_381Fp12 {
+        // x is negative such that |x - 1| = |x| + 1.
+        let abs_x_minus_one = Bls12_381::ate_loop_count() + 1u32;
+        let a3 = f.pow_biguint(&(&abs_x_minus_one * &abs_x_minus_one / 3u32));
+        let a2 = Bls12_381::cyclotomic_exp_by_x(a3);
+        let a1 = Bls12_381::cyclotomic_exp_by_x(a2) * a3.conjugate();
+        let a0 = Bls12_381::cyclotomic_exp_by_x(a1) * f;
+        a0 * a1.frobenius(1) * a2.frobenius(2) * a3.frobenius(3)
+    }
+}
+
+unsigned_integer!(Bn254Canvas, 256);
+field_integer!(
+    Bn254Fp,
+    Bn254Canvas,
+    Bn254Canvas::from_bytes_be(&hex_string_to_bytes(
+        "30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47"
+    ))
+);
+field_integer!(
+    Bn254Scalar,
+    Bn254Canvas,
+    Bn254Canvas::from_bytes_be(&hex_string_to_bytes(
+        "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001"
+    ))
+);
+// Fp2 = Fp[u]/(u² + 1)
+quadratic_extension!(
+    Bn254Fp2,
+    Bn254Fp,
+    Bn254Fp::from_literal(0) - Bn254Fp::from_literal(1)
+);
+// Fp6 = Fp2[v]/(v³ - (u + 9))
+cubic_extension!(
+    Bn254Fp6,
+    Bn254Fp2,
+    Bn254Fp2::new(Bn254Fp::from_literal(9), Bn254Fp::from_literal(1))
+);
+// Fp12 = Fp6[w]/(w² - v)
+quadratic_extension!(
+    Bn254Fp12,
+    Bn254Fp6,
+    Bn254Fp6::new(Bn254Fp2::zero(), Bn254Fp2::one(), Bn254Fp2::zero())
+);
+
+/// The group G1 of BN254 on y² = x³ + 3 over Fp.
+pub struct Bn254G1;
+
+impl WeierstrassCurve for Bn254G1 {
+    type Field = Bn254Fp;
+    type Scalar = Bn254Scalar;
+
+    fn a() -> Bn254Fp {
+        Bn254Fp::from_literal(0)
+    }
+
+    fn b() -> Bn254Fp {
+        Bn254Fp::from_literal(3)
+    }
+
+    fn generator() -> Affine<Bn254Fp> {
+        Affine::Point(Bn254Fp::from_literal(1), Bn254Fp::from_literal(2))
+    }
+}
+
+/// The group G2 of BN254 on the twist y² = x³ + 3/(u + 9) over Fp2.
+pub struct Bn254G2;
+
+impl WeierstrassCurve for Bn254G2 {
+    type Field = Bn254Fp2;
+    type Scalar = Bn254Scalar;
+
+    fn a() -> Bn254Fp2 {
+        Bn254Fp2::zero()
+    }
+
+    fn b() -> Bn254Fp2 {
+        Bn254Fp2::from_literal(3) * Bn254Fp6::non_residue().inv()
+    }
+
+    fn generator() -> Affine<Bn254Fp2> {
+        Affine::Point(
+            Bn254Fp2::new(
+                Bn254Fp::from_hex("1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed"),
+                Bn254Fp::from_hex("198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2"),
+            ),
+            Bn254Fp2::new(
+                Bn254Fp::from_hex("12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa"),
+                Bn254Fp::from_hex("090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b"),
+            ),
+        )
+    }
+}
+
+/// The ate pairing on BN254, also known as alt_bn128.
+pub struct Bn254;
+
+impl Bn254 {
+    /// The curve parameter x = 0x44e992b44a6909f1.
+    fn x() -> BigUint {
+        BigUint::from(0x44e992b44a6909f1u64)
+    }
+}
+
+impl PairingCurve for Bn254 {
+    type Fp = Bn254Fp;
+    type Fp2 = Bn254Fp2;
+    type Gt = Bn254Fp12;
+    type G1 = Bn254G1;
+    type G2 = Bn254G2;
+
+    const ATE_LOOP_NEGATIVE: bool = false;
+
+    /// 6x²
+    fn ate_loop_count() -> BigUint {
+        let x = Bn254::x();
+        &x * &x * 6u32
+    }
+
+    /// The twist point (x, y) corresponds to (x·w², y·w³) on the curve over
+    /// Fp12 with w⁶ = u + 9, i.e. the line is y_P - λx_P·w + (λx - y)·w³.
+    fn line(lambda: Bn254Fp2, t: (Bn254Fp2, Bn254Fp2), p: (Bn254Fp, Bn254Fp)) -> Bn254Fp12 {
+        let (tx, ty) = t;
+        let (px, py) = p;
+        let zero = Bn254Fp2::zero();
+        Bn254Fp12::new(
+            Bn254Fp6::new(Bn254Fp2::from_base(py), zero, zero),
+            Bn254Fp6::new(zero - lambda.mul_base(px), lambda * tx - ty, zero),
+        )
+    }
+
+    /// The hard part with (p⁴ - p² + 1) / r = λ0 + λ1·p + λ2·p² + λ3·p³ for
+    /// λ3 = 1, λ2 = 6x² + 1, λ1 = -36x³ - 18x² - 12x + 1, and
+    /// λ0 = -36x³ - 30x² - 18x - 2.
+    fn final_exponentiation_hard(f: Bn254Fp12) -> Bn254Fp12 {
+        let x = Bn254::x();
+        let x2 = &x * &x;
+        let x3 = &x2 * &x;
+        // The inverse is the conjugate in the cyclotomic subgroup.
+        let a0 = f.pow_biguint(&(&x3 * 36u32 + &x2 * 30u32 + &x * 18u32 + 2u32)).conjugate();
+        let a1 = f.pow_biguint(&(&x3 * 36u32 + &x2 * 18u32 + &x * 12u32 - 1u32)).conjugate();
+        let a2 = f.pow_biguint(&(x2 * 6u32 + 1u32));
+        a0 * a1.frobenius(1) * a2.frobenius(2) * f.frobenius(3)
+    }
+}
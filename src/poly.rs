@@ -46,10 +46,10 @@ fn pad<T: TRestrictions<T>>(v: &[T], l: usize) -> Vec<T> {
     out
 }
 
+/// Strip the zero coefficients above the leading coefficient.
 #[inline]
-fn truncate<T: TRestrictions<T>>(v: &[T]) -> Vec<T> {
-    let (d, c) = leading_coefficient(v);
-    println!("d: {:?}, c: {:x?}", d, c);
+pub(crate) fn truncate<T: TRestrictions<T>>(v: &[T]) -> Vec<T> {
+    let (d, _) = leading_coefficient(v);
     let mut out = vec![T::default(); d + 1];
     for (a, &b) in out.iter_mut().zip(v.iter()) {
         *a = b;
@@ -122,7 +122,8 @@ pub(crate) fn poly_mul_plain<T: TRestrictions<T>>(x: &[T], y: &[T], n: T) -> Vec
 }
 
 /// Polynomial multiplication using operand scanning.
-/// This is very inefficient and prone to side-channel attacks.
+/// This is very inefficient but the sequence of operations only depends on
+/// the lengths of `x` and `y`.
 #[inline]
 pub(crate) fn poly_mul_op_scanning<T: TRestrictions<T>>(x: &[T], y: &[T], n: T) -> Vec<T> {
     let mut out = vec![T::default(); x.len() + y.len()];
@@ -158,6 +159,81 @@ pub fn poly_mul<T: TRestrictions<T>>(x: &[T], y: &[T], n: T) -> Vec<T> {
     out
 }
 
+/// Constant-time polynomial multiplication for secret coefficients in [0, n).
+/// The loop bounds are fixed and no coefficients are skipped such that the
+/// sequence of coefficient operations only depends on the lengths of `x` and
+/// `y`, unlike `poly_mul`.
+/// The coefficients are multiplied and added with `mul_mod_ct` and
+/// `add_mod_ct`, which don't branch on their values for the built-in integers
+/// and the `montgomery_integer!` and `barrett_integer!` types.
+pub fn poly_mul_ct<T: TRestrictions<T>>(x: &[T], y: &[T], n: T) -> Vec<T> {
+    let mut out = vec![T::default(); x.len() + y.len()];
+    for i in 0..x.len() {
+        for j in 0..y.len() {
+            out[i + j] = out[i + j].add_mod_ct(x[i].mul_mod_ct(y[j], n), n);
+        }
+    }
+    out
+}
+
+/// Reduce `a` modulo x^l - 1, i.e. add the coefficient of x^(l + i) onto x^i.
+/// The coefficients have to be in [0, n) and are added with `add_mod_ct`
+/// such that the sequence of operations only depends on the lengths.
+pub fn cyclic_reduce<T: TRestrictions<T>>(a: &[T], l: usize, n: T) -> Vec<T> {
+    let mut out = vec![T::default(); l];
+    for (i, &c) in a.iter().enumerate() {
+        out[i % l] = out[i % l].add_mod_ct(c, n);
+    }
+    out
+}
+
+/// Reduce `a` modulo x^l + 1, i.e. subtract the coefficient of x^(l + i)
+/// from x^i.
+/// The coefficients have to be in [0, n) and are added with `add_mod_ct` and
+/// `sub_mod_ct` such that the sequence of operations only depends on the
+/// lengths.
+pub fn negacyclic_reduce<T: TRestrictions<T>>(a: &[T], l: usize, n: T) -> Vec<T> {
+    let mut out = vec![T::default(); l];
+    for (i, &c) in a.iter().enumerate() {
+        // x^l = -1
+        if (i / l) & 1 == 0 {
+            out[i % l] = out[i % l].add_mod_ct(c, n);
+        } else {
+            out[i % l] = out[i % l].sub_mod_ct(c, n);
+        }
+    }
+    out
+}
+
+/// The shape of the modulus of a quotient ring ℤn[x]/mℤ[x].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RingKind {
+    /// m = x^l - 1
+    Cyclic,
+    /// m = x^l + 1
+    Negacyclic,
+    /// Any other modulus.
+    General,
+}
+
+/// Detect whether the modulus `irr` of degree l = `irr.len() - 1` is
+/// x^l - 1 or x^l + 1 over ℤn.
+pub fn ring_kind<T: TRestrictions<T>>(irr: &[T], n: T) -> RingKind {
+    let zero = T::default();
+    let one = T::from_literal(1);
+    let l = irr.len() - 1;
+    if l == 0 || irr[l] != one || irr[1..l].iter().any(|&c| c != zero) {
+        return RingKind::General;
+    }
+    if irr[0] == one {
+        RingKind::Negacyclic
+    } else if irr[0] == T::from_signed_literal(-1) || (n != zero && irr[0] == n - one) {
+        RingKind::Cyclic
+    } else {
+        RingKind::General
+    }
+}
+
 #[inline]
 pub fn random_poly<T: TRestrictions<T>>(l: usize, min: i128, max: i128) -> Seq<T> {
     let mut rng = rand::thread_rng();
@@ -191,7 +267,7 @@ pub fn euclid_div<T: TRestrictions<T>>(x: &[T], y: &[T], n: T) -> (Vec<T>, Vec<T
             r_c / c
         } else {
             // r_c / c in ℤn is r_c * 1/c.
-            r_c * T::inv(c, n)
+            r_c.mul_mod(T::inv(c, n), n)
         };
         if c_idx == T::default() {
             panic!("c_idx is 0; can't divide these two polynomials");
@@ -211,7 +287,7 @@ pub fn euclid_div<T: TRestrictions<T>>(x: &[T], y: &[T], n: T) -> (Vec<T>, Vec<T
 }
 
 #[inline]
-fn is_zero<T: TRestrictions<T>>(v: &[T]) -> bool {
+pub(crate) fn is_zero<T: TRestrictions<T>>(v: &[T]) -> bool {
     for &x in v {
         if x != T::default() {
             return false;
@@ -220,11 +296,6 @@ fn is_zero<T: TRestrictions<T>>(v: &[T]) -> bool {
     return true;
 }
 
-#[inline]
-fn poly_z_inv<T: TRestrictions<T>>(v: &[T], n: T) -> Vec<T> {
-    v.iter().map(|&x| T::inv(x, n)).collect::<Vec<T>>()
-}
-
 /// Extended euclidean algorithm to compute the inverse of x in ℤ/n
 ///
 /// **Panics** if x is not invertible.
@@ -235,23 +306,26 @@ pub(crate) fn extended_euclid_invert<T: TRestrictions<T>>(x: T, n: T, signed: bo
     let mut r = n;
     let mut new_t = T::from_literal(1);
     let mut new_r = x;
+    let unsigned = T::from_signed_literal(-1) > T::default();
 
     while new_r != T::default() {
         let q: T = r / new_r;
 
         let tmp = new_r.clone();
-        new_r = r.sub_lift(q * new_r, n);
+        new_r = r - q * new_r;
         r = tmp;
 
+        // Unsigned coefficients are kept in ℤn so the product has to be
+        // reduced to not overflow.
+        let q_new_t = if unsigned { q.mul_mod(new_t, n) } else { q * new_t };
         let tmp = new_t.clone();
-        new_t = t.sub_lift(q * new_t, n);
+        new_t = t.sub_lift(q_new_t, n);
         t = tmp;
     }
 
     if r > T::from_literal(1) && x != T::default() {
         panic!("{:x?} is not invertible in ℤ/{:x?}", x, n);
     }
-    println!("xeucl: {:?}", t);
     if t < T::default() {
         if signed {
             t = t.abs()
@@ -263,45 +337,202 @@ pub(crate) fn extended_euclid_invert<T: TRestrictions<T>>(x: T, n: T, signed: bo
     t
 }
 
-/// Extended euclidean algorithm to compute the inverse of x in yℤ[x]
+/// Errors returned by the polynomial GCD and resultant functions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PolyError {
+    /// Division by the zero polynomial.
+    DivisionByZero,
+    /// A leading coefficient isn't a unit in ℤn, e.g. because n isn't prime.
+    /// Over ℤ (n = 0) only ±1 are units.
+    NonUnitLeadingCoefficient,
+    /// The polynomial isn't invertible because the GCD isn't 1.
+    NotInvertible,
+    /// The polynomial is constant, e.g. when computing a discriminant.
+    ConstantPolynomial,
+    /// The polynomial has a repeated factor.
+    NotSquarefree,
+    /// The polynomial isn't a product of distinct irreducible factors of the
+    /// given degree.
+    NotEqualDegree,
+}
+
+/// The inverse of the unit `c` in ℤn.
+fn unit_inv<T: TRestrictions<T>>(c: T, n: T) -> Result<T, PolyError> {
+    let one = T::from_literal(1);
+    if n == T::default() {
+        return if c == one || c == T::from_signed_literal(-1) {
+            Ok(c)
+        } else {
+            Err(PolyError::NonUnitLeadingCoefficient)
+        };
+    }
+    let c = c.add_mod(T::default(), n);
+    if c == T::default() || gcd(c, n) != one {
+        return Err(PolyError::NonUnitLeadingCoefficient);
+    }
+    Ok(extended_euclid_invert(c, n, false))
+}
+
 #[inline]
-pub fn extended_euclid<T: TRestrictions<T>>(x: &[T], y: &[T], n: T) -> Result<Vec<T>, &'static str> {
-    let (x, y) = normalize(x, y);
-    println!("euclid: {:?} / {:?}", x, y);
+fn poly_scale<T: TRestrictions<T>>(x: &[T], c: T, n: T) -> Vec<T> {
+    x.iter().map(|&a| a.mul_mod(c, n)).collect()
+}
+
+/// Division with remainder x = q·y + r that fails instead of panicking if
+/// the leading coefficient of y isn't a unit.
+pub(crate) fn poly_divrem<T: TRestrictions<T>>(x: &[T], y: &[T], n: T) -> Result<(Vec<T>, Vec<T>), PolyError> {
+    if is_zero(y) {
+        return Err(PolyError::DivisionByZero);
+    }
+    // Divide by the monic y / c and scale the quotient back.
+    let c_inv = unit_inv(leading_coefficient(y).1, n)?;
+    let (q, r) = euclid_div(x, &poly_scale(y, c_inv, n), n);
+    Ok((truncate(&poly_scale(&q, c_inv, n)), truncate(&r)))
+}
 
-    let mut new_t = vec![T::default(); x.len()];
-    new_t[0] = T::from_literal(1);
+/// Scale `x` such that its leading coefficient is 1.
+pub fn poly_monic<T: TRestrictions<T>>(x: &[T], n: T) -> Result<Vec<T>, PolyError> {
+    let c_inv = unit_inv(leading_coefficient(x).1, n)?;
+    Ok(truncate(&poly_scale(x, c_inv, n)))
+}
 
-    let mut new_r = x.clone();
+/// The GCD `g` and the Bézout coefficients `s` and `t` as `(g, s, t)`.
+pub type Xgcd<T> = (Vec<T>, Vec<T>, Vec<T>);
 
-    let mut t = vec![T::default(); x.len()];
-    let mut r = y.clone();
+/// Extended euclidean algorithm on polynomials over ℤn.
+/// Returns `(g, s, t)` with `s·x + t·y = g` where `g` is the monic GCD of
+/// x and y, or 0 if both are 0.
+///
+/// Fails if a leading coefficient on the way isn't a unit, which can't
+/// happen for prime n.
+pub fn poly_xgcd<T: TRestrictions<T>>(x: &[T], y: &[T], n: T) -> Result<Xgcd<T>, PolyError> {
+    let zero = vec![T::default()];
+    let one = vec![T::from_literal(1)];
+    let (mut r, mut new_r) = (truncate(x), truncate(y));
+    let (mut s, mut new_s) = (one.clone(), zero.clone());
+    let (mut t, mut new_t) = (zero.clone(), one);
 
     while !is_zero(&new_r) {
-        println!("{:?} / {:?}", r, new_r);
-        let q = euclid_div(&r, &new_r, n).0;
+        let (q, rem) = poly_divrem(&r, &new_r, n)?;
+        r = std::mem::replace(&mut new_r, rem);
+        let s_next = truncate(&poly_sub(&s, &poly_mul(&q, &new_s, n), n));
+        s = std::mem::replace(&mut new_s, s_next);
+        let t_next = truncate(&poly_sub(&t, &poly_mul(&q, &new_t, n), n));
+        t = std::mem::replace(&mut new_t, t_next);
+    }
 
-        let tmp = new_r.clone();
-        new_r = poly_sub(&r, &poly_mul(&q, &new_r, n), n);
-        r = tmp;
+    if is_zero(&r) {
+        return Ok((zero.clone(), s, t));
+    }
+    let c_inv = unit_inv(leading_coefficient(&r).1, n)?;
+    Ok((
+        truncate(&poly_scale(&r, c_inv, n)),
+        truncate(&poly_scale(&s, c_inv, n)),
+        truncate(&poly_scale(&t, c_inv, n)),
+    ))
+}
 
-        let tmp = new_t.clone();
-        new_t = poly_sub(&t, &poly_mul(&q, &new_t, n), n);
-        t = tmp;
+/// The monic greatest common divisor of x and y over ℤn.
+pub fn poly_gcd<T: TRestrictions<T>>(x: &[T], y: &[T], n: T) -> Result<Vec<T>, PolyError> {
+    Ok(poly_xgcd(x, y, n)?.0)
+}
+
+/// Extended euclidean algorithm to compute the inverse of x in ℤn[x]/y.
+#[inline]
+pub fn extended_euclid<T: TRestrictions<T>>(x: &[T], y: &[T], n: T) -> Result<Vec<T>, PolyError> {
+    let (g, s, _) = poly_xgcd(x, y, n)?;
+    if g != [T::from_literal(1)] {
+        return Err(PolyError::NotInvertible);
     }
+    Ok(poly_divrem(&s, y, n)?.1)
+}
 
-    if leading_coefficient(&r).0 > 0 {
-        return Err("Could not invert the polynomial");
+/// The resultant of x and y over ℤn, i.e. lc(x)^deg(y)·Π y(α) for the roots
+/// α of x, computed with the euclidean algorithm.
+/// The degrees are the actual degrees of x and y.
+pub fn poly_resultant<T: TRestrictions<T>>(x: &[T], y: &[T], n: T) -> Result<T, PolyError> {
+    let zero = T::default();
+    let one = T::from_literal(1);
+    let (mut a, mut b) = (truncate(x), truncate(y));
+    let mut res = one;
+    loop {
+        if is_zero(&a) || is_zero(&b) {
+            return Ok(zero);
+        }
+        let (deg_a, _) = leading_coefficient(&a);
+        let (deg_b, lc_b) = leading_coefficient(&b);
+        if deg_b == 0 {
+            return Ok(res.mul_mod(T::pow_mod(lc_b, T::from_literal(deg_a as u128), n), n));
+        }
+        let r = poly_divrem(&a, &b, n)?.1;
+        if is_zero(&r) {
+            return Ok(zero);
+        }
+        // Res(a, b) = (-1)^(deg a·deg b)·lc(b)^(deg a - deg r)·Res(b, r)
+        let deg_r = leading_coefficient(&r).0;
+        res = res.mul_mod(T::pow_mod(lc_b, T::from_literal((deg_a - deg_r) as u128), n), n);
+        if deg_a & deg_b & 1 == 1 {
+            res = zero.sub_mod(res, n);
+        }
+        a = b;
+        b = r;
     }
+}
+
+/// The discriminant (-1)^(d(d-1)/2)·Res(x, x')/lc(x) of x with degree d ≥ 1
+/// over ℤn. It is 0 if and only if x has a repeated root.
+pub fn poly_discriminant<T: TRestrictions<T>>(x: &[T], n: T) -> Result<T, PolyError> {
+    let zero = T::default();
+    let (d, lc) = leading_coefficient(x);
+    if d == 0 {
+        return Err(PolyError::ConstantPolynomial);
+    }
+    let lc_inv = unit_inv(lc, n)?;
+    let derivative = poly_derivative(&truncate(x), n);
+    // The resultant with the formal degree d - 1 of x' in case the degree
+    // drops modulo n.
+    let deg_derivative = leading_coefficient(&derivative).0;
+    let res = poly_resultant(x, &derivative, n)?.mul_mod(
+        T::pow_mod(lc, T::from_literal((d - 1 - deg_derivative) as u128), n),
+        n,
+    );
+    let disc = res.mul_mod(lc_inv, n);
+    if (d * (d - 1) / 2) & 1 == 1 {
+        Ok(zero.sub_mod(disc, n))
+    } else {
+        Ok(disc)
+    }
+}
 
-    Ok(poly_mul(&t, &poly_z_inv(&r, n), n))
+/// Chinese remainder theorem on coefficient vectors.
+/// `residues[i]` is a polynomial over ℤ(moduli[i]), e.g. the result of
+/// `poly_mul` with `moduli[i]`. The result is the polynomial over ℤm, with
+/// `m` the product of all moduli, that reduces to each of them.
+///
+/// **Note** `m` must fit into `T`.
+pub fn poly_crt<T: TRestrictions<T>>(residues: &[Vec<T>], moduli: &[T]) -> Result<Vec<T>, CrtError> {
+    if residues.len() != moduli.len() || moduli.is_empty() {
+        return Err(CrtError::LengthMismatch);
+    }
+    let l = residues.iter().map(|r| r.len()).max().unwrap();
+    let residues: Vec<Vec<T>> = residues.iter().map(|r| pad(r, l)).collect();
+    (0..l)
+        .map(|i| {
+            let coefficients: Vec<T> = residues.iter().map(|r| r[i]).collect();
+            crt(&coefficients, moduli)
+        })
+        .collect()
 }
 
 #[macro_export]
 macro_rules! poly {
-    ($name:ident, $t:ty, $l:expr, $n:expr, $m:expr) => {
+    (@define $name:ident, $t:ty, $l:expr, $n:expr, $m:expr, $strategy:expr, $validate:expr) => {
         /// The poly struct for fixed-length polynomials.
         /// Every polynomial is over ℤn[x]/mℤ[x] and reduced by mℤ[x].
+        /// Products are computed with the `MulStrategy` `$strategy`.
+        /// If the macro is called with `validate`, m has to be irreducible over
+        /// the prime field ℤn, which is checked when the first polynomial is
+        /// created.
         #[derive(Clone, Copy)]
         struct $name {
             poly: [$t; $l],
@@ -309,6 +540,25 @@ macro_rules! poly {
             n: $t,
         }
         impl $name {
+            /// The modulus m.
+            ///
+            /// **Panics** if m is validated and isn't irreducible or the
+            /// irreducibility test fails.
+            fn modulus() -> [$t; $l+1] {
+                let mut irr = [<$t>::default(); $l+1];
+                for c in $m.iter() {
+                    irr[c.0] = c.1;
+                }
+                if $validate {
+                    static IRREDUCIBLE: std::sync::OnceLock<Result<bool, PolyError>> = std::sync::OnceLock::new();
+                    match *IRREDUCIBLE.get_or_init(|| poly_is_irreducible(&irr, $n)) {
+                        Ok(true) => (),
+                        Ok(false) => panic!("the modulus of {} is not irreducible", stringify!($name)),
+                        Err(e) => panic!("the modulus of {} can't be validated: {:?}", stringify!($name), e),
+                    }
+                }
+                irr
+            }
             /// Get a new sparse polynomial.
             /// For other polynomials use `new_full`.
             fn new(p: &[(usize, $t)]) -> $name {
@@ -316,10 +566,7 @@ macro_rules! poly {
                 for c in p.iter() {
                     poly[c.0] = c.1;
                 }
-                let mut irr = [<$t>::default(); $l+1];
-                for c in $m.iter() {
-                    irr[c.0] = c.1;
-                }
+                let irr = $name::modulus();
                 Self {
                     poly: poly,
                     irr: irr,
@@ -328,10 +575,7 @@ macro_rules! poly {
             }
             /// Get a new polynomial from a full array with coefficients.
             fn new_full(p: [$t; $l]) -> $name {
-                let mut irr = [<$t>::default(); $l+1];
-                for c in $m.iter() {
-                    irr[c.0] = c.1;
-                }
+                let irr = $name::modulus();
                 Self {
                     poly: p,
                     irr: irr,
@@ -340,13 +584,10 @@ macro_rules! poly {
             }
             /// Generate a random polynomial with coefficients between 0 and $n.
             fn random() -> $name {
-                let mut irr = [<$t>::default(); $l+1];
-                for c in $m.iter() {
-                    irr[c.0] = c.1;
-                }
+                let irr = $name::modulus();
                 let mut rng = rand::thread_rng();
                 let p_vec: Vec<$t> = (0..$l)
-                    .map(|_| rng.gen_range(0, $n))
+                    .map(|_| rng.gen_range(<$t>::default(), $n))
                     .collect();
                 let mut p = [<$t>::default(); $l];
                 for (a, b) in p.iter_mut().zip(p_vec.iter()) {
@@ -381,7 +622,6 @@ macro_rules! poly {
             /// Invert this polynomial.
             /// **Panics** if the polynomial is not invertible.
             fn inv(self) -> Self {
-                println!("inv {:?}", self);
                 Self::from(extended_euclid(&self.poly, &self.irr, self.n).unwrap())
             }
         }
@@ -397,10 +637,7 @@ macro_rules! poly {
                 for (a, b) in p.iter_mut().zip(v.iter()) {
                     *a = *b;
                 }
-                let mut irr = [<$t>::default(); $l+1];
-                for c in $m.iter() {
-                    irr[c.0] = c.1;
-                }
+                let irr = $name::modulus();
                 $name {
                     poly: p,
                     irr: irr,
@@ -409,6 +646,12 @@ macro_rules! poly {
             }
         }
 
+        impl From<Seq<$t>> for $name {
+            fn from(v: Seq<$t>) -> $name {
+                Self::from(v.raw().to_vec())
+            }
+        }
+
         impl fmt::Debug for $name {
             // TODO: ugh
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -462,16 +705,28 @@ macro_rules! poly {
         }
 
         /// Polynomial multiplication on ℤn[x]/mℤ[x]
+        /// The product is reduced in linear time if m is x^l ± 1 and with
+        /// `euclid_div` otherwise.
         impl Mul for $name {
             type Output = Self;
             fn mul(self, rhs: Self) -> Self::Output {
                 debug_assert!(self.compatible(&rhs));
-                let tmp = poly_mul(&self.poly, &rhs.poly, self.n);
-                let r = euclid_div(&tmp, &self.irr, self.n).1;
+                let tmp = poly_mul_with(&self.poly, &rhs.poly, self.n, $strategy);
+                let r = match ring_kind(&self.irr, self.n) {
+                    RingKind::Cyclic => cyclic_reduce(&tmp, $l, self.n),
+                    RingKind::Negacyclic => negacyclic_reduce(&tmp, $l, self.n),
+                    RingKind::General => euclid_div(&tmp, &self.irr, self.n).1,
+                };
                 Self::from(r)
             }
         }
 
+        impl RingElement for $name {
+            fn zero() -> Self {
+                $name::new(&[])
+            }
+        }
+
         /// Polynomial division on ℤn[x]/mℤ[x]
         impl Div for $name {
             type Output = (Self, Self);
@@ -482,6 +737,18 @@ macro_rules! poly {
             }
         }
     };
+    ($name:ident, $t:ty, $l:expr, $n:expr, $m:expr) => {
+        poly!(@define $name, $t, $l, $n, $m, MulStrategy::Sparse, false);
+    };
+    ($name:ident, $t:ty, $l:expr, $n:expr, $m:expr, validate) => {
+        poly!(@define $name, $t, $l, $n, $m, MulStrategy::Sparse, true);
+    };
+    ($name:ident, $t:ty, $l:expr, $n:expr, $m:expr, $strategy:expr) => {
+        poly!(@define $name, $t, $l, $n, $m, $strategy, false);
+    };
+    ($name:ident, $t:ty, $l:expr, $n:expr, $m:expr, $strategy:expr, validate) => {
+        poly!(@define $name, $t, $l, $n, $m, $strategy, true);
+    };
 }
 
 /// Polynomial multiplication on ℤ[x]
@@ -0,0 +1,165 @@
+//!
+//! # Module lattices
+//!
+//! `PolyVector<R, K>` and `PolyMatrix<R, K, L>` are vectors of length K and
+//! K×L matrices over a ring R as used in module lattice schemes such as
+//! ML-KEM (Kyber) and ML-DSA (Dilithium).
+//! R can be any type implementing `RingElement`, e.g. a polynomial defined
+//! with `poly!` or a type for ring elements in NTT domain.
+//!
+//! `map` converts between rings, e.g. to apply the NTT to every element, and
+//! `PolyMatrix::expand` builds a matrix from a seed with a sampling function
+//! such as the `SampleNTT(ρ‖j‖i)` of ML-KEM.
+//!
+//! ```
+//! use hacspec::prelude::*;
+//!
+//! poly!(Rq, u128, 256, 3329, &[(0, 1), (256, 1)]);
+//!
+//! let a = PolyMatrix::<Rq, 2, 3>::from_fn(|_, _| Rq::random());
+//! let s = PolyVector::<Rq, 3>::from_fn(|_| Rq::random());
+//! let t = a * s;
+//! assert_eq!(t.dot(&t), (s * a.transpose()).dot(&(a * s)));
+//! ```
+//!
+
+use crate::prelude::*;
+
+/// A commutative ring with the operations needed for vectors and matrices.
+pub trait RingElement:
+    Clone + PartialEq + fmt::Debug + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    fn zero() -> Self;
+}
+
+/// A vector of K ring elements.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PolyVector<R, const K: usize>(pub [R; K]);
+
+/// A K×L matrix of ring elements stored row by row.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PolyMatrix<R, const K: usize, const L: usize>(pub [[R; L]; K]);
+
+impl<R: RingElement, const K: usize> PolyVector<R, K> {
+    pub fn zero() -> Self {
+        Self::from_fn(|_| R::zero())
+    }
+
+    /// The vector with the elements `f(i)`.
+    pub fn from_fn<F: FnMut(usize) -> R>(f: F) -> Self {
+        Self(std::array::from_fn(f))
+    }
+
+    /// Expand a vector from `seed` with `sample(seed, i)` for the element
+    /// at position i.
+    pub fn expand<F: Fn(&Seq<U8>, usize) -> R>(seed: &Seq<U8>, sample: F) -> Self {
+        Self::from_fn(|i| sample(seed, i))
+    }
+
+    /// Apply `f` to every element, e.g. the NTT.
+    pub fn map<S: RingElement, F: Fn(&R) -> S>(&self, f: F) -> PolyVector<S, K> {
+        PolyVector::from_fn(|i| f(&self.0[i]))
+    }
+
+    /// The inner product Σ self[i]·other[i].
+    pub fn dot(&self, other: &Self) -> R {
+        self.0
+            .iter()
+            .zip(other.0.iter())
+            .fold(R::zero(), |acc, (a, b)| acc + a.clone() * b.clone())
+    }
+
+    /// Multiply every element with `r`.
+    pub fn mul_scalar(&self, r: &R) -> Self {
+        self.map(|a| a.clone() * r.clone())
+    }
+}
+
+impl<R: RingElement, const K: usize, const L: usize> PolyMatrix<R, K, L> {
+    pub fn zero() -> Self {
+        Self::from_fn(|_, _| R::zero())
+    }
+
+    /// The matrix with the elements `f(i, j)` in row i and column j.
+    pub fn from_fn<F: FnMut(usize, usize) -> R>(mut f: F) -> Self {
+        Self(std::array::from_fn(|i| std::array::from_fn(|j| f(i, j))))
+    }
+
+    /// Expand a matrix from `seed` with `sample(seed, i, j)` for the element
+    /// in row i and column j.
+    /// For ML-KEM, `sample` would be `SampleNTT(ρ‖j‖i)`.
+    pub fn expand<F: Fn(&Seq<U8>, usize, usize) -> R>(seed: &Seq<U8>, sample: F) -> Self {
+        Self::from_fn(|i, j| sample(seed, i, j))
+    }
+
+    /// Apply `f` to every element, e.g. the NTT.
+    pub fn map<S: RingElement, F: Fn(&R) -> S>(&self, f: F) -> PolyMatrix<S, K, L> {
+        PolyMatrix::from_fn(|i, j| f(&self.0[i][j]))
+    }
+
+    pub fn transpose(&self) -> PolyMatrix<R, L, K> {
+        PolyMatrix::from_fn(|i, j| self.0[j][i].clone())
+    }
+
+    pub fn row(&self, i: usize) -> PolyVector<R, L> {
+        PolyVector(self.0[i].clone())
+    }
+
+    pub fn column(&self, j: usize) -> PolyVector<R, K> {
+        PolyVector::from_fn(|i| self.0[i][j].clone())
+    }
+}
+
+impl<R: RingElement, const K: usize> Add for PolyVector<R, K> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::from_fn(|i| self.0[i].clone() + rhs.0[i].clone())
+    }
+}
+
+impl<R: RingElement, const K: usize> Sub for PolyVector<R, K> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::from_fn(|i| self.0[i].clone() - rhs.0[i].clone())
+    }
+}
+
+/// The row vector times matrix product selfᵀ·rhs.
+impl<R: RingElement, const K: usize, const L: usize> Mul<PolyMatrix<R, K, L>> for PolyVector<R, K> {
+    type Output = PolyVector<R, L>;
+    fn mul(self, rhs: PolyMatrix<R, K, L>) -> PolyVector<R, L> {
+        PolyVector::from_fn(|j| self.dot(&rhs.column(j)))
+    }
+}
+
+impl<R: RingElement, const K: usize, const L: usize> Add for PolyMatrix<R, K, L> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::from_fn(|i, j| self.0[i][j].clone() + rhs.0[i][j].clone())
+    }
+}
+
+impl<R: RingElement, const K: usize, const L: usize> Sub for PolyMatrix<R, K, L> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::from_fn(|i, j| self.0[i][j].clone() - rhs.0[i][j].clone())
+    }
+}
+
+/// The matrix-vector product.
+impl<R: RingElement, const K: usize, const L: usize> Mul<PolyVector<R, L>> for PolyMatrix<R, K, L> {
+    type Output = PolyVector<R, K>;
+    fn mul(self, rhs: PolyVector<R, L>) -> PolyVector<R, K> {
+        PolyVector::from_fn(|i| self.row(i).dot(&rhs))
+    }
+}
+
+/// The matrix product of a K×L and an L×M matrix.
+impl<R: RingElement, const K: usize, const L: usize, const M: usize> Mul<PolyMatrix<R, L, M>>
+    for PolyMatrix<R, K, L>
+{
+    type Output = PolyMatrix<R, K, M>;
+    fn mul(self, rhs: PolyMatrix<R, L, M>) -> PolyMatrix<R, K, M> {
+        PolyMatrix::from_fn(|i, j| self.row(i).dot(&rhs.column(j)))
+    }
+}
@@ -5,10 +5,26 @@
 //! ```
 
 pub use crate::array::*;
+pub use crate::binary_field::*;
+pub use crate::edwards::*;
+pub use crate::encoding::*;
+pub use crate::factorization::*;
+pub use crate::fast_mul::*;
+pub use crate::field::*;
+pub use crate::hash_to_curve::*;
+pub use crate::montgomery::*;
+pub use crate::ntt::*;
+pub use crate::pairing::*;
 pub use crate::poly::*;
+pub use crate::poly_matrix::*;
+pub use crate::primes::*;
+pub use crate::reduction::*;
+pub use crate::sampling::*;
 pub use crate::seq::*;
 pub use crate::util::*;
+pub use crate::weierstrass::*;
 pub use crate::integer::*;
+pub use crate::interpolation::*;
 pub use crate::*;
 pub use abstract_integers::*;
 pub use num::{self, BigUint, CheckedSub, Num, Zero};
@@ -0,0 +1,406 @@
+//!
+//! # Primes
+//!
+//! This module implements primality tests and prime generation over `BigUint`.
+//!
+//! * Miller–Rabin with random bases from any `Rng` or from a fixed seed
+//! * Strong Lucas probable prime test
+//! * Baillie–PSW, which is used by `is_prime`
+//! * Generation of random primes and safe primes of a given bit length
+//! * Provable prime generation with the Shawe-Taylor algorithm from FIPS 186-5
+//!
+//! All randomised functions take an `Rng` such that specs can use
+//! `rand::thread_rng()` or a seeded generator for reproducible results.
+//!
+
+use crate::prelude::*;
+use num::integer::Integer as NumInteger;
+use num::ToPrimitive;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// Errors returned by the prime generation functions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PrimeError {
+    /// The requested bit length is too small.
+    InvalidLength,
+    /// The prime generation counter was exceeded (FIPS 186-5 `FAILURE`).
+    Failure,
+}
+
+/// Bound for the trial divisions in `baillie_psw`.
+const SMALL_PRIME_BOUND: u32 = 1000;
+
+fn small_primes() -> Vec<u32> {
+    let mut sieve = vec![true; SMALL_PRIME_BOUND as usize];
+    let mut primes = Vec::new();
+    for i in 2..SMALL_PRIME_BOUND as usize {
+        if sieve[i] {
+            primes.push(i as u32);
+            for j in (i * i..SMALL_PRIME_BOUND as usize).step_by(i) {
+                sieve[j] = false;
+            }
+        }
+    }
+    primes
+}
+
+#[inline]
+fn bit(x: &BigUint, i: usize) -> bool {
+    let bytes = x.to_bytes_le();
+    i / 8 < bytes.len() && (bytes[i / 8] >> (i % 8)) & 1 == 1
+}
+
+#[inline]
+fn sub_mod_big(a: &BigUint, b: &BigUint, n: &BigUint) -> BigUint {
+    ((a % n) + n - (b % n)) % n
+}
+
+/// `x / 2 mod n` for `x < n` and odd `n`.
+#[inline]
+fn half_mod(x: BigUint, n: &BigUint) -> BigUint {
+    if x.is_odd() {
+        (x + n) >> 1
+    } else {
+        x >> 1
+    }
+}
+
+/// Get a uniformly random integer with at most `bits` bits.
+pub fn random_bits<R: Rng + ?Sized>(bits: usize, rng: &mut R) -> BigUint {
+    let mut bytes = vec![0u8; bits.div_ceil(8)];
+    rng.fill_bytes(&mut bytes);
+    if !bits.is_multiple_of(8) {
+        bytes[0] &= 0xFF >> (8 - bits % 8);
+    }
+    BigUint::from_bytes_be(&bytes)
+}
+
+/// Get a uniformly random integer in [0, bound) using rejection sampling.
+pub fn random_below<R: Rng + ?Sized>(bound: &BigUint, rng: &mut R) -> BigUint {
+    debug_assert!(!bound.is_zero());
+    let bits = bound.bits();
+    loop {
+        let r = random_bits(bits, rng);
+        if &r < bound {
+            return r;
+        }
+    }
+}
+
+/// Jacobi symbol (a/n) for odd `n`.
+pub fn jacobi_big(a: &BigUint, n: &BigUint) -> i8 {
+    let mut a = a % n;
+    let mut n = n.clone();
+    let mut result = 1;
+    while !a.is_zero() {
+        while a.is_even() {
+            a >>= 1;
+            let r = (&n % 8u32).to_u32().unwrap();
+            if r == 3 || r == 5 {
+                result = -result;
+            }
+        }
+        std::mem::swap(&mut a, &mut n);
+        if (&a % 4u32).to_u32().unwrap() == 3 && (&n % 4u32).to_u32().unwrap() == 3 {
+            result = -result;
+        }
+        a = &a % &n;
+    }
+    if n == BigUint::from(1u32) {
+        result
+    } else {
+        0
+    }
+}
+
+/// Strong probable prime test for the odd `n > 3` to base `a`.
+pub fn miller_rabin_base(n: &BigUint, a: &BigUint) -> bool {
+    let one = BigUint::from(1u32);
+    let n_minus_one = n - &one;
+    let mut d = n_minus_one.clone();
+    let mut s = 0;
+    while d.is_even() {
+        d >>= 1;
+        s += 1;
+    }
+
+    let mut x = a.modpow(&d, n);
+    if x == one || x == n_minus_one {
+        return true;
+    }
+    for _ in 1..s {
+        x = (&x * &x) % n;
+        if x == n_minus_one {
+            return true;
+        }
+        if x == one {
+            return false;
+        }
+    }
+    false
+}
+
+/// Miller–Rabin test with `rounds` random bases drawn from `rng`.
+pub fn miller_rabin<R: Rng + ?Sized>(n: &BigUint, rounds: usize, rng: &mut R) -> bool {
+    let two = BigUint::from(2u32);
+    let three = BigUint::from(3u32);
+    if n < &two {
+        return false;
+    }
+    if n <= &three {
+        return true;
+    }
+    if n.is_even() {
+        return false;
+    }
+    // Bases are in [2, n - 2].
+    let bound = n - &three;
+    (0..rounds).all(|_| miller_rabin_base(n, &(random_below(&bound, rng) + &two)))
+}
+
+/// Miller–Rabin test where the bases are derived deterministically from `seed`.
+pub fn miller_rabin_seeded(n: &BigUint, rounds: usize, seed: u64) -> bool {
+    miller_rabin(n, rounds, &mut StdRng::seed_from_u64(seed))
+}
+
+/// Strong Lucas probable prime test with Selfridge's parameters
+/// (P = 1, Q = (1 - D) / 4 for the first D in 5, -7, 9, -11, ... with
+/// (D/n) = -1).
+pub fn strong_lucas(n: &BigUint) -> bool {
+    let one = BigUint::from(1u32);
+    if n <= &one || n.is_even() {
+        return n == &BigUint::from(2u32);
+    }
+    let root = n.sqrt();
+    if &root * &root == *n {
+        // There's no D with (D/n) = -1 for squares.
+        return false;
+    }
+
+    // Find D. d_abs is |D|, d_mod is D mod n.
+    let mut d_abs = 5u32;
+    let mut positive = true;
+    let d_mod = loop {
+        let d = BigUint::from(d_abs);
+        let d_mod = if positive {
+            &d % n
+        } else {
+            sub_mod_big(n, &d, n)
+        };
+        match jacobi_big(&d_mod, n) {
+            -1 => break d_mod,
+            0 => return n == &d,
+            _ => (),
+        }
+        d_abs += 2;
+        positive = !positive;
+    };
+    // Q = (1 - D) / 4
+    let q_abs = BigUint::from(if positive { (d_abs - 1) / 4 } else { (d_abs + 1) / 4 });
+    let q = if positive {
+        sub_mod_big(n, &q_abs, n)
+    } else {
+        &q_abs % n
+    };
+
+    // n + 1 = d * 2^s
+    let mut d = n + &one;
+    let mut s = 0;
+    while d.is_even() {
+        d >>= 1;
+        s += 1;
+    }
+
+    // Compute U_d, V_d, and Q^d left to right.
+    let mut u = one.clone();
+    let mut v = one.clone();
+    let mut qk = q.clone();
+    for i in (0..d.bits() - 1).rev() {
+        u = (&u * &v) % n;
+        v = sub_mod_big(&(&v * &v), &(&qk << 1), n);
+        qk = (&qk * &qk) % n;
+        if bit(&d, i) {
+            let u_next = half_mod((&u + &v) % n, n);
+            let v_next = half_mod((&d_mod * &u + &v) % n, n);
+            u = u_next;
+            v = v_next;
+            qk = (&qk * &q) % n;
+        }
+    }
+
+    if u.is_zero() || v.is_zero() {
+        return true;
+    }
+    for _ in 1..s {
+        v = sub_mod_big(&(&v * &v), &(&qk << 1), n);
+        if v.is_zero() {
+            return true;
+        }
+        qk = (&qk * &qk) % n;
+    }
+    false
+}
+
+/// Baillie–PSW probable prime test.
+/// There are no known composites passing this test.
+pub fn baillie_psw(n: &BigUint) -> bool {
+    if n < &BigUint::from(2u32) {
+        return false;
+    }
+    for p in small_primes() {
+        if n == &BigUint::from(p) {
+            return true;
+        }
+        if (n % p).is_zero() {
+            return false;
+        }
+    }
+    miller_rabin_base(n, &BigUint::from(2u32)) && strong_lucas(n)
+}
+
+/// Check if `n` is prime using `baillie_psw`.
+pub fn is_prime(n: &BigUint) -> bool {
+    baillie_psw(n)
+}
+
+/// Generate a random prime with exactly `bits` bits.
+///
+/// **Panics** if `bits < 2`.
+pub fn random_prime<R: Rng + ?Sized>(bits: usize, rng: &mut R) -> BigUint {
+    assert!(bits >= 2, "There are no primes with less than 2 bits.");
+    let top = BigUint::from(1u32) << (bits - 1);
+    loop {
+        let c = random_bits(bits - 1, rng) | &top | BigUint::from(1u32);
+        if bits == 2 || baillie_psw(&c) {
+            return c;
+        }
+    }
+}
+
+/// Generate a random safe prime `p = 2q + 1` with prime `q` and exactly
+/// `bits` bits.
+///
+/// **Panics** if `bits < 3`.
+pub fn random_safe_prime<R: Rng + ?Sized>(bits: usize, rng: &mut R) -> BigUint {
+    assert!(bits >= 3, "There are no safe primes with less than 3 bits.");
+    let primes = small_primes();
+    let top = BigUint::from(1u32) << (bits - 2);
+    'candidates: loop {
+        let q = random_bits(bits - 2, rng) | &top | BigUint::from(1u32);
+        let p: BigUint = (&q << 1) + 1u32;
+        // Sieve both candidates before running the expensive tests.
+        for &r in primes.iter() {
+            let r = BigUint::from(r);
+            if (q > r && (&q % &r).is_zero()) || (p > r && (&p % &r).is_zero()) {
+                continue 'candidates;
+            }
+        }
+        if baillie_psw(&q) && baillie_psw(&p) {
+            return p;
+        }
+    }
+}
+
+/// Deterministic primality test by trial division for small `c`.
+fn is_prime_trial_division(c: u64) -> bool {
+    if c < 2 {
+        return false;
+    }
+    let mut i = 2;
+    while i * i <= c {
+        if c.is_multiple_of(i) {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Add `k` to the seed interpreted as big-endian integer of `seed.len()` bytes.
+fn seed_add(seed: &[u8], k: usize) -> Vec<u8> {
+    let modulus = BigUint::from(1u32) << (8 * seed.len());
+    let s = (BigUint::from_bytes_be(seed) + k) % modulus;
+    let bytes = s.to_bytes_be();
+    let mut out = vec![0u8; seed.len() - bytes.len().min(seed.len())];
+    out.extend_from_slice(&bytes[bytes.len().saturating_sub(seed.len())..]);
+    out
+}
+
+/// Generate a provable prime of `length` bits from `input_seed` with the
+/// Shawe-Taylor random prime routine (FIPS 186-5, Appendix A.1.2.1.2 and
+/// FIPS 186-4, Appendix C.6) using the hash function `hash`.
+///
+/// Returns `(prime, prime_seed, prime_gen_counter)`.
+pub fn provable_prime<H: Fn(&[u8]) -> Vec<u8>>(
+    length: usize,
+    input_seed: &[u8],
+    hash: &H,
+) -> Result<(BigUint, Vec<u8>, usize), PrimeError> {
+    if length < 2 {
+        return Err(PrimeError::InvalidLength);
+    }
+    let one = BigUint::from(1u32);
+    let two = BigUint::from(2u32);
+    let hash_int = |seed: &[u8]| BigUint::from_bytes_be(&hash(seed));
+
+    if length < 33 {
+        let mut prime_seed = input_seed.to_vec();
+        let mut prime_gen_counter = 0;
+        loop {
+            let c = hash_int(&prime_seed) ^ hash_int(&seed_add(&prime_seed, 1));
+            let half = &one << (length - 1);
+            let c = &half + (c % &half);
+            let c: BigUint = ((c >> 1) << 1) + 1u32;
+            prime_gen_counter += 1;
+            prime_seed = seed_add(&prime_seed, 2);
+            if is_prime_trial_division(c.to_u64().unwrap()) {
+                return Ok((c, prime_seed, prime_gen_counter));
+            }
+            if prime_gen_counter > 4 * length {
+                return Err(PrimeError::Failure);
+            }
+        }
+    }
+
+    let (c0, mut prime_seed, mut prime_gen_counter) =
+        provable_prime(length.div_ceil(2) + 1, input_seed, hash)?;
+    let outlen = hash(&[]).len() * 8;
+    let iterations = length.div_ceil(outlen) - 1;
+    let old_counter = prime_gen_counter;
+
+    let hash_sum = |seed: &[u8]| {
+        let mut x = BigUint::zero();
+        for i in 0..=iterations {
+            x += hash_int(&seed_add(seed, i)) << (i * outlen);
+        }
+        x
+    };
+
+    let half = &one << (length - 1);
+    let x = &half + (hash_sum(&prime_seed) % &half);
+    prime_seed = seed_add(&prime_seed, iterations + 1);
+
+    let two_c0 = &c0 << 1;
+    let mut t = (&x + &two_c0 - &one) / &two_c0;
+    loop {
+        if &two * &t * &c0 + &one > (&one << length) {
+            t = (&half + &two_c0 - &one) / &two_c0;
+        }
+        let c = &two_c0 * &t + &one;
+        prime_gen_counter += 1;
+
+        let a = hash_sum(&prime_seed);
+        prime_seed = seed_add(&prime_seed, iterations + 1);
+        let a = &two + (a % (&c - 3u32));
+        let z = a.modpow(&(&t << 1), &c);
+        // z - 1 mod c, z is 0 for some composite c.
+        if ((&z + &c - &one) % &c).gcd(&c) == one && z.modpow(&c0, &c) == one {
+            return Ok((c, prime_seed, prime_gen_counter));
+        }
+        if prime_gen_counter >= 4 * length + old_counter {
+            return Err(PrimeError::Failure);
+        }
+        t += 1u32;
+    }
+}
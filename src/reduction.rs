@@ -0,0 +1,421 @@
+//!
+//! # Modular reduction
+//!
+//! This module implements Montgomery and Barrett reduction for fixed odd
+//! moduli q < 2^31 such as the ones used in Kyber (q = 3329),
+//! Dilithium (q = 8380417), and Falcon (q = 12289).
+//!
+//! The contexts `MontgomeryCtx` and `BarrettCtx` work directly on `u32`.
+//! The `montgomery_integer!` and `barrett_integer!` macros define element types
+//! of ℤq on top of them that implement `Integer` and can therefore be used
+//! as coefficients in `poly!` and `poly_mul`.
+//!
+//! ```
+//! use hacspec::prelude::*;
+//!
+//! montgomery_integer!(KyberMont, 3329);
+//! poly!(RqKyber, KyberMont, 256, KyberMont::modulus(),
+//!       &[(0, KyberMont::from_literal(1)), (256, KyberMont::from_literal(1))]);
+//! ```
+//!
+
+use crate::prelude::*;
+use rand::distributions::uniform::{SampleBorrow, UniformInt, UniformSampler};
+use std::marker::PhantomData;
+
+/// Subtract `q` from `a < 2q` if `a ≥ q` without branching on `a`.
+#[inline]
+pub fn csubq(a: u64, q: u64) -> u64 {
+    let (t, borrow) = a.overflowing_sub(q);
+    // All ones if a < q.
+    let mask = 0u64.wrapping_sub(borrow as u64);
+    (a & mask) | (t & !mask)
+}
+
+/// Montgomery arithmetic modulo `q` with R = 2^32.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MontgomeryCtx {
+    pub q: u32,
+    /// -q^-1 mod R
+    q_neg_inv: u32,
+    /// R^2 mod q
+    r2: u32,
+}
+
+impl MontgomeryCtx {
+    /// Create a new Montgomery context for the odd modulus `q < 2^31`.
+    ///
+    /// **Panics** if `q` is even or too large, at compile time for the
+    /// constant of `montgomery_integer!`.
+    pub const fn new(q: u32) -> Self {
+        assert!(q % 2 == 1 && q < 1 << 31, "the modulus must be odd and less than 2^31");
+        // Newton iteration doubles the number of correct bits in each step.
+        // q * q = 1 mod 8 such that we start with 3 correct bits.
+        let mut inv = q;
+        let mut i = 0;
+        while i < 4 {
+            inv = inv.wrapping_mul(2u32.wrapping_sub(q.wrapping_mul(inv)));
+            i += 1;
+        }
+        Self {
+            q,
+            q_neg_inv: inv.wrapping_neg(),
+            r2: ((1u128 << 64) % (q as u128)) as u32,
+        }
+    }
+
+    /// Montgomery reduction (REDC) computing `a * R^-1 mod q` for `a < q * R`.
+    #[inline]
+    pub fn reduce(&self, a: u64) -> u32 {
+        let m = (a as u32).wrapping_mul(self.q_neg_inv);
+        let t = (a + (m as u64) * (self.q as u64)) >> 32;
+        csubq(t, self.q as u64) as u32
+    }
+
+    /// Convert `a < q` into Montgomery form `a * R mod q`.
+    #[inline]
+    pub fn to_mont(&self, a: u32) -> u32 {
+        self.reduce((a as u64) * (self.r2 as u64))
+    }
+
+    /// Convert `a` out of Montgomery form.
+    #[inline]
+    pub fn from_mont(&self, a: u32) -> u32 {
+        self.reduce(a as u64)
+    }
+
+    /// Multiply two elements in Montgomery form.
+    #[inline]
+    pub fn mul(&self, a: u32, b: u32) -> u32 {
+        self.reduce((a as u64) * (b as u64))
+    }
+}
+
+/// Barrett arithmetic modulo `q`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BarrettCtx {
+    pub q: u32,
+    /// floor(2^k / q)
+    m: u128,
+    k: u32,
+}
+
+impl BarrettCtx {
+    /// Create a new Barrett context for the modulus `0 < q < 2^31`.
+    ///
+    /// **Panics** if `q` is out of range, at compile time for the constant
+    /// of `barrett_integer!`.
+    pub const fn new(q: u32) -> Self {
+        assert!(q > 0 && q < 1 << 31, "the modulus must be positive and less than 2^31");
+        let k = 2 * (32 - q.leading_zeros());
+        Self {
+            q,
+            m: (1u128 << k) / (q as u128),
+            k,
+        }
+    }
+
+    /// Barrett reduction computing `a mod q` for `a < q^2`.
+    #[inline]
+    pub fn reduce(&self, a: u64) -> u32 {
+        let quotient = ((a as u128) * self.m) >> self.k;
+        let r = a - (quotient as u64) * (self.q as u64);
+        // The quotient estimate is off by at most two.
+        let q = self.q as u64;
+        csubq(csubq(r, 2 * q), q) as u32
+    }
+
+    /// Multiply two elements `a, b < q`.
+    #[inline]
+    pub fn mul(&self, a: u32, b: u32) -> u32 {
+        self.reduce((a as u64) * (b as u64))
+    }
+}
+
+/// Common interface of the element types defined with `montgomery_integer!`
+/// and `barrett_integer!`.
+pub trait ModularElement: Copy {
+    /// The modulus q of this type.
+    fn modulus_value() -> u32;
+    /// Get the element for the canonical representative `x < q`.
+    fn from_canonical(x: u32) -> Self;
+    /// Get the canonical representative in [0, q).
+    /// The modulus element itself returns q.
+    fn to_canonical(self) -> u32;
+}
+
+/// Uniform sampler for `ModularElement`s such that they can be used with
+/// `rng.gen_range`.
+pub struct UniformModular<X> {
+    inner: UniformInt<u32>,
+    _x: PhantomData<X>,
+}
+
+impl<X: ModularElement> UniformSampler for UniformModular<X> {
+    type X = X;
+    fn new<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        Self {
+            inner: UniformInt::<u32>::new(low.borrow().to_canonical(), high.borrow().to_canonical()),
+            _x: PhantomData,
+        }
+    }
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        Self {
+            inner: UniformInt::<u32>::new_inclusive(
+                low.borrow().to_canonical(),
+                high.borrow().to_canonical(),
+            ),
+            _x: PhantomData,
+        }
+    }
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+        X::from_canonical(self.inner.sample(rng))
+    }
+}
+
+/// Implementation details shared by `montgomery_integer!` and `barrett_integer!`.
+/// `$name` has to be a tuple struct around a `u32` in [0, q] and implement
+/// `from_canonical`, `to_canonical`, and `mul_elem`.
+/// Addition and subtraction work directly on the `u32` such that it can be
+/// in Montgomery form and don't branch on the values.
+#[macro_export]
+macro_rules! _modular_element_base {
+    ($name:ident, $q:expr) => {
+        impl $name {
+            /// The modulus as element of this type.
+            /// This is the value to pass as `n` to `poly!` and the `Integer`
+            /// functions. Note that `n` is ignored by all functions because the
+            /// modulus is fixed by the type.
+            pub const fn modulus() -> $name {
+                $name($q)
+            }
+            pub fn from_literal(x: u128) -> $name {
+                $name::from_canonical((x % ($q as u128)) as u32)
+            }
+            /// The representation reduced into [0, q), i.e. 0 for the modulus.
+            fn reduced(self) -> u64 {
+                csubq(self.0 as u64, $q)
+            }
+            fn add_elem(self, rhs: $name) -> $name {
+                $name(csubq(self.reduced() + rhs.reduced(), $q) as u32)
+            }
+            fn sub_elem(self, rhs: $name) -> $name {
+                $name(csubq(self.reduced() + $q - rhs.reduced(), $q) as u32)
+            }
+            /// Division in ℤq, i.e. multiplication with the inverse.
+            /// **Note** this requires q to be prime.
+            pub fn div_elem(self, rhs: $name) -> $name {
+                self.mul_elem(<$name as Integer<$name>>::inv(rhs, $name::modulus()))
+            }
+            /// Compute `self^e` with square-and-multiply.
+            pub fn pow(self, e: u32) -> $name {
+                let mut r = $name::from_canonical(1);
+                for i in (0..32).rev() {
+                    r = r.mul_elem(r);
+                    if (e >> i) & 1 == 1 {
+                        r = r.mul_elem(self);
+                    }
+                }
+                r
+            }
+        }
+
+        impl ModularElement for $name {
+            fn modulus_value() -> u32 {
+                $q
+            }
+            fn from_canonical(x: u32) -> $name {
+                $name::from_canonical(x)
+            }
+            fn to_canonical(self) -> u32 {
+                $name::to_canonical(self)
+            }
+        }
+
+        impl Integer<$name> for $name {
+            #[inline]
+            fn from_literal(x: u128) -> $name {
+                $name::from_literal(x)
+            }
+            #[inline]
+            fn from_signed_literal(x: i128) -> $name {
+                let r = x % ($q as i128);
+                $name::from_canonical(if r < 0 { r + $q as i128 } else { r } as u32)
+            }
+            /// Inversion with Fermat's little theorem.
+            /// **Note** this requires q to be prime.
+            #[inline]
+            fn inv(x: $name, _n: $name) -> $name {
+                x.pow($q - 2)
+            }
+            #[inline]
+            fn max() -> $name {
+                $name::from_canonical($q - 1)
+            }
+            #[inline]
+            fn sub_lift(self, rhs: $name, _n: $name) -> $name {
+                self.sub_elem(rhs)
+            }
+            #[inline]
+            fn sub_mod(self, rhs: $name, _n: $name) -> $name {
+                self.sub_elem(rhs)
+            }
+            #[inline]
+            fn add_mod(self, rhs: $name, _n: $name) -> $name {
+                self.add_elem(rhs)
+            }
+            #[inline]
+            fn mul_mod(self, rhs: $name, _n: $name) -> $name {
+                self.mul_elem(rhs)
+            }
+            #[inline]
+            fn rem(self, _n: $name) -> $name {
+                self
+            }
+            #[inline]
+            fn abs(self) -> $name {
+                self
+            }
+            /// The exponent is interpreted as its canonical representative.
+            fn pow_mod(x: $name, e: $name, _n: $name) -> $name {
+                x.pow(e.to_canonical())
+            }
+            /// `pow` always runs over all 32 exponent bits.
+            fn pow_mod_ct(x: $name, e: $name, _n: $name) -> $name {
+                x.pow(e.to_canonical())
+            }
+            fn legendre(a: $name, _p: $name) -> i8 {
+                u128::legendre(a.to_canonical() as u128, $q)
+            }
+            fn jacobi(a: $name, _n: $name) -> i8 {
+                u128::jacobi(a.to_canonical() as u128, $q)
+            }
+            fn sqrt_mod(a: $name, _p: $name) -> Option<$name> {
+                u128::sqrt_mod(a.to_canonical() as u128, $q)
+                    .map(|r| $name::from_canonical(r as u32))
+            }
+        }
+
+        impl rand::distributions::uniform::SampleUniform for $name {
+            type Sampler = UniformModular<$name>;
+        }
+
+        impl Add for $name {
+            type Output = $name;
+            fn add(self, rhs: $name) -> $name {
+                self.add_elem(rhs)
+            }
+        }
+        impl Sub for $name {
+            type Output = $name;
+            fn sub(self, rhs: $name) -> $name {
+                self.sub_elem(rhs)
+            }
+        }
+        impl Mul for $name {
+            type Output = $name;
+            fn mul(self, rhs: $name) -> $name {
+                self.mul_elem(rhs)
+            }
+        }
+        /// Integer division of the canonical representatives as it is used by
+        /// the generic `Integer` code, e.g. in `gcd`. Use `div_elem` for the
+        /// division in ℤq.
+        impl Div for $name {
+            type Output = $name;
+            fn div(self, rhs: $name) -> $name {
+                $name::from_canonical(self.to_canonical() / rhs.to_canonical())
+            }
+        }
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+        impl PartialOrd for $name {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                self.to_canonical().partial_cmp(&other.to_canonical())
+            }
+        }
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.to_canonical().fmt(f)
+            }
+        }
+    };
+}
+
+/// Define an element type of ℤq that is stored in Montgomery form.
+#[macro_export]
+macro_rules! montgomery_integer {
+    ($name:ident, $q:expr) => {
+        #[derive(Clone, Copy, Default)]
+        pub struct $name(u32);
+
+        impl $name {
+            pub const CTX: MontgomeryCtx = MontgomeryCtx::new($q);
+
+            /// Get the element for the canonical representative `x < q`.
+            pub fn from_canonical(x: u32) -> $name {
+                $name(Self::CTX.to_mont(x))
+            }
+            /// Get the canonical representative in [0, q).
+            pub fn to_canonical(self) -> u32 {
+                // The modulus element is the only one that isn't reduced.
+                if self.0 >= $q {
+                    return self.0;
+                }
+                Self::CTX.from_mont(self.0)
+            }
+            /// Get the Montgomery representation of this element.
+            pub fn to_mont(self) -> u32 {
+                self.0
+            }
+            /// Get an element from its Montgomery representation `x < q`.
+            pub fn from_mont(x: u32) -> $name {
+                $name(x)
+            }
+            fn mul_elem(self, rhs: $name) -> $name {
+                $name(Self::CTX.mul(self.0, rhs.0))
+            }
+        }
+
+        _modular_element_base!($name, $q);
+    };
+}
+
+/// Define an element type of ℤq that is stored in canonical form and
+/// reduced with Barrett reduction.
+#[macro_export]
+macro_rules! barrett_integer {
+    ($name:ident, $q:expr) => {
+        #[derive(Clone, Copy, Default)]
+        pub struct $name(u32);
+
+        impl $name {
+            pub const CTX: BarrettCtx = BarrettCtx::new($q);
+
+            /// Get the element for the canonical representative `x < q`.
+            pub fn from_canonical(x: u32) -> $name {
+                $name(x)
+            }
+            /// Get the canonical representative in [0, q).
+            pub fn to_canonical(self) -> u32 {
+                self.0
+            }
+            fn mul_elem(self, rhs: $name) -> $name {
+                $name(Self::CTX.mul(self.0, rhs.0))
+            }
+        }
+
+        _modular_element_base!($name, $q);
+    };
+}
@@ -0,0 +1,303 @@
+//!
+//! # Coefficient samplers
+//!
+//! Deterministic samplers for polynomial coefficients as they are specified
+//! in lattice-based schemes. Instead of `thread_rng` they consume bytes from
+//! a `ByteSource`, either a fixed `Seq<U8>` (`SeqReader`) or the output of an
+//! XOF (`XofReader`).
+//!
+//! * `sample_uniform` draws uniformly from ℤq by rejection, e.g. `SampleNTT`
+//!   in ML-KEM or `RejNTTPoly` in ML-DSA.
+//! * `sample_cbd` draws from the centered binomial distribution CBD_η, e.g.
+//!   `SamplePolyCBD` in ML-KEM.
+//! * `sample_ternary` draws a vector with exactly `weight` coefficients ±1,
+//!   e.g. `SampleInBall` in ML-DSA.
+//! * `sample_gaussian` draws from a discrete Gaussian with a cumulative
+//!   distribution table.
+//!
+//! All samplers return `Seq<T>` coefficient vectors with coefficients in
+//! [0, q) or, for q = 0, signed coefficients in ℤ. Polynomials defined with
+//! `poly!` can be built from them with `From<Seq<T>>`.
+//!
+//! ```
+//! use hacspec::prelude::*;
+//!
+//! let bytes = ByteSeq::from("2a".repeat(128).as_str());
+//! let e: Seq<u128> = sample_cbd(&mut SeqReader::new(&bytes), 256, 2, 3329).unwrap();
+//! assert!(e.iter().all(|&c| c <= 2 || c >= 3327));
+//! ```
+//!
+//! **Note** that the samplers declassify the bytes they read.
+//!
+
+use crate::prelude::*;
+
+/// Errors returned by the samplers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SamplingError {
+    /// The byte source has no more bytes.
+    InsufficientBytes,
+    /// The parameters aren't supported by the sampler.
+    InvalidParameters,
+}
+
+/// A stream of (pseudo-)random bytes.
+pub trait ByteSource {
+    /// Read the next `len` bytes.
+    fn read_bytes(&mut self, len: usize) -> Result<Vec<u8>, SamplingError>;
+}
+
+/// Reads the bytes of a `Seq<U8>` front to back.
+#[derive(Clone, Debug)]
+pub struct SeqReader {
+    bytes: Vec<u8>,
+    pos: usize,
+}
+
+impl SeqReader {
+    pub fn new(bytes: &Seq<U8>) -> Self {
+        Self {
+            bytes: bytes.iter().map(|&b| U8::declassify(b)).collect(),
+            pos: 0,
+        }
+    }
+
+    /// The number of bytes that haven't been read yet.
+    pub fn remaining(&self) -> usize {
+        self.bytes.len() - self.pos
+    }
+}
+
+impl ByteSource for SeqReader {
+    fn read_bytes(&mut self, len: usize) -> Result<Vec<u8>, SamplingError> {
+        if len > self.remaining() {
+            return Err(SamplingError::InsufficientBytes);
+        }
+        self.pos += len;
+        Ok(self.bytes[self.pos - len..self.pos].to_vec())
+    }
+}
+
+/// Squeezes an XOF such as SHAKE128 absorbing `input`.
+/// The XOF is given as `xof(input, len)` returning `len` bytes like in
+/// `Expander::Xof`. Its output is recomputed in growing blocks when the
+/// reader runs out of bytes.
+#[derive(Clone, Debug)]
+pub struct XofReader {
+    xof: fn(&[u8], usize) -> Vec<u8>,
+    input: Vec<u8>,
+    buffer: Vec<u8>,
+    pos: usize,
+}
+
+impl XofReader {
+    pub fn new(xof: fn(&[u8], usize) -> Vec<u8>, input: &Seq<U8>) -> Self {
+        Self {
+            xof,
+            input: input.iter().map(|&b| U8::declassify(b)).collect(),
+            buffer: Vec::new(),
+            pos: 0,
+        }
+    }
+}
+
+impl ByteSource for XofReader {
+    fn read_bytes(&mut self, len: usize) -> Result<Vec<u8>, SamplingError> {
+        if self.pos + len > self.buffer.len() {
+            let l = std::cmp::max(std::cmp::max(2 * self.buffer.len(), 168), self.pos + len);
+            self.buffer = (self.xof)(&self.input, l);
+        }
+        self.pos += len;
+        Ok(self.buffer[self.pos - len..self.pos].to_vec())
+    }
+}
+
+/// Reads little-endian bit strings of up to 64 bits from a `ByteSource`.
+struct BitReader<'a, S: ByteSource> {
+    source: &'a mut S,
+    acc: u128,
+    bits: usize,
+}
+
+impl<'a, S: ByteSource> BitReader<'a, S> {
+    fn new(source: &'a mut S) -> Self {
+        Self {
+            source,
+            acc: 0,
+            bits: 0,
+        }
+    }
+
+    fn read(&mut self, bits: usize) -> Result<u128, SamplingError> {
+        debug_assert!(bits <= 64);
+        while self.bits < bits {
+            let b = self.source.read_bytes(1)?[0];
+            self.acc |= (b as u128) << self.bits;
+            self.bits += 8;
+        }
+        let r = self.acc & ((1u128 << bits) - 1);
+        self.acc >>= bits;
+        self.bits -= bits;
+        Ok(r)
+    }
+}
+
+/// The coefficient `v` in [0, q) or in ℤ for q = 0.
+fn coefficient<T: TRestrictions<T>>(v: i128, q: u128) -> T {
+    if q == 0 {
+        T::from_signed_literal(v)
+    } else {
+        T::from_literal(v.rem_euclid(q as i128) as u128)
+    }
+}
+
+/// Sample `n` coefficients uniformly from ℤq by rejection.
+/// Every candidate is a little-endian `bits`-bit string and rejected if it
+/// isn't smaller than q, e.g. `bits = 12` for ML-KEM `SampleNTT`.
+///
+/// Returns `InvalidParameters` if q = 0, q > 2^bits or bits > 64.
+pub fn sample_uniform<T: TRestrictions<T>, S: ByteSource>(
+    source: &mut S,
+    n: usize,
+    q: u128,
+    bits: usize,
+) -> Result<Seq<T>, SamplingError> {
+    sample_uniform_masked(source, n, q, bits, bits)
+}
+
+/// Sample `n` coefficients uniformly from ℤq by rejection.
+/// Every candidate is read as a little-endian `chunk_bits`-bit string of
+/// which only the lowest `bits` bits are kept, e.g. ML-DSA `RejNTTPoly`
+/// reads 24 bits and keeps 23.
+///
+/// Returns `InvalidParameters` if q = 0, q > 2^bits or bits > chunk_bits > 64.
+pub fn sample_uniform_masked<T: TRestrictions<T>, S: ByteSource>(
+    source: &mut S,
+    n: usize,
+    q: u128,
+    chunk_bits: usize,
+    bits: usize,
+) -> Result<Seq<T>, SamplingError> {
+    if q == 0 || chunk_bits > 64 || bits > chunk_bits || q > 1u128 << bits {
+        return Err(SamplingError::InvalidParameters);
+    }
+    let mask = (1u128 << bits) - 1;
+    let mut reader = BitReader::new(source);
+    let mut out = Vec::with_capacity(n);
+    while out.len() < n {
+        let c = reader.read(chunk_bits)? & mask;
+        if c < q {
+            out.push(T::from_literal(c));
+        }
+    }
+    Ok(out.into())
+}
+
+/// Sample `n` coefficients from the centered binomial distribution CBD_η
+/// in ℤq (or ℤ for q = 0).
+/// Every coefficient is a - b where a and b are the sums of the next two
+/// η bits, reading 2η·n bits in total like ML-KEM `SamplePolyCBD`.
+///
+/// Returns `InvalidParameters` if η is 0 or larger than 32.
+pub fn sample_cbd<T: TRestrictions<T>, S: ByteSource>(
+    source: &mut S,
+    n: usize,
+    eta: usize,
+    q: u128,
+) -> Result<Seq<T>, SamplingError> {
+    if eta == 0 || eta > 32 {
+        return Err(SamplingError::InvalidParameters);
+    }
+    let mut reader = BitReader::new(source);
+    let mut out = Vec::with_capacity(n);
+    for _ in 0..n {
+        let a = reader.read(eta)?.count_ones() as i128;
+        let b = reader.read(eta)?.count_ones() as i128;
+        out.push(coefficient(a - b, q));
+    }
+    Ok(out.into())
+}
+
+/// Sample a vector of length `n` with exactly `weight` coefficients ±1 and
+/// all others 0 in ℤq (or ℤ for q = 0) like ML-DSA `SampleInBall`.
+///
+/// The first 8 bytes are the signs, followed by a Fisher-Yates shuffle with
+/// positions sampled by rejection from the smallest number of whole bytes
+/// that can hold n - 1.
+///
+/// Returns `InvalidParameters` if `weight` is larger than n or 64.
+pub fn sample_ternary<T: TRestrictions<T>, S: ByteSource>(
+    source: &mut S,
+    n: usize,
+    weight: usize,
+    q: u128,
+) -> Result<Seq<T>, SamplingError> {
+    if weight > n || weight > 64 {
+        return Err(SamplingError::InvalidParameters);
+    }
+    let mut reader = BitReader::new(source);
+    let signs = reader.read(64)?;
+    let index_bits = (usize::BITS - n.saturating_sub(1).leading_zeros()).div_ceil(8) as usize * 8;
+    let mut c = vec![0i128; n];
+    for (k, i) in (n - weight..n).enumerate() {
+        let j = loop {
+            let j = reader.read(index_bits)? as usize;
+            if j <= i {
+                break j;
+            }
+        };
+        c[i] = c[j];
+        c[j] = 1 - 2 * ((signs >> k) & 1) as i128;
+    }
+    Ok(c.iter().map(|&v| coefficient(v, q)).collect::<Vec<T>>().into())
+}
+
+/// Sample `n` coefficients from the discrete Gaussian with standard
+/// deviation σ, centered at 0 and cut off at ±⌈12σ⌉, in ℤq (or ℤ for q = 0).
+///
+/// Every coefficient reads 64 bits and is looked up in a cumulative
+/// distribution table by scanning the whole table.
+///
+/// **Note** the table is computed with `f64` and hence only has a precision
+/// of about 53 bits.
+///
+/// Returns `InvalidParameters` if σ isn't positive or too large.
+pub fn sample_gaussian<T: TRestrictions<T>, S: ByteSource>(
+    source: &mut S,
+    n: usize,
+    sigma: f64,
+    q: u128,
+) -> Result<Seq<T>, SamplingError> {
+    if !(sigma > 0.0 && sigma <= 1e6) {
+        return Err(SamplingError::InvalidParameters);
+    }
+    let cdt = gaussian_cdt(sigma);
+    let tail = (cdt.len() / 2) as i128;
+    let mut reader = BitReader::new(source);
+    let mut out = Vec::with_capacity(n);
+    for _ in 0..n {
+        let u = reader.read(64)? as u64;
+        let index = cdt.iter().map(|&c| (u >= c) as i128).sum::<i128>();
+        out.push(coefficient(index - tail, q));
+    }
+    Ok(out.into())
+}
+
+/// The cumulative distribution table of the discrete Gaussian on
+/// [-t, t] with t = ⌈12σ⌉, scaled to 2^64 and without the last entry.
+fn gaussian_cdt(sigma: f64) -> Vec<u64> {
+    let tail = (12.0 * sigma).ceil() as i64;
+    let weights: Vec<f64> = (-tail..=tail)
+        .map(|k| (-((k * k) as f64) / (2.0 * sigma * sigma)).exp())
+        .collect();
+    let total: f64 = weights.iter().sum();
+    let mut cumulative = 0.0;
+    weights[..weights.len() - 1]
+        .iter()
+        .map(|w| {
+            cumulative += w;
+            // The cast saturates at u64::MAX.
+            (cumulative / total * 18446744073709551616.0) as u64
+        })
+        .collect()
+}
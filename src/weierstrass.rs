@@ -0,0 +1,432 @@
+//!
+//! # Short Weierstrass curves
+//!
+//! Arithmetic on curves y² = x³ + ax + b over a field implementing
+//! `FieldElement`.
+//! A curve is defined by implementing `WeierstrassCurve`, which provides
+//! point arithmetic in affine, projective, and Jacobian coordinates,
+//! scalar multiplication, and the SEC1 point encoding.
+//!
+//! ```
+//! use hacspec::prelude::*;
+//!
+//! unsigned_integer!(P256Canvas, 256);
+//! field_integer!(
+//!     P256FieldElement,
+//!     P256Canvas,
+//!     P256Canvas::from_bytes_be(&hex_string_to_bytes(
+//!         "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff"
+//!     ))
+//! );
+//! field_integer!(
+//!     P256Scalar,
+//!     P256Canvas,
+//!     P256Canvas::from_bytes_be(&hex_string_to_bytes(
+//!         "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551"
+//!     ))
+//! );
+//!
+//! struct P256;
+//! impl WeierstrassCurve for P256 {
+//!     type Field = P256FieldElement;
+//!     type Scalar = P256Scalar;
+//!     fn a() -> P256FieldElement {
+//!         P256FieldElement::from_literal(0) - P256FieldElement::from_literal(3)
+//!     }
+//!     fn b() -> P256FieldElement {
+//!         P256FieldElement::from_hex("5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b")
+//!     }
+//!     fn generator() -> Affine<P256FieldElement> {
+//!         Affine::Point(
+//!             P256FieldElement::from_hex("6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296"),
+//!             P256FieldElement::from_hex("4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5"),
+//!         )
+//!     }
+//! }
+//!
+//! let p = P256::mul_generator(P256Scalar::from_literal(2));
+//! assert_eq!(p, P256::double(P256::generator()));
+//! assert!(P256::is_on_curve(p));
+//! ```
+//!
+
+use crate::prelude::*;
+
+/// Errors returned when decoding points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EcError {
+    InvalidEncoding,
+    NotOnCurve,
+}
+
+/// A point in affine coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Affine<F: FieldElement> {
+    Infinity,
+    Point(F, F),
+}
+
+/// A point (X : Y : Z) in homogeneous projective coordinates representing
+/// (X/Z, Y/Z). The point at infinity is (0 : 1 : 0).
+#[derive(Debug, Clone, Copy)]
+pub struct Projective<F: FieldElement> {
+    pub x: F,
+    pub y: F,
+    pub z: F,
+}
+
+/// A point (X : Y : Z) in Jacobian coordinates representing (X/Z², Y/Z³).
+/// The point at infinity has Z = 0.
+#[derive(Debug, Clone, Copy)]
+pub struct Jacobian<F: FieldElement> {
+    pub x: F,
+    pub y: F,
+    pub z: F,
+}
+
+pub trait WeierstrassCurve {
+    type Field: FieldElement;
+    type Scalar: PrimeField;
+
+    fn a() -> Self::Field;
+    fn b() -> Self::Field;
+    fn generator() -> Affine<Self::Field>;
+
+    fn is_on_curve(p: Affine<Self::Field>) -> bool {
+        match p {
+            Affine::Infinity => true,
+            Affine::Point(x, y) => y.square() == x.square() * x + Self::a() * x + Self::b(),
+        }
+    }
+
+    fn neg(p: Affine<Self::Field>) -> Affine<Self::Field> {
+        match p {
+            Affine::Infinity => Affine::Infinity,
+            Affine::Point(x, y) => Affine::Point(x, Self::Field::zero() - y),
+        }
+    }
+
+    /// Add two points in affine coordinates.
+    fn add(p: Affine<Self::Field>, q: Affine<Self::Field>) -> Affine<Self::Field> {
+        let (x1, y1, x2, y2) = match (p, q) {
+            (Affine::Infinity, _) => return q,
+            (_, Affine::Infinity) => return p,
+            (Affine::Point(x1, y1), Affine::Point(x2, y2)) => (x1, y1, x2, y2),
+        };
+        if x1 == x2 {
+            if y1 == y2 {
+                return Self::double(p);
+            }
+            return Affine::Infinity;
+        }
+        let lambda = (y2 - y1) * (x2 - x1).inv();
+        let x3 = lambda.square() - x1 - x2;
+        let y3 = lambda * (x1 - x3) - y1;
+        Affine::Point(x3, y3)
+    }
+
+    /// Double a point in affine coordinates.
+    fn double(p: Affine<Self::Field>) -> Affine<Self::Field> {
+        let (x, y) = match p {
+            Affine::Infinity => return Affine::Infinity,
+            Affine::Point(x, y) => (x, y),
+        };
+        if y == Self::Field::zero() {
+            return Affine::Infinity;
+        }
+        let three = Self::Field::from_literal(3);
+        let lambda = (three * x.square() + Self::a()) * (y + y).inv();
+        let x3 = lambda.square() - x - x;
+        let y3 = lambda * (x - x3) - y;
+        Affine::Point(x3, y3)
+    }
+
+    fn to_projective(p: Affine<Self::Field>) -> Projective<Self::Field> {
+        match p {
+            Affine::Infinity => Projective {
+                x: Self::Field::zero(),
+                y: Self::Field::one(),
+                z: Self::Field::zero(),
+            },
+            Affine::Point(x, y) => Projective {
+                x,
+                y,
+                z: Self::Field::one(),
+            },
+        }
+    }
+
+    fn from_projective(p: Projective<Self::Field>) -> Affine<Self::Field> {
+        if p.z == Self::Field::zero() {
+            return Affine::Infinity;
+        }
+        let z_inv = p.z.inv();
+        Affine::Point(p.x * z_inv, p.y * z_inv)
+    }
+
+    /// Complete addition in projective coordinates
+    /// (Renes, Costello, Batina 2016, Algorithm 1).
+    /// This works for all inputs, including doubling and the point at infinity.
+    fn projective_add(p: Projective<Self::Field>, q: Projective<Self::Field>) -> Projective<Self::Field> {
+        let a = Self::a();
+        let b3 = Self::Field::from_literal(3) * Self::b();
+        let (x1, y1, z1) = (p.x, p.y, p.z);
+        let (x2, y2, z2) = (q.x, q.y, q.z);
+
+        let mut t0 = x1 * x2;
+        let mut t1 = y1 * y2;
+        let mut t2 = z1 * z2;
+        let mut t3 = x1 + y1;
+        let mut t4 = x2 + y2;
+        t3 = t3 * t4;
+        t4 = t0 + t1;
+        t3 = t3 - t4;
+        t4 = x1 + z1;
+        let mut t5 = x2 + z2;
+        t4 = t4 * t5;
+        t5 = t0 + t2;
+        t4 = t4 - t5;
+        t5 = y1 + z1;
+        let mut x3 = y2 + z2;
+        t5 = t5 * x3;
+        x3 = t1 + t2;
+        t5 = t5 - x3;
+        let mut z3 = a * t4;
+        x3 = b3 * t2;
+        z3 = x3 + z3;
+        x3 = t1 - z3;
+        z3 = t1 + z3;
+        let mut y3 = x3 * z3;
+        t1 = t0 + t0;
+        t1 = t1 + t0;
+        t2 = a * t2;
+        t4 = b3 * t4;
+        t1 = t1 + t2;
+        t2 = t0 - t2;
+        t2 = a * t2;
+        t4 = t4 + t2;
+        t0 = t1 * t4;
+        y3 = y3 + t0;
+        t0 = t5 * t4;
+        x3 = t3 * x3;
+        x3 = x3 - t0;
+        t0 = t3 * t1;
+        z3 = t5 * z3;
+        z3 = z3 + t0;
+
+        Projective {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    fn projective_double(p: Projective<Self::Field>) -> Projective<Self::Field> {
+        Self::projective_add(p, p)
+    }
+
+    fn to_jacobian(p: Affine<Self::Field>) -> Jacobian<Self::Field> {
+        match p {
+            Affine::Infinity => Jacobian {
+                x: Self::Field::one(),
+                y: Self::Field::one(),
+                z: Self::Field::zero(),
+            },
+            Affine::Point(x, y) => Jacobian {
+                x,
+                y,
+                z: Self::Field::one(),
+            },
+        }
+    }
+
+    fn from_jacobian(p: Jacobian<Self::Field>) -> Affine<Self::Field> {
+        if p.z == Self::Field::zero() {
+            return Affine::Infinity;
+        }
+        let z_inv = p.z.inv();
+        let z_inv2 = z_inv.square();
+        Affine::Point(p.x * z_inv2, p.y * z_inv2 * z_inv)
+    }
+
+    /// Doubling in Jacobian coordinates (dbl-2007-bl).
+    fn jacobian_double(p: Jacobian<Self::Field>) -> Jacobian<Self::Field> {
+        let two = Self::Field::from_literal(2);
+        let three = Self::Field::from_literal(3);
+        let eight = Self::Field::from_literal(8);
+        let xx = p.x.square();
+        let yy = p.y.square();
+        let yyyy = yy.square();
+        let zz = p.z.square();
+        let s = two * ((p.x + yy).square() - xx - yyyy);
+        let m = three * xx + Self::a() * zz.square();
+        let x3 = m.square() - two * s;
+        let y3 = m * (s - x3) - eight * yyyy;
+        let z3 = (p.y + p.z).square() - yy - zz;
+        Jacobian {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    /// Addition in Jacobian coordinates (add-2007-bl).
+    /// The exceptional cases are handled by branching.
+    fn jacobian_add(p: Jacobian<Self::Field>, q: Jacobian<Self::Field>) -> Jacobian<Self::Field> {
+        let zero = Self::Field::zero();
+        if p.z == zero {
+            return q;
+        }
+        if q.z == zero {
+            return p;
+        }
+        let two = Self::Field::from_literal(2);
+        let z1z1 = p.z.square();
+        let z2z2 = q.z.square();
+        let u1 = p.x * z2z2;
+        let u2 = q.x * z1z1;
+        let s1 = p.y * q.z * z2z2;
+        let s2 = q.y * p.z * z1z1;
+        let h = u2 - u1;
+        let r = two * (s2 - s1);
+        if h == zero {
+            if r == zero {
+                return Self::jacobian_double(p);
+            }
+            return Self::to_jacobian(Affine::Infinity);
+        }
+        let i = (two * h).square();
+        let j = h * i;
+        let v = u1 * i;
+        let x3 = r.square() - j - two * v;
+        let y3 = r * (v - x3) - two * s1 * j;
+        let z3 = ((p.z + q.z).square() - z1z1 - z2z2) * h;
+        Jacobian {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    /// Scalar multiplication with left-to-right double-and-add in Jacobian
+    /// coordinates.
+    /// **Note** that this is not constant-time. Use `scalar_mul_ladder` for
+    /// secret scalars.
+    fn scalar_mul(k: Self::Scalar, p: Affine<Self::Field>) -> Affine<Self::Field> {
+        let p = Self::to_jacobian(p);
+        let mut r = Self::to_jacobian(Affine::Infinity);
+        for i in (0..Self::Scalar::modulus_bits()).rev() {
+            r = Self::jacobian_double(r);
+            if k.bit(i) {
+                r = Self::jacobian_add(r, p);
+            }
+        }
+        Self::from_jacobian(r)
+    }
+
+    /// Scalar multiplication with a Montgomery ladder using the complete
+    /// projective addition formulas.
+    /// The sequence of field operations is independent of the scalar.
+    fn scalar_mul_ladder(k: Self::Scalar, p: Affine<Self::Field>) -> Affine<Self::Field> {
+        let mut r0 = Self::to_projective(Affine::Infinity);
+        let mut r1 = Self::to_projective(p);
+        for i in (0..Self::Scalar::modulus_bits()).rev() {
+            let bit = k.bit(i);
+            let (s0, s1) = cswap(r0, r1, bit);
+            let s1 = Self::projective_add(s0, s1);
+            let s0 = Self::projective_double(s0);
+            let (s0, s1) = cswap(s0, s1, bit);
+            r0 = s0;
+            r1 = s1;
+        }
+        Self::from_projective(r0)
+    }
+
+    /// Compute `k * G` for the generator `G`.
+    fn mul_generator(k: Self::Scalar) -> Affine<Self::Field> {
+        Self::scalar_mul_ladder(k, Self::generator())
+    }
+
+    /// SEC1 encoding of `p`, i.e. `0x04 || x || y` or, if `compressed`,
+    /// `0x02 || x` for even and `0x03 || x` for odd `y`.
+    /// The point at infinity is encoded as the single byte `0x00`.
+    fn encode_sec1(p: Affine<Self::Field>, compressed: bool) -> Seq<U8>
+    where
+        Self::Field: PrimeField,
+    {
+        let (x, y) = match p {
+            Affine::Infinity => return Seq::from(vec![U8::classify(0u8)]),
+            Affine::Point(x, y) => (x, y),
+        };
+        let mut out = Vec::new();
+        if compressed {
+            out.push(U8::classify(0x02u8 | y.sgn0() as u8));
+            out.extend(x.to_byte_seq_be().iter());
+        } else {
+            out.push(U8::classify(0x04u8));
+            out.extend(x.to_byte_seq_be().iter());
+            out.extend(y.to_byte_seq_be().iter());
+        }
+        Seq::from(out)
+    }
+
+    /// Decode a SEC1 encoded point and check that it is on the curve.
+    fn decode_sec1(s: &Seq<U8>) -> Result<Affine<Self::Field>, EcError>
+    where
+        Self::Field: PrimeField,
+    {
+        let l = Self::Field::byte_len();
+        if s.len() == 0 {
+            return Err(EcError::InvalidEncoding);
+        }
+        let tag = U8::declassify(s[0]);
+        if tag == 0x00 && s.len() == 1 {
+            return Ok(Affine::Infinity);
+        }
+        if s.len() < 1 + l {
+            return Err(EcError::InvalidEncoding);
+        }
+        let x = Self::Field::from_canonical_byte_seq_be(s.clone().sub(1, l))
+            .ok_or(EcError::InvalidEncoding)?;
+        let p = match (tag, s.len()) {
+            (0x04, len) if len == 1 + 2 * l => {
+                let y = Self::Field::from_canonical_byte_seq_be(s.clone().sub(1 + l, l))
+                    .ok_or(EcError::InvalidEncoding)?;
+                Affine::Point(x, y)
+            }
+            (0x02, len) | (0x03, len) if len == 1 + l => {
+                let rhs = x.square() * x + Self::a() * x + Self::b();
+                let mut y = rhs.sqrt().ok_or(EcError::NotOnCurve)?;
+                if y.sgn0() != (tag == 0x03) {
+                    y = Self::Field::zero() - y;
+                    if y.sgn0() != (tag == 0x03) {
+                        // y = 0 can't have odd parity.
+                        return Err(EcError::InvalidEncoding);
+                    }
+                }
+                Affine::Point(x, y)
+            }
+            _ => return Err(EcError::InvalidEncoding),
+        };
+        if !Self::is_on_curve(p) {
+            return Err(EcError::NotOnCurve);
+        }
+        Ok(p)
+    }
+}
+
+/// Swap `a` and `b` if `bit` is set without branching on `bit`.
+fn cswap<F: FieldElement>(a: Projective<F>, b: Projective<F>, bit: bool) -> (Projective<F>, Projective<F>) {
+    (
+        Projective {
+            x: F::select(a.x, b.x, bit),
+            y: F::select(a.y, b.y, bit),
+            z: F::select(a.z, b.z, bit),
+        },
+        Projective {
+            x: F::select(b.x, a.x, bit),
+            y: F::select(b.y, a.y, bit),
+            z: F::select(b.z, a.z, bit),
+        },
+    )
+}
@@ -0,0 +1,132 @@
+use hacspec::prelude::*;
+
+// AES: x^8 + x^4 + x^3 + x + 1
+binary_field!(GfAes, 8, [4, 3, 1, 0]);
+// Reed-Solomon (e.g. QR codes): x^8 + x^4 + x^3 + x^2 + 1
+binary_field!(GfRs, 8, [4, 3, 2, 0]);
+// GHASH: x^128 + x^7 + x^2 + x + 1
+binary_field!(GfGhash, 128, [7, 2, 1, 0]);
+// POLYVAL: x^128 + x^127 + x^126 + x^121 + 1
+binary_field!(GfPolyval, 128, [127, 126, 121, 0]);
+// NIST B-233: x^233 + x^74 + 1
+binary_field!(Gf233, 233, [74, 0]);
+
+bytes!(Block, 16);
+
+#[test]
+fn test_clmul() {
+    assert_eq!(clmul64(3, 3), (5, 0));
+    assert_eq!(clmul64(1 << 63, 2), (0, 1));
+    assert_eq!(clmul64(u64::MAX, u64::MAX), (0x5555555555555555, 0x5555555555555555));
+    assert_eq!(clmul_words(&[1 << 63, 1], &[2]), vec![0, 3, 0]);
+    // x^8 = x^4 + x^3 + x + 1
+    assert_eq!(reduce_words(&[0x100], 8, &[4, 3, 1, 0]), vec![0x1b]);
+}
+
+fn aes_sbox(x: u8) -> u8 {
+    let b = GfAes::from_literal(x as u128).inv().to_words()[0] as u8;
+    b ^ b.rotate_left(1) ^ b.rotate_left(2) ^ b.rotate_left(3) ^ b.rotate_left(4) ^ 0x63
+}
+
+#[test]
+fn test_aes_field() {
+    let a = GfAes::from_literal(0x57);
+    assert_eq!(a * GfAes::from_literal(0x83), GfAes::from_literal(0xc1));
+    assert_eq!(a * GfAes::from_literal(0x13), GfAes::from_literal(0xfe));
+    assert_eq!(a + GfAes::from_literal(0x83), GfAes::from_literal(0xd4));
+    assert_eq!(a - GfAes::from_literal(0x83), GfAes::from_literal(0xd4));
+    assert_eq!(GfAes::from_literal(0x53).inv(), GfAes::from_literal(0xca));
+    assert_eq!(GfAes::zero().inv(), GfAes::zero());
+    for x in 1..256 {
+        let x = GfAes::from_literal(x);
+        assert_eq!(x * x.inv(), GfAes::one());
+        assert_eq!(x.square(), x * x);
+        assert_eq!(x.sqrt().square(), x);
+    }
+    assert_eq!(aes_sbox(0x00), 0x63);
+    assert_eq!(aes_sbox(0x01), 0x7c);
+    assert_eq!(aes_sbox(0x53), 0xed);
+    assert_eq!(aes_sbox(0xff), 0x16);
+}
+
+#[test]
+fn test_reed_solomon_field() {
+    // α = x generates the multiplicative group of order 255 = 3 * 5 * 17.
+    let alpha = GfRs::from_literal(2);
+    assert_eq!(alpha.pow(255), GfRs::one());
+    for d in [85, 51, 15] {
+        assert!(alpha.pow(d) != GfRs::one());
+    }
+    assert_eq!(alpha.pow(8), GfRs::from_literal(0x1d));
+    assert_eq!(alpha.pow(254), alpha.inv());
+    assert_eq!(GfRs::from_literal(0x11d), GfRs::zero());
+}
+
+#[test]
+fn test_ghash() {
+    // GCM specification, test case 2
+    let h = GfGhash::from_byte_seq_reflected(Block::from("66e94bd4ef8a2c3b884cfa59ca342b2e"));
+    let c = GfGhash::from_byte_seq_reflected(Block::from("0388dace60b6a392f328c2b971b2fe78"));
+    let lengths = GfGhash::from_byte_seq_reflected(Block::from("00000000000000000000000000000080"));
+    let x = c * h;
+    let x = (x + lengths) * h;
+    let tag: Block = x.to_byte_array_reflected();
+    assert_eq!(tag.to_hex(), "f38cbb1ad69223dcc3457ae5b6b0f885");
+
+    assert_eq!(GfGhash::one().to_byte_seq_reflected().to_hex(), "80000000000000000000000000000000");
+}
+
+#[test]
+fn test_polyval() {
+    // RFC 8452, Appendix A: POLYVAL(H, X_1, X_2)
+    let h = GfPolyval::from_byte_seq_le(Block::from("25629347589242761d31f826ba4b757b"));
+    let x1 = GfPolyval::from_byte_seq_le(Block::from("4f4f95668c83dfb6401762bb2d01a262"));
+    let x2 = GfPolyval::from_byte_seq_le(Block::from("d1a24ddd2721d006bbe45f20d3c9f362"));
+    // dot(a, b) = a * b * x^-128
+    let x_inv_128 = GfPolyval::from_literal(2).inv().pow(128);
+    let dot = |a: GfPolyval, b: GfPolyval| a * b * x_inv_128;
+    let s = dot(dot(x1, h) + x2, h);
+    assert_eq!(s.to_byte_seq_le().to_hex(), "f7a3b47b846119fae5b7866cf5e5b77e");
+}
+
+#[test]
+fn test_multi_word_field() {
+    let a = Gf233::from_words(&[0x0123456789abcdef, 0xfedcba9876543210, 0xdeadbeef, 0x1ffffffffff]);
+    let b = Gf233::from_literal(0x1234567890abcdef1234567890abcdef);
+    assert_eq!(a * a.inv(), Gf233::one());
+    assert_eq!(a * (b + Gf233::one()), a * b + a);
+    assert_eq!(a.square(), a * a);
+    assert_eq!(a.sqrt().square(), a);
+    // a^(2^n) = a
+    let mut t = a;
+    for _ in 0..Gf233::bits() {
+        t = t.square();
+    }
+    assert_eq!(t, a);
+    // x^233 = x^74 + 1
+    let x = Gf233::from_literal(2);
+    assert_eq!(x.pow(233), x.pow(74) + Gf233::one());
+    assert!(!a.coefficient(233));
+}
+
+#[test]
+fn test_byte_orders() {
+    let a = Gf233::from_literal(0x0102);
+    let be = a.to_byte_seq_be();
+    let le = a.to_byte_seq_le();
+    let reflected = a.to_byte_seq_reflected();
+    assert_eq!(Gf233::byte_len(), 30);
+    let zeros = "00".repeat(28);
+    assert_eq!(be.to_hex(), format!("{}0102", zeros));
+    assert_eq!(le.to_hex(), format!("0201{}", zeros));
+    assert_eq!(reflected.to_hex(), format!("4080{}", zeros));
+    assert_eq!(Gf233::from_byte_seq_be(be), a);
+    assert_eq!(Gf233::from_byte_seq_le(le), a);
+    assert_eq!(Gf233::from_byte_seq_reflected(reflected), a);
+
+    let block = Block::from("000102030405060708090a0b0c0d0e0f");
+    let g = GfGhash::from_byte_seq_be(block);
+    assert_eq!(g.to_byte_array_be::<Block>().to_hex(), block.to_hex());
+    assert_eq!(GfGhash::from_byte_seq_le(block).to_byte_array_le::<Block>().to_hex(), block.to_hex());
+    assert_eq!(g.to_words(), [0x08090a0b0c0d0e0f, 0x0001020304050607]);
+}
@@ -0,0 +1,34 @@
+use hacspec::prelude::*;
+
+mod common;
+use common::{sha256, sha512, shake128};
+
+#[test]
+fn test_sha256() {
+    assert_eq!(
+        Seq::<u8>::from(sha256(b"abc")).to_hex(),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+}
+
+#[test]
+fn test_sha512() {
+    assert_eq!(
+        Seq::<u8>::from(sha512(b"abc")).to_hex(),
+        "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+    );
+}
+
+#[test]
+fn test_shake128() {
+    assert_eq!(
+        Seq::<u8>::from(shake128(b"", 32)).to_hex(),
+        "7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26"
+    );
+    // Several absorbed and squeezed blocks
+    let long = shake128(&[0xa3u8; 200], 400);
+    assert_eq!(
+        Seq::<u8>::from(long[390..].to_vec()).to_hex(),
+        "d71ff3e50c537ac1b0f8"
+    );
+}
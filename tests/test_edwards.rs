@@ -0,0 +1,197 @@
+use hacspec::prelude::*;
+
+// RFC 9496, Appendix A.1: multiples of the generator
+const RISTRETTO_MULTIPLES: [&str; 5] = [
+    "0000000000000000000000000000000000000000000000000000000000000000",
+    "e2f2ae0a6abc4e71a884a961c500515f58e30b6aa582dd8db6a65945e08d2d76",
+    "6a493210f7499cd17fecb510ae0cea23a110e8d5b901f8acadd3095c73a3b919",
+    "94741f5d5d52755ece4f23f044ee27d5d1ea1e2bd196b462166b16152a9d0259",
+    "da80862773358b466ffadfe0b3293ab3d9fd53c5ea6c955358f568322daf6a57",
+];
+
+// RFC 9496, Appendix A.2: multiples of the generator
+const DECAF_MULTIPLES: [&str; 4] = [
+    "0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+    "6666666666666666666666666666666666666666666666666666666633333333333333333333333333333333333333333333333333333333",
+    "c898eb4f87f97c564c6fd61fc7e49689314a1f818ec85eeb3bd5514ac816d38778f69ef347a89fca817e66defdedce178c7cc709b2116e75",
+    "a0c09bf2ba7208fda0f4bfe3d0f5b29a543012306d43831b5adc6fe7f8596fa308763db15468323b11cf6e4aeb8c18fe44678f44545a69bc",
+];
+
+#[test]
+fn test_arithmetic() {
+    let b = Ed25519::base_point();
+    assert!(Ed25519::is_on_curve(b));
+    let b2 = Ed25519::double(b);
+    let b3 = Ed25519::add(b2, b);
+    assert!(Ed25519::is_on_curve(b3));
+    assert!(Ed25519::point_eq(Ed25519::add(b, b), b2));
+    assert!(Ed25519::point_eq(Ed25519::add(b, Ed25519::identity()), b));
+    assert!(Ed25519::point_eq(Ed25519::add(b, Ed25519::neg(b)), Ed25519::identity()));
+    assert!(!Ed25519::point_eq(b2, b3));
+
+    let k = Ed25519Scalar::from_hex("0123456789abcdef0123456789abcdef");
+    let kb = Ed25519::scalar_mul(k, b);
+    assert!(Ed25519::point_eq(kb, Ed25519::mul_biguint(&k.to_biguint(), b)));
+    assert!(Ed25519::point_eq(
+        Ed25519::scalar_mul(k + Ed25519Scalar::from_literal(3), b),
+        Ed25519::add(kb, b3)
+    ));
+    // l * B = 0
+    let l_minus_one = Ed25519Scalar::from_literal(0) - Ed25519Scalar::from_literal(1);
+    assert!(Ed25519::point_eq(Ed25519::scalar_mul(l_minus_one, b), Ed25519::neg(b)));
+
+    let b = Ed448::base_point();
+    assert!(Ed448::is_on_curve(b));
+    let k = Ed448Scalar::from_literal(1234567);
+    assert!(Ed448::point_eq(
+        Ed448::scalar_mul(k, b),
+        Ed448::mul_biguint(&k.to_biguint(), b)
+    ));
+}
+
+#[test]
+fn test_cofactor() {
+    let one = Ed25519FieldElement::from_literal(1);
+    let zero = Ed25519FieldElement::from_literal(0);
+    // (sqrt(-1), 0) has order 4 and (0, -1) order 2.
+    let t4 = Ed25519::from_affine(Ed25519::sqrt_m1(), zero);
+    let t2 = Ed25519::from_affine(zero, zero - one);
+    assert!(Ed25519::is_on_curve(t4));
+    assert!(Ed25519::point_eq(Ed25519::double(t4), t2));
+    assert!(Ed25519::is_small_order(t4));
+    assert!(Ed25519::is_small_order(t2));
+    assert!(Ed25519::is_small_order(Ed25519::identity()));
+
+    let b = Ed25519::base_point();
+    assert!(!Ed25519::is_small_order(b));
+    assert!(Ed25519::is_torsion_free(b));
+    assert!(!Ed25519::is_torsion_free(Ed25519::add(b, t4)));
+    assert!(Ed25519::point_eq(
+        Ed25519::mul_by_cofactor(Ed25519::add(b, t4)),
+        Ed25519::mul_by_cofactor(b)
+    ));
+
+    let t4 = Ed448::from_affine(Ed448FieldElement::from_literal(1), Ed448FieldElement::from_literal(0));
+    assert!(Ed448::is_on_curve(t4));
+    assert!(Ed448::is_small_order(t4));
+    assert!(!Ed448::is_small_order(Ed448::base_point()));
+}
+
+#[test]
+fn test_compression() {
+    // RFC 8032 base points
+    let b = Ed25519::base_point();
+    let enc = Ed25519::compress(b);
+    assert_eq!(enc.to_hex(), "5866666666666666666666666666666666666666666666666666666666666666");
+    assert!(Ed25519::point_eq(Ed25519::decompress(&enc).unwrap(), b));
+    let b = Ed448::base_point();
+    let enc = Ed448::compress(b);
+    assert_eq!(
+        enc.to_hex(),
+        "14fa30f25b790898adc8d74e2c13bdfdc4397ce61cffd33ad7c2a0051e9c78874098a36c7373ea4b62c7c9563720768824bcb66e71463f6900"
+    );
+    assert!(Ed448::point_eq(Ed448::decompress(&enc).unwrap(), b));
+
+    // RFC 8032, Section 7.1, TEST 1 public key
+    let pk = ByteSeq::from("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+    let p = Ed25519::decompress(&pk).unwrap();
+    assert!(Ed25519::is_on_curve(p));
+    assert_eq!(Ed25519::compress(p).to_hex(), pk.to_hex());
+    let nb = Ed25519::neg(Ed25519::base_point());
+    assert!(Ed25519::point_eq(Ed25519::decompress(&Ed25519::compress(nb)).unwrap(), nb));
+
+    // y = p
+    assert_eq!(
+        Ed25519::decompress(&ByteSeq::from("edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f")).err(),
+        Some(EcError::InvalidEncoding)
+    );
+    // y = 1, x = 0 with sign bit set
+    assert_eq!(
+        Ed25519::decompress(&ByteSeq::from("0100000000000000000000000000000000000000000000000000000000000080")).err(),
+        Some(EcError::InvalidEncoding)
+    );
+    // No point with y = 2
+    assert_eq!(
+        Ed25519::decompress(&ByteSeq::from("0200000000000000000000000000000000000000000000000000000000000000")).err(),
+        Some(EcError::NotOnCurve)
+    );
+    assert_eq!(Ed25519::decompress(&ByteSeq::new(31)).err(), Some(EcError::InvalidEncoding));
+    // The last byte of an Ed448 point may only hold the sign bit.
+    let mut bad = Ed448::compress(Ed448::base_point());
+    bad[56] = U8::classify(0x01u8);
+    assert_eq!(Ed448::decompress(&bad).err(), Some(EcError::InvalidEncoding));
+    let mut no_point = ByteSeq::new(57);
+    no_point[0] = U8::classify(0x02u8);
+    assert_eq!(Ed448::decompress(&no_point).err(), Some(EcError::NotOnCurve));
+}
+
+#[test]
+fn test_ristretto() {
+    let b = Ed25519::base_point();
+    let mut p = Ed25519::identity();
+    for expected in RISTRETTO_MULTIPLES.iter() {
+        let enc = Ed25519::ristretto_encode(p);
+        assert_eq!(enc.to_hex(), *expected);
+        let q = Ed25519::ristretto_decode(&enc).unwrap();
+        assert!(Ed25519::ristretto_eq(p, q));
+        assert_eq!(Ed25519::ristretto_encode(q).to_hex(), *expected);
+        p = Ed25519::add(p, b);
+    }
+
+    // Adding a 4-torsion point doesn't change the element.
+    let zero = Ed25519FieldElement::from_literal(0);
+    let t4 = Ed25519::from_affine(Ed25519::sqrt_m1(), zero);
+    let b3 = Ed25519::scalar_mul(Ed25519Scalar::from_literal(3), b);
+    let b3_t4 = Ed25519::add(b3, t4);
+    assert!(!Ed25519::point_eq(b3, b3_t4));
+    assert!(Ed25519::ristretto_eq(b3, b3_t4));
+    assert_eq!(Ed25519::ristretto_encode(b3_t4).to_hex(), RISTRETTO_MULTIPLES[3]);
+    assert!(!Ed25519::ristretto_eq(b3, b));
+
+    // Non-canonical
+    assert_eq!(
+        Ed25519::ristretto_decode(&ByteSeq::from("edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f")).err(),
+        Some(EcError::InvalidEncoding)
+    );
+    // Negative
+    assert_eq!(
+        Ed25519::ristretto_decode(&ByteSeq::from("0100000000000000000000000000000000000000000000000000000000000000")).err(),
+        Some(EcError::InvalidEncoding)
+    );
+    // Not a square
+    assert_eq!(
+        Ed25519::ristretto_decode(&ByteSeq::from("0800000000000000000000000000000000000000000000000000000000000000")).err(),
+        Some(EcError::InvalidEncoding)
+    );
+    // Negative t
+    assert_eq!(
+        Ed25519::ristretto_decode(&ByteSeq::from("0200000000000000000000000000000000000000000000000000000000000000")).err(),
+        Some(EcError::InvalidEncoding)
+    );
+}
+
+#[test]
+fn test_decaf() {
+    // The decaf448 generator is twice the edwards448 base point.
+    let g = Ed448::double(Ed448::base_point());
+    let mut p = Ed448::identity();
+    for expected in DECAF_MULTIPLES.iter() {
+        let enc = Ed448::decaf_encode(p);
+        assert_eq!(enc.to_hex(), *expected);
+        let q = Ed448::decaf_decode(&enc).unwrap();
+        assert!(Ed448::decaf_eq(p, q));
+        assert_eq!(Ed448::decaf_encode(q).to_hex(), *expected);
+        p = Ed448::add(p, g);
+    }
+
+    let t2 = Ed448::from_affine(Ed448FieldElement::from_literal(0), Ed448FieldElement::from_literal(0) - Ed448FieldElement::from_literal(1));
+    let g_t2 = Ed448::add(g, t2);
+    assert!(!Ed448::point_eq(g, g_t2));
+    assert!(Ed448::decaf_eq(g, g_t2));
+    assert_eq!(Ed448::decaf_encode(g_t2).to_hex(), DECAF_MULTIPLES[1]);
+
+    let mut negative = ByteSeq::new(56);
+    negative[0] = U8::classify(0x01u8);
+    assert_eq!(Ed448::decaf_decode(&negative).err(), Some(EcError::InvalidEncoding));
+    assert_eq!(Ed448::decaf_decode(&ByteSeq::new(55)).err(), Some(EcError::InvalidEncoding));
+}
@@ -0,0 +1,89 @@
+use hacspec::prelude::*;
+
+// Rq = ℤ3329[x]/(x^256 + 1)
+poly!(Rq, u128, 256, 3329, &[(0, 1), (256, 1)]);
+
+#[test]
+fn test_compress() {
+    let q = 3329u128;
+    assert_eq!(compress(&[1000u128], q, 10).raw(), &[308]);
+    assert_eq!(decompress(&[308u128], q, 10).raw(), &[1001]);
+    assert_eq!(compress(&[1665u128], q, 4).raw(), &[8]);
+    // Compress_1 rounds to 0 or ⌈q/2⌋.
+    assert_eq!(compress(&[832u128, 833, 2496, 2497, 3328], q, 1).raw(), &[0, 1, 1, 0, 0]);
+    assert_eq!(decompress(&[0u128, 1], q, 1).raw(), &[0, 1665]);
+
+    // |x - Decompress_d(Compress_d(x))| mod q ≤ ⌈q / 2^(d+1)⌋
+    let x: Vec<u128> = (0..q).collect();
+    for &d in [1, 4, 5, 10, 11].iter() {
+        let bound = (q + (1 << d)) >> (d + 1);
+        let y = decompress(compress(&x, q, d).raw(), q, d);
+        for (a, b) in x.iter().zip(y.iter()) {
+            let e = a.abs_diff(*b);
+            assert!(std::cmp::min(e, q - e) <= bound, "d = {}, x = {}", d, a);
+        }
+    }
+}
+
+#[test]
+fn test_byte_encode() {
+    let bytes = byte_encode(&[1u128, 2, 3328, 0], 12).unwrap();
+    assert_eq!(bytes.to_hex(), "012000000d00");
+    assert_eq!(byte_decode::<u128>(&bytes, 4, 12, 3329).unwrap().raw(), &[1, 2, 3328, 0]);
+
+    // A polynomial round trip with 12 bits.
+    let p = Rq::random();
+    let bytes = byte_encode(&p.poly, 12).unwrap();
+    assert_eq!(bytes.len(), 384);
+    assert_eq!(Rq::from(byte_decode::<u128>(&bytes, 256, 12, 3329).unwrap()), p);
+
+    let c = compress(&p.poly, 3329, 1);
+    let m = byte_encode(c.raw(), 1).unwrap();
+    assert_eq!(m.len(), 32);
+    assert_eq!(byte_decode::<u128>(&m, 256, 1, 0).unwrap(), c);
+
+    // Non-canonical: the coefficient 0xfff isn't smaller than q.
+    let bytes = ByteSeq::from("ff0f00");
+    assert_eq!(byte_decode::<u128>(&bytes, 2, 12, 0).unwrap().raw(), &[4095, 0]);
+    assert_eq!(byte_decode::<u128>(&bytes, 2, 12, 3329), Err(EncodingError::NonCanonical));
+    assert_eq!(byte_decode::<u128>(&bytes, 3, 12, 3329), Err(EncodingError::InvalidLength));
+    // Non-zero padding bits
+    assert_eq!(byte_decode::<u128>(&ByteSeq::from("ff1f"), 1, 12, 0), Err(EncodingError::NonCanonical));
+
+    assert_eq!(byte_encode(&[16u128], 4).err(), Some(EncodingError::NonCanonical));
+    assert_eq!(byte_encode(&[0u128], 0).err(), Some(EncodingError::InvalidParameters));
+}
+
+#[test]
+fn test_bit_pack() {
+    let q = 8380417u128;
+
+    // ML-DSA with η = 2 packs 2 - x with 3 bits.
+    let s: Vec<i128> = vec![2, -2, 0, 1, -1, 0, 0, 0];
+    let bytes = bit_pack(&s, 2, 2, 0).unwrap();
+    assert_eq!(bytes.to_hex(), "a03249");
+    assert_eq!(bit_unpack::<i128>(&bytes, 8, 2, 2, 0).unwrap().raw(), &s[..]);
+    // The same coefficients in ℤq
+    let s_q: Vec<u128> = s.iter().map(|&c| c.rem_euclid(q as i128) as u128).collect();
+    assert_eq!(bit_pack(&s_q, 2, 2, q).unwrap().to_hex(), bytes.to_hex());
+    assert_eq!(bit_unpack::<u128>(&bytes, 8, 2, 2, q).unwrap().raw(), &s_q[..]);
+
+    // z with γ1 = 2^17 in [-(γ1 - 1), γ1]
+    let g = 1u128 << 17;
+    let z: Vec<u128> = vec![g, q - (g - 1), 0, 1];
+    let bytes = bit_pack(&z, g - 1, g, q).unwrap();
+    assert_eq!(bytes.to_hex(), "0000fcff0f00e0ff7f");
+    assert_eq!(bit_unpack::<u128>(&bytes, 4, g - 1, g, q).unwrap().raw(), &z[..]);
+    assert_eq!(bit_pack(&[q - g], g - 1, g, q).err(), Some(EncodingError::NonCanonical));
+
+    // t0 in [-(2^12 - 1), 2^12] round trip
+    let t0 = random_poly::<i128>(256, -4095, 4097);
+    let bytes = bit_pack(t0.raw(), 4095, 4096, 0).unwrap();
+    assert_eq!(bytes.len(), 416);
+    assert_eq!(bit_unpack::<i128>(&bytes, 256, 4095, 4096, 0).unwrap(), t0);
+
+    // Non-canonical: 7 decodes to 2 - 7 = -5 < -η.
+    let bytes = ByteSeq::from("07");
+    assert_eq!(bit_unpack::<i128>(&bytes, 2, 2, 2, 0), Err(EncodingError::NonCanonical));
+    assert_eq!(bit_pack(&[3i128], 2, 2, 0).err(), Some(EncodingError::NonCanonical));
+}
@@ -0,0 +1,145 @@
+use hacspec::prelude::*;
+
+fn product(factors: &[Vec<u128>], p: u128) -> Vec<u128> {
+    let mut r = vec![1u128];
+    for f in factors.iter() {
+        r = poly_monic(&poly_mul(&r, f, p), p).unwrap();
+    }
+    r
+}
+
+fn sorted(mut factors: Vec<Vec<u128>>) -> Vec<Vec<u128>> {
+    factors.sort();
+    factors
+}
+
+#[test]
+fn test_irreducible() {
+    // AES: x^8 + x^4 + x^3 + x + 1 over ℤ2
+    assert!(poly_is_irreducible(&[1u128, 1, 0, 1, 1, 0, 0, 0, 1], 2).unwrap());
+    assert!(!poly_is_irreducible(&[1u128, 0, 0, 1, 1, 0, 0, 0, 1], 2).unwrap());
+    // x^2 + 1 is irreducible modulo 3 and 2^61 - 1 but not modulo 5.
+    assert!(poly_is_irreducible(&[1u128, 0, 1], 3).unwrap());
+    assert!(poly_is_irreducible(&[1u128, 0, 1], 2305843009213693951).unwrap());
+    assert!(!poly_is_irreducible(&[1u128, 0, 1], 5).unwrap());
+    // Kyber: x^256 + 1 splits modulo 3329, x^2 - 17 doesn't.
+    let mut f = vec![0u128; 257];
+    f[0] = 1;
+    f[256] = 1;
+    assert!(!poly_is_irreducible(&f, 3329).unwrap());
+    assert!(poly_is_irreducible(&[3329 - 17u128, 0, 1], 3329).unwrap());
+
+    assert!(poly_is_irreducible(&[3u128, 5], 7).unwrap());
+    assert!(!poly_is_irreducible(&[3u128], 7).unwrap());
+    // A product of two irreducible polynomials of degree 3 has no roots.
+    let g = poly_mul(&[1u128, 2, 0, 1], &[2, 2, 0, 1], 3);
+    assert!(!poly_is_irreducible(&g, 3).unwrap());
+    assert_eq!(poly_roots(&g, 3).unwrap(), vec![]);
+}
+
+#[test]
+fn test_ddf_edf() {
+    // (x + 1)(x + 2)(x^2 + 1)(x^3 + 2x + 1)(x^3 + 2x + 2) over ℤ3
+    let p = 3u128;
+    let linear = vec![vec![1u128, 1], vec![2, 1]];
+    let quadratic = vec![vec![1u128, 0, 1]];
+    let cubic = vec![vec![1u128, 2, 0, 1], vec![2, 2, 0, 1]];
+    let f = product(&[linear.clone(), quadratic.clone(), cubic.clone()].concat(), p);
+
+    let ddf = poly_ddf(&f, p).unwrap();
+    assert_eq!(ddf.iter().map(|(_, d)| *d).collect::<Vec<usize>>(), vec![1, 2, 3]);
+    assert_eq!(ddf[0].0, product(&linear, p));
+    assert_eq!(ddf[1].0, product(&quadratic, p));
+    assert_eq!(ddf[2].0, product(&cubic, p));
+
+    assert_eq!(sorted(poly_edf(&ddf[0].0, 1, p).unwrap()), linear);
+    assert_eq!(sorted(poly_edf(&ddf[1].0, 2, p).unwrap()), quadratic);
+    assert_eq!(sorted(poly_edf(&ddf[2].0, 3, p).unwrap()), cubic);
+
+    // Like x^256 + 1 modulo 3329 in Kyber, x^16 + 1 splits into 8 factors
+    // x^2 - ζ^(2i + 1) modulo 17.
+    let mut f = vec![0u128; 17];
+    f[0] = 1;
+    f[16] = 1;
+    let ddf = poly_ddf(&f, 17).unwrap();
+    assert_eq!(ddf.len(), 1);
+    assert_eq!(ddf[0].1, 2);
+    let factors = poly_edf(&ddf[0].0, 2, 17).unwrap();
+    assert_eq!(factors.len(), 8);
+    assert!(factors.iter().all(|g| g[1] == 0 && g[2] == 1));
+    assert_eq!(product(&factors, 17), f);
+
+    assert_eq!(
+        poly_ddf(&poly_mul(&[1u128, 1], &[1, 1], p), p),
+        Err(PolyError::NotSquarefree)
+    );
+
+    // x^2 + 1 is irreducible over ℤ3 and doesn't split into linear factors.
+    assert_eq!(poly_edf(&[1u128, 0, 1], 1, p), Err(PolyError::NotEqualDegree));
+    assert_eq!(poly_edf(&f, 4, 17), Err(PolyError::NotEqualDegree));
+    assert_eq!(poly_edf(&f, 0, 17), Err(PolyError::NotEqualDegree));
+    assert_eq!(poly_edf(&[1u128], 1, p), Err(PolyError::NotEqualDegree));
+}
+
+#[test]
+fn test_roots() {
+    // (x - 3)^2 (x - 5) over ℤ7
+    let f = poly_mul(&poly_mul(&[4u128, 1], &[4, 1], 7), &[2, 1], 7);
+    assert_eq!(poly_roots(&f, 7).unwrap(), vec![3, 5]);
+    // x^2 + x over ℤ2
+    assert_eq!(poly_roots(&[0u128, 1, 1], 2).unwrap(), vec![0, 1]);
+    assert_eq!(poly_roots(&[5u128], 7).unwrap(), vec![]);
+
+    // x^128 + 1 has the 128 roots ζ^(2i + 1) modulo 3329 with ζ = 17.
+    let q = 3329u128;
+    let mut f = vec![0u128; 129];
+    f[0] = 1;
+    f[128] = 1;
+    let roots = poly_roots(&f, q).unwrap();
+    assert_eq!(roots.len(), 128);
+    let mut expected: Vec<u128> = (0..128).map(|i| u128::pow_mod(17, 2 * i + 1, q)).collect();
+    expected.sort();
+    assert_eq!(roots, expected);
+
+    // Roots of a random polynomial are roots.
+    let g = random_poly::<u128>(20, 0, 7681);
+    for r in poly_roots(g.raw(), 7681).unwrap() {
+        assert_eq!(poly_eval(g.raw(), r, 7681), 0);
+    }
+}
+
+// GF(3^3) = ℤ3[x]/(x^3 + 2x + 1)
+poly!(Gf27, u128, 3, 3, &[(0, 1), (1, 2), (3, 1)], validate);
+
+#[test]
+fn test_poly_validation() {
+    let a = Gf27::new(&[(0, 1), (2, 2)]);
+    let one = Gf27::new(&[(0, 1)]);
+    assert_eq!(a * a.inv(), one);
+}
+
+#[test]
+#[should_panic(expected = "not irreducible")]
+fn test_poly_validation_panic() {
+    // x^3 + 2x = x(x + 1)(x + 2)
+    poly!(Gf27Broken, u128, 3, 3, &[(1, 2), (3, 1)], validate);
+    let _ = Gf27Broken::new(&[(0, 1)]);
+}
+
+// GF(2^8) = ℤ2[x]/(x^8 + x^4 + x^3 + x + 1) as in AES
+poly!(Gf256, u8, 8, 2, &[(0, 1), (1, 1), (3, 1), (4, 1), (8, 1)], MulStrategy::Schoolbook, validate);
+
+#[test]
+fn test_poly_validation_strategy() {
+    let a = Gf256::new(&[(0, 1), (1, 1), (6, 1)]);
+    let one = Gf256::new(&[(0, 1)]);
+    assert_eq!(a * a.inv(), one);
+}
+
+#[test]
+#[should_panic(expected = "can't be validated: NonUnitLeadingCoefficient")]
+fn test_poly_validation_error() {
+    // 2 isn't a unit in ℤ4.
+    poly!(Z4Broken, u128, 2, 4, &[(0, 1), (2, 2)], validate);
+    let _ = Z4Broken::new(&[(0, 1)]);
+}
@@ -0,0 +1,88 @@
+use hacspec::prelude::*;
+
+montgomery_integer!(KyberMont, 3329);
+
+const STRATEGIES: [MulStrategy; 5] = [
+    MulStrategy::Sparse,
+    MulStrategy::Schoolbook,
+    MulStrategy::Karatsuba,
+    MulStrategy::ToomCook3,
+    MulStrategy::ToomCook4,
+];
+
+#[test]
+fn test_strategies_agree() {
+    // Saber (q = 2^13) and NTRU (q = 2^11) moduli aren't NTT-friendly.
+    for &q in [8192u128, 2048, 3329].iter() {
+        for &(la, lb) in [(256, 256), (509, 509), (100, 37), (5, 200), (1, 1)].iter() {
+            let a = random_poly::<u128>(la, 0, q as i128);
+            let b = random_poly::<u128>(lb, 0, q as i128);
+            let expected = poly_mul(a.raw(), b.raw(), q);
+            for &strategy in STRATEGIES.iter() {
+                assert_eq!(poly_mul_with(a.raw(), b.raw(), q, strategy), expected, "{:?}", strategy);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_toom_cook_integers() {
+    // Over ℤ with signed coefficients.
+    let a = random_poly::<i128>(300, -1000, 1000);
+    let b = random_poly::<i128>(300, -1000, 1000);
+    let expected = poly_mul_with(a.raw(), b.raw(), 0, MulStrategy::Schoolbook);
+    assert_eq!(poly_mul_toom_cook(a.raw(), b.raw(), 0, 3), expected);
+    assert_eq!(poly_mul_toom_cook(a.raw(), b.raw(), 0, 4), expected);
+    assert_eq!(poly_mul_toom_cook(a.raw(), b.raw(), 0, 5), expected);
+    assert_eq!(poly_mul_karatsuba(a.raw(), b.raw(), 0), expected);
+
+    // Unsigned Karatsuba over ℤ never has negative intermediate values.
+    let a = random_poly::<u128>(300, 0, 1 << 20);
+    let b = random_poly::<u128>(300, 0, 1 << 20);
+    assert_eq!(poly_mul_karatsuba(a.raw(), b.raw(), 0), poly_mul(a.raw(), b.raw(), 0));
+}
+
+#[test]
+fn test_toom_cook_narrow_integers() {
+    // The modulus can't be lifted in every recursion level.
+    let a = random_poly::<u16>(512, 0, 8192);
+    let b = random_poly::<u16>(512, 0, 8192);
+    let expected = poly_mul(a.raw(), b.raw(), 8192);
+    assert_eq!(poly_mul_toom_cook(a.raw(), b.raw(), 8192, 3), expected);
+    assert_eq!(poly_mul_toom_cook(a.raw(), b.raw(), 8192, 4), expected);
+
+    let a = random_poly::<u32>(4096, 0, 8192);
+    let b = random_poly::<u32>(4096, 0, 8192);
+    let expected = poly_mul(a.raw(), b.raw(), 8192);
+    assert_eq!(poly_mul_toom_cook(a.raw(), b.raw(), 8192, 4), expected);
+
+    // 3329·5! doesn't fit into i16 but 5! is invertible modulo 3329.
+    let a = random_poly::<i16>(256, 0, 3329);
+    let b = random_poly::<i16>(256, 0, 3329);
+    assert_eq!(poly_mul_toom_cook(a.raw(), b.raw(), 3329, 4), poly_mul(a.raw(), b.raw(), 3329));
+}
+
+#[test]
+fn test_toom_cook_modular_element() {
+    let q = KyberMont::modulus();
+    let mut rng = rand::thread_rng();
+    let zero = KyberMont::from_literal(0);
+    let a: Vec<KyberMont> = (0..256).map(|_| rng.gen_range(zero, q)).collect();
+    let b: Vec<KyberMont> = (0..256).map(|_| rng.gen_range(zero, q)).collect();
+    let expected = poly_mul(&a, &b, q);
+    assert_eq!(poly_mul_with(&a, &b, q, MulStrategy::ToomCook4), expected);
+    assert_eq!(poly_mul_with(&a, &b, q, MulStrategy::Karatsuba), expected);
+}
+
+// Saber: Rq = ℤ(2^13)[x]/(x^256 + 1)
+poly!(RqSaber, u128, 256, 8192, &[(0, 1), (256, 1)]);
+poly!(RqSaberToom, u128, 256, 8192, &[(0, 1), (256, 1)], MulStrategy::ToomCook4);
+
+#[test]
+fn test_poly_strategy() {
+    let a = RqSaber::random();
+    let b = RqSaber::random();
+    let c = a * b;
+    let c_toom = RqSaberToom::new_full(a.poly) * RqSaberToom::new_full(b.poly);
+    assert_eq!(c.poly.to_vec(), c_toom.poly.to_vec());
+}
@@ -0,0 +1,177 @@
+use hacspec::prelude::*;
+
+unsigned_integer!(P256Canvas, 256);
+field_integer!(
+    P256FieldElement,
+    P256Canvas,
+    P256Canvas::from_bytes_be(&hex_string_to_bytes(
+        "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff"
+    ))
+);
+
+unsigned_integer!(P384Canvas, 384);
+field_integer!(
+    P384FieldElement,
+    P384Canvas,
+    P384Canvas::from_bytes_be(&hex_string_to_bytes(
+        "fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffff0000000000000000ffffffff"
+    ))
+);
+
+unsigned_integer!(Ed448Canvas, 448);
+field_integer!(
+    Ed448FieldElement,
+    Ed448Canvas,
+    Ed448Canvas::from_bytes_be(&hex_string_to_bytes(
+        "fffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffffffffffffffffffffffffffffffffffffffffffffffffffff"
+    ))
+);
+
+bytes!(P256SerializedFieldElement, 32);
+bytes!(P384SerializedFieldElement, 48);
+
+#[test]
+fn test_fixed_width_encoding() {
+    assert_eq!(P256FieldElement::byte_len(), 32);
+    assert_eq!(P384FieldElement::byte_len(), 48);
+    assert_eq!(Ed448FieldElement::byte_len(), 56);
+
+    let one = P256FieldElement::from_literal(1);
+    let le = one.to_byte_seq_le();
+    let be = one.to_byte_seq_be();
+    assert_eq!(le.len(), 32);
+    assert_eq!(be.len(), 32);
+    assert_eq!(U8::declassify(le[0]), 1);
+    assert_eq!(U8::declassify(be[31]), 1);
+    assert_eq!(P256FieldElement::from_byte_seq_le(le), one);
+    assert_eq!(P256FieldElement::from_byte_seq_be(be), one);
+
+    let x = P384FieldElement::from_hex("aa87ca22be8b05378eb1c71ef320ad746e1d3b628ba79b9859f741e082542a385502f25dbf55296c3a545e3872760ab7");
+    let be: P384SerializedFieldElement = x.to_byte_array_be();
+    assert_eq!(
+        be.to_hex(),
+        "aa87ca22be8b05378eb1c71ef320ad746e1d3b628ba79b9859f741e082542a385502f25dbf55296c3a545e3872760ab7"
+    );
+    let le: P384SerializedFieldElement = x.to_byte_array_le();
+    assert_eq!(U8::declassify(le[0]), 0xb7);
+    assert_eq!(P384FieldElement::from_byte_seq_be(be), x);
+    assert_eq!(P384FieldElement::from_byte_seq_le(le), x);
+
+    let zero: P256SerializedFieldElement = P256FieldElement::from_literal(0).to_byte_array_le();
+    assert_eq!(zero, P256SerializedFieldElement::new());
+}
+
+#[test]
+fn test_canonical_decoding() {
+    let p = ByteSeq::from("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff");
+    let p_minus_one = ByteSeq::from("ffffffff00000001000000000000000000000000fffffffffffffffffffffffe");
+    assert_eq!(P256FieldElement::from_canonical_byte_seq_be(p.clone()), None);
+    assert_eq!(
+        P256FieldElement::from_canonical_byte_seq_be(p_minus_one.clone()),
+        Some(P256FieldElement::from_literal(0) - P256FieldElement::from_literal(1))
+    );
+    // The unchecked version reduces.
+    assert_eq!(P256FieldElement::from_byte_seq_be(p), P256FieldElement::from_literal(0));
+
+    // Wrong length
+    assert_eq!(P256FieldElement::from_canonical_byte_seq_be(p_minus_one.sub(1, 31)), None);
+
+    let p_le = ByteSeq::from(
+        "fffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+    );
+    assert_eq!(Ed448FieldElement::from_canonical_byte_seq_le(p_le), None);
+    let two = Ed448FieldElement::from_literal(2);
+    assert_eq!(Ed448FieldElement::from_canonical_byte_seq_le(two.to_byte_seq_le()), Some(two));
+
+    assert_eq!(P256FieldElement::modulus_biguint().bits(), 256);
+    assert_eq!(P256FieldElement::from_biguint(&P256FieldElement::modulus_biguint()), P256FieldElement::from_literal(0));
+}
+
+// p = 2^255 - 19 ≡ 5 mod 8
+unsigned_integer!(Curve25519Canvas, 256);
+field_integer!(
+    Curve25519FieldElement,
+    Curve25519Canvas,
+    Curve25519Canvas::from_bytes_be(&hex_string_to_bytes(
+        "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed"
+    ))
+);
+
+// p = 2^32 - 2^20 + 1 ≡ 1 mod 2^20 needs Tonelli-Shanks.
+unsigned_integer!(SmallCanvas, 64);
+field_integer!(SmallFieldElement, SmallCanvas, SmallCanvas::from_literal(0xfff0_0001));
+
+#[test]
+fn test_pow_inv() {
+    let x = P256FieldElement::from_literal(0x1234_5678_9abc_def0);
+    assert_eq!(x * x.inv(), P256FieldElement::from_literal(1));
+    assert_eq!(P256FieldElement::from_literal(0).inv(), P256FieldElement::from_literal(0));
+    assert_eq!(x.pow_felem(P256FieldElement::from_literal(3)), x * x * x);
+    assert_eq!(x.pow_felem(P256FieldElement::from_literal(0)), P256FieldElement::from_literal(1));
+    let p_minus_one = P256FieldElement::modulus_biguint() - 1u32;
+    assert_eq!(x.pow_biguint(&p_minus_one), P256FieldElement::from_literal(1));
+
+    let y = Curve25519FieldElement::from_literal(9);
+    assert_eq!(y.inv() * y, Curve25519FieldElement::from_literal(1));
+}
+
+macro_rules! check_sqrt_of_squares {
+    ($t:ty) => {
+        for a in 0..200u128 {
+            let x = <$t>::from_literal(a);
+            let sq = x * x;
+            assert!(sq.is_square());
+            let r = sq.sqrt().unwrap();
+            assert!(r == x || r == <$t>::from_literal(0) - x);
+        }
+    };
+}
+
+#[test]
+fn test_sqrt() {
+    check_sqrt_of_squares!(P256FieldElement);
+    check_sqrt_of_squares!(Curve25519FieldElement);
+    check_sqrt_of_squares!(SmallFieldElement);
+
+    // Non-squares: -1 for p ≡ 3 mod 4, 2 for p ≡ 5 mod 8, and the smallest
+    // non-residue 17 mod 2^32 - 2^20 + 1.
+    let minus_one = P256FieldElement::from_literal(0) - P256FieldElement::from_literal(1);
+    assert!(!minus_one.is_square());
+    assert_eq!(minus_one.sqrt(), None);
+    assert!(!Curve25519FieldElement::from_literal(2).is_square());
+    assert_eq!(Curve25519FieldElement::from_literal(2).sqrt(), None);
+    // sqrt(-1) exists for p ≡ 1 mod 4
+    let i = (Curve25519FieldElement::from_literal(0) - Curve25519FieldElement::from_literal(1))
+        .sqrt()
+        .unwrap();
+    assert_eq!(i * i + Curve25519FieldElement::from_literal(1), Curve25519FieldElement::from_literal(0));
+    let n = SmallFieldElement::from_literal(17);
+    assert!(!n.is_square());
+    assert_eq!(n.sqrt(), None);
+    assert!((n * n * n).sqrt().is_none());
+}
+
+#[test]
+fn test_sgn0() {
+    assert!(!P256FieldElement::from_literal(0).sgn0());
+    assert!(P256FieldElement::from_literal(1).sgn0());
+    assert!(!P256FieldElement::from_literal(2).sgn0());
+    // -1 = p - 1 is even
+    assert!(!(P256FieldElement::from_literal(0) - P256FieldElement::from_literal(1)).sgn0());
+}
+
+#[test]
+fn test_batch_inv() {
+    let xs: Seq<P256FieldElement> = Seq::from(
+        (0..10u128)
+            .map(|i| P256FieldElement::from_literal(i * 0x1_0000_0001 + 3))
+            .chain(Some(P256FieldElement::from_literal(0)))
+            .collect::<Vec<_>>(),
+    );
+    let invs = P256FieldElement::batch_inv(&xs);
+    assert_eq!(invs.len(), xs.len());
+    for i in 0..xs.len() {
+        assert_eq!(invs[i], xs[i].inv());
+    }
+    assert_eq!(P256FieldElement::batch_inv(&Seq::new(0)).len(), 0);
+}
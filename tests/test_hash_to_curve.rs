@@ -0,0 +1,338 @@
+use hacspec::prelude::*;
+
+mod common;
+use common::{sha256, sha512, shake128};
+
+unsigned_integer!(Canvas256, 256);
+
+field_integer!(
+    P256FieldElement,
+    Canvas256,
+    Canvas256::from_bytes_be(&hex_string_to_bytes(
+        "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff"
+    ))
+);
+field_integer!(
+    P256Scalar,
+    Canvas256,
+    Canvas256::from_bytes_be(&hex_string_to_bytes(
+        "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551"
+    ))
+);
+
+struct P256;
+impl WeierstrassCurve for P256 {
+    type Field = P256FieldElement;
+    type Scalar = P256Scalar;
+    fn a() -> P256FieldElement {
+        P256FieldElement::from_literal(0) - P256FieldElement::from_literal(3)
+    }
+    fn b() -> P256FieldElement {
+        P256FieldElement::from_hex("5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b")
+    }
+    fn generator() -> Affine<P256FieldElement> {
+        Affine::Point(
+            P256FieldElement::from_hex("6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296"),
+            P256FieldElement::from_hex("4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5"),
+        )
+    }
+}
+
+field_integer!(
+    Secp256k1FieldElement,
+    Canvas256,
+    Canvas256::from_bytes_be(&hex_string_to_bytes(
+        "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f"
+    ))
+);
+field_integer!(
+    Secp256k1Scalar,
+    Canvas256,
+    Canvas256::from_bytes_be(&hex_string_to_bytes(
+        "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"
+    ))
+);
+
+struct Secp256k1;
+impl WeierstrassCurve for Secp256k1 {
+    type Field = Secp256k1FieldElement;
+    type Scalar = Secp256k1Scalar;
+    fn a() -> Secp256k1FieldElement {
+        Secp256k1FieldElement::from_literal(0)
+    }
+    fn b() -> Secp256k1FieldElement {
+        Secp256k1FieldElement::from_literal(7)
+    }
+    fn generator() -> Affine<Secp256k1FieldElement> {
+        Affine::Point(
+            Secp256k1FieldElement::from_hex("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"),
+            Secp256k1FieldElement::from_hex("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"),
+        )
+    }
+}
+
+fn sha256_xmd() -> Expander {
+    Expander::Xmd {
+        hash: sha256,
+        b_in_bytes: 32,
+        s_in_bytes: 64,
+    }
+}
+
+struct P256Sswu;
+impl WeierstrassSuite for P256Sswu {
+    type Field = P256FieldElement;
+    type Curve = P256;
+    const K: usize = 128;
+    fn expander() -> Expander {
+        sha256_xmd()
+    }
+    fn map() -> WeierstrassMap<P256FieldElement> {
+        WeierstrassMap::Sswu {
+            z: P256FieldElement::from_literal(0) - P256FieldElement::from_literal(10),
+        }
+    }
+}
+
+// RFC 9380, Appendix E.1: 3-isogeny map for secp256k1
+const SECP256K1_ISO: [&[&str]; 4] = [
+    &[
+        "8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa8c7",
+        "07d3d4c80bc321d5b9f315cea7fd44c5d595d2fc0bf63b92dfff1044f17c6581",
+        "534c328d23f234e6e2a413deca25caece4506144037c40314ecbd0b53d9dd262",
+        "8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa88c",
+    ],
+    &[
+        "d35771193d94918a9ca34ccbb7b640dd86cd409542f8487d9fe6b745781eb49b",
+        "edadc6f64383dc1df7c4b2d51b54225406d36b641f5e41bbc52a56612a8c6d14",
+        "01",
+    ],
+    &[
+        "4bda12f684bda12f684bda12f684bda12f684bda12f684bda12f684b8e38e23c",
+        "c75e0c32d5cb7c0fa9d0a54b12a0a6d5647ab046d686da6fdffc90fc201d71a3",
+        "29a6194691f91a73715209ef6512e576722830a201be2018a765e85a9ecee931",
+        "2f684bda12f684bda12f684bda12f684bda12f684bda12f684bda12f38e38d84",
+    ],
+    &[
+        "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffff93b",
+        "7a06534bb8bdb49fd5e9e6632722c2989467c1bfc8e8d978dfb425d2685c2573",
+        "6484aa716545ca2cf3a70c3fa8fe337e0a3d21162f0d6299a7bf8192bfd2a76f",
+        "01",
+    ],
+];
+
+struct Secp256k1Sswu;
+impl WeierstrassSuite for Secp256k1Sswu {
+    type Field = Secp256k1FieldElement;
+    type Curve = Secp256k1;
+    const K: usize = 128;
+    fn expander() -> Expander {
+        sha256_xmd()
+    }
+    fn map() -> WeierstrassMap<Secp256k1FieldElement> {
+        let coefficients = |i: usize| {
+            SECP256K1_ISO[i]
+                .iter()
+                .map(|c| Secp256k1FieldElement::from_hex(c))
+                .collect::<Vec<_>>()
+        };
+        WeierstrassMap::SswuIsogeny {
+            a: Secp256k1FieldElement::from_hex("3f8731abdd661adca08a5558f0f5d272e953d363cb6f0e5d405447c01a444533"),
+            b: Secp256k1FieldElement::from_literal(1771),
+            z: Secp256k1FieldElement::from_literal(0) - Secp256k1FieldElement::from_literal(11),
+            iso: IsogenyMap {
+                x_num: coefficients(0),
+                x_den: coefficients(1),
+                y_num: coefficients(2),
+                y_den: coefficients(3),
+            },
+        }
+    }
+}
+
+struct Edwards25519Ell2;
+impl EdwardsSuite for Edwards25519Ell2 {
+    type Field = Ed25519FieldElement;
+    type Curve = Ed25519;
+    const K: usize = 128;
+    fn expander() -> Expander {
+        Expander::Xmd {
+            hash: sha512,
+            b_in_bytes: 64,
+            s_in_bytes: 128,
+        }
+    }
+    fn map() -> Elligator2Map<Ed25519FieldElement> {
+        Elligator2Map {
+            j: Ed25519FieldElement::from_literal(486662),
+            k: Ed25519FieldElement::from_literal(1),
+            z: Ed25519FieldElement::from_literal(2),
+            // sqrt(-486664) with sgn0 = 0
+            c: Ed25519FieldElement::from_hex("0f26edf460a006bbd27b08dc03fc4f7ec5a1d3d14b7d1a82cc6e04aaff457e06"),
+        }
+    }
+}
+
+create_test_vectors!(
+    ExpanderTestVectors,
+    DST: String,
+    hash: String,
+    k: usize,
+    name: String,
+    tests: Vec<ExpanderTest>
+);
+create_test_vectors!(ExpanderTest, len_in_bytes: String, msg: String, uniform_bytes: String);
+
+create_test_vectors!(
+    SuiteTestVectors,
+    ciphersuite: String,
+    dst: String,
+    randomOracle: bool,
+    vectors: Vec<SuiteTest>
+);
+create_test_vectors!(SuiteTest, P: Point, msg: String, u: Vec<String>);
+create_test_vectors!(Point, x: String, y: String);
+
+fn byte_seq(s: &str) -> ByteSeq {
+    ByteSeq::from(s.as_bytes().iter().map(|x| U8::classify(*x)).collect::<Vec<U8>>())
+}
+
+fn strip(s: &str) -> &str {
+    s.trim_start_matches("0x")
+}
+
+fn check_expander(file: &'static str, expander: Expander) {
+    let vectors = ExpanderTestVectors::new(file);
+    assert_eq!(vectors.tests.len(), 10);
+    let dst = byte_seq(&vectors.DST);
+    for test in vectors.tests.iter() {
+        let len_in_bytes = usize::from_str_radix(strip(&test.len_in_bytes), 16).unwrap();
+        let uniform_bytes = expand_message(&expander, &byte_seq(&test.msg), &dst, len_in_bytes).unwrap();
+        assert_eq!(uniform_bytes.to_hex(), test.uniform_bytes);
+    }
+}
+
+#[test]
+fn test_expand_message_xmd() {
+    check_expander("tests/h2c/expand_message_xmd_SHA256_38.json", sha256_xmd());
+}
+
+#[test]
+fn test_expand_message_xmd_oversize_dst() {
+    let vectors = ExpanderTestVectors::new("tests/h2c/expand_message_xmd_SHA256_256.json");
+    assert!(vectors.DST.len() > 255);
+    check_expander("tests/h2c/expand_message_xmd_SHA256_256.json", sha256_xmd());
+}
+
+#[test]
+fn test_expand_message_xof() {
+    check_expander(
+        "tests/h2c/expand_message_xof_SHAKE128_36.json",
+        Expander::Xof { xof: shake128, k: 128 },
+    );
+}
+
+#[test]
+fn test_expand_message_invalid_length() {
+    let msg = byte_seq("abc");
+    let dst = byte_seq("QUUX-V01-CS02-with-expander-SHA256-128");
+    assert_eq!(
+        expand_message(&sha256_xmd(), &msg, &dst, 255 * 32 + 1).unwrap_err(),
+        HashToCurveError::InvalidLength
+    );
+    assert!(expand_message(&sha256_xmd(), &msg, &dst, 255 * 32).is_ok());
+    assert_eq!(
+        expand_message(&Expander::Xof { xof: shake128, k: 128 }, &msg, &dst, 65536).unwrap_err(),
+        HashToCurveError::InvalidLength
+    );
+}
+
+fn check_weierstrass_suite<S: WeierstrassSuite>(file: &'static str) {
+    let vectors = SuiteTestVectors::new(file);
+    let dst = byte_seq(&vectors.dst);
+    let count = if vectors.randomOracle { 2 } else { 1 };
+    for test in vectors.vectors.iter() {
+        let msg = byte_seq(&test.msg);
+        let u = hash_to_field::<S::Field>(&msg, &dst, count, 1, S::K, &S::expander()).unwrap();
+        let u_hex = u.iter().map(|e| e[0].to_byte_seq_be().to_hex()).collect::<Vec<_>>();
+        let expected_u = test.u.iter().map(|e| strip(e).to_string()).collect::<Vec<_>>();
+        assert_eq!(u_hex, expected_u);
+
+        let p = if vectors.randomOracle {
+            S::hash_to_curve(&msg, &dst).unwrap()
+        } else {
+            S::encode_to_curve(&msg, &dst).unwrap()
+        };
+        assert!(S::Curve::is_on_curve(p));
+        match p {
+            Affine::Point(x, y) => {
+                assert_eq!(x.to_byte_seq_be().to_hex(), strip(&test.P.x));
+                assert_eq!(y.to_byte_seq_be().to_hex(), strip(&test.P.y));
+            }
+            Affine::Infinity => panic!("Unexpected point at infinity."),
+        }
+    }
+}
+
+#[test]
+fn test_p256_sswu_ro() {
+    check_weierstrass_suite::<P256Sswu>("tests/h2c/P256_XMD_SHA-256_SSWU_RO_.json");
+}
+
+#[test]
+fn test_p256_sswu_nu() {
+    check_weierstrass_suite::<P256Sswu>("tests/h2c/P256_XMD_SHA-256_SSWU_NU_.json");
+}
+
+#[test]
+fn test_secp256k1_sswu_ro() {
+    check_weierstrass_suite::<Secp256k1Sswu>("tests/h2c/secp256k1_XMD_SHA-256_SSWU_RO_.json");
+}
+
+fn check_edwards_suite<S: EdwardsSuite>(file: &'static str) {
+    let vectors = SuiteTestVectors::new(file);
+    let dst = byte_seq(&vectors.dst);
+    for test in vectors.vectors.iter() {
+        let msg = byte_seq(&test.msg);
+        let p = if vectors.randomOracle {
+            S::hash_to_curve(&msg, &dst).unwrap()
+        } else {
+            S::encode_to_curve(&msg, &dst).unwrap()
+        };
+        assert!(S::Curve::is_on_curve(p));
+        assert!(S::Curve::is_torsion_free(p));
+        let (x, y) = S::Curve::to_affine(p);
+        assert_eq!(x.to_byte_seq_be().to_hex(), strip(&test.P.x));
+        assert_eq!(y.to_byte_seq_be().to_hex(), strip(&test.P.y));
+    }
+}
+
+#[test]
+fn test_edwards25519_ell2_ro() {
+    check_edwards_suite::<Edwards25519Ell2>("tests/h2c/edwards25519_XMD_SHA-512_ELL2_RO_.json");
+}
+
+#[test]
+fn test_edwards25519_ell2_nu() {
+    check_edwards_suite::<Edwards25519Ell2>("tests/h2c/edwards25519_XMD_SHA-512_ELL2_NU_.json");
+}
+
+#[test]
+fn test_isogeny_map_pole() {
+    let one = P256FieldElement::from_literal(1);
+    // x_den = x - 2
+    let map = IsogenyMap {
+        x_num: vec![one],
+        x_den: vec![P256FieldElement::from_literal(0) - P256FieldElement::from_literal(2), one],
+        y_num: vec![one],
+        y_den: vec![one],
+    };
+    assert_eq!(
+        map.map(P256FieldElement::from_literal(2), P256FieldElement::from_literal(5)),
+        Affine::Infinity
+    );
+    assert_eq!(
+        map.map(P256FieldElement::from_literal(3), P256FieldElement::from_literal(5)),
+        Affine::Point(one, P256FieldElement::from_literal(5))
+    );
+}
@@ -0,0 +1,176 @@
+use hacspec::prelude::*;
+use rand::SeedableRng;
+
+// 2^127 - 1
+const P127: u128 = 0x7fff_ffff_ffff_ffff_ffff_ffff_ffff_ffff;
+// 2^128 - 159
+const P128: u128 = 0xffff_ffff_ffff_ffff_ffff_ffff_ffff_ff61;
+
+const A: u128 = 0x5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a;
+const B: u128 = 0x7123456789abcdef0123456789abcdef;
+
+#[test]
+fn test_mul_mod_large_moduli() {
+    assert_eq!(A.mul_mod(B, P127), 113127206493029640150339980054713855572);
+    assert_eq!(A.mul_mod(B, P128), 279498595104276709382759205555126956583);
+    assert_eq!((P127 - 1).mul_mod(P127 - 2, P127), 2);
+    assert_eq!((P128 - 1).mul_mod(P128 - 2, P128), 2);
+    assert_eq!(u128::MAX.mul_mod(u128::MAX, P128), 158 * 158);
+
+    // Products that fit into 128 bits don't need the wide reduction.
+    assert_eq!(0xffff_ffff_ffff_ffffu64.mul_mod(0xffff_ffff_ffff_fffe, 0xffff_ffff_ffff_ffc5), 3306);
+}
+
+#[test]
+fn test_add_sub_mod_large_moduli() {
+    assert_eq!((P128 - 1).add_mod(P128 - 2, P128), 340282366920938463463374607431768211294);
+    assert_eq!(3u128.sub_mod(P128 - 1, P128), 4);
+    assert_eq!((P128 - 1).sub_mod(3, P128), P128 - 4);
+
+    // Neither operand has to be reduced.
+    assert_eq!(5u8.sub_mod(251, 7), 6);
+    assert_eq!(200u8.add_mod(100, 201), 99);
+}
+
+#[test]
+fn test_signed_mod_large_moduli() {
+    let n = P127 as i128;
+    assert_eq!((-3i128).mul_mod(n - 5, n), 15);
+    assert_eq!((A as i128).mul_mod(B as i128, n), 113127206493029640150339980054713855572);
+    assert_eq!(
+        (-(A as i128)).mul_mod(B as i128, n),
+        57013976967439591581347323661170250155
+    );
+    assert_eq!(
+        (-(A as i128)).add_mod(-(B as i128), n),
+        69796806404551315899672302291221338037
+    );
+    assert_eq!((n - 1).add_mod(n - 1, n), n - 2);
+    assert_eq!(1i128.sub_mod(n - 1, n), 2);
+}
+
+#[test]
+fn test_inv_large_moduli() {
+    assert_eq!(u128::inv(A, P127), 28356863910078205288614550619314017624);
+    assert_eq!(u128::inv(B, P128), 182318352501091772939636539855899596977);
+    assert_eq!(A.mul_mod(u128::inv(A, P127), P127), 1);
+}
+
+#[test]
+fn test_poly_large_modulus() {
+    poly!(ZxBig, u128, 3, P127, &[(0, 1), (3, 1)]);
+    let x = ZxBig::new(&[(0, P127 - 1), (1, P127 - 2)]);
+    let y = ZxBig::new(&[(0, P127 - 1)]);
+    let expected = ZxBig::new(&[(0, 1), (1, 2)]);
+    assert_eq!(x * y, expected);
+}
+
+// 2^64 - 2^32 + 1
+const GOLDILOCKS: u128 = 0xffff_ffff_0000_0001;
+
+#[test]
+fn test_pow_mod() {
+    assert_eq!(u128::pow_mod(A, 0x123456789, P127), 35429206092102763689089302377549722502);
+    assert_eq!(u128::pow_mod_ct(A, 0x123456789, P127), 35429206092102763689089302377549722502);
+    // Fermat inversion agrees with the extended euclidean algorithm.
+    assert_eq!(u128::pow_mod(A, P128 - 2, P128), 179114874571253473911628617203008794765);
+    assert_eq!(u128::pow_mod(A, P128 - 2, P128), u128::inv(A, P128));
+    assert_eq!(u128::pow_mod_ct(A, P128 - 2, P128), u128::inv(A, P128));
+
+    // Carmichael number
+    assert_eq!(u16::pow_mod(7, 560, 561), 1);
+    assert_eq!(u32::pow_mod_ct(3, 0x1234, 3329), 276);
+    assert_eq!(i16::pow_mod(-3, 0x1234, 3329), 276);
+    assert_eq!(i64::pow_mod_ct(-3, 0x1234, 3329), 276);
+    assert_eq!(u8::pow_mod(5, 0, 7), 1);
+    assert_eq!(u8::pow_mod_ct(5, 0, 1), 0);
+}
+
+#[test]
+fn test_legendre_jacobi() {
+    let expected = [1, -1, -1, -1, 1, -1];
+    for (&a, &e) in [2u128, 3, 5, 7, 11, 12345].iter().zip(expected.iter()) {
+        assert_eq!(u128::legendre(a, P127), e);
+    }
+    let expected = [1, 1, -1, -1, 1, 1];
+    for (&a, &e) in [2u128, 3, 5, 7, 11, 12345].iter().zip(expected.iter()) {
+        assert_eq!(u128::legendre(a, P128), e);
+    }
+    assert_eq!(u128::legendre(P128, P128), 0);
+    assert_eq!(i32::legendre(-1, 13), 1);
+    assert_eq!(i32::legendre(-1, 3329), 1);
+    assert_eq!(i32::legendre(-1, 8380417), 1);
+    assert_eq!(u32::legendre(3, 3329), -1);
+
+    assert_eq!(u64::jacobi(1001, 9907), -1);
+    assert_eq!(u64::jacobi(19, 45), 1);
+    assert_eq!(u64::jacobi(8, 21), -1);
+    assert_eq!(u64::jacobi(5, 21), 1);
+    assert_eq!(u64::jacobi(30, 57), 0);
+    assert_eq!(i128::jacobi(12345, P127 as i128), -1);
+}
+
+#[test]
+fn test_sqrt_mod() {
+    // p = 3 mod 4
+    assert_eq!(
+        u128::sqrt_mod(122454554186081661592356260113854304468, P127),
+        Some(50041524547196832862260971681142384037)
+    );
+    // p = 1 mod 8 with small and large 2-adicity
+    assert_eq!(
+        u128::sqrt_mod(312023623647227310788215470482417221081, P128),
+        Some(120099658913272398869426332034741721690)
+    );
+    assert_eq!(u128::sqrt_mod(11553151131584416021, GOLDILOCKS), Some(5425512963108395611));
+    assert_eq!(u128::sqrt_mod(7, GOLDILOCKS), None);
+    assert_eq!(u128::sqrt_mod(0, GOLDILOCKS), Some(0));
+
+    for a in 0..3329u32 {
+        match u32::sqrt_mod(a, 3329) {
+            Some(r) => assert_eq!(r.mul_mod(r, 3329), a),
+            None => assert_eq!(u32::legendre(a, 3329), -1),
+        }
+    }
+    assert_eq!(i16::sqrt_mod(-1, 13), Some(5));
+}
+
+#[test]
+fn test_crt() {
+    assert_eq!(crt(&[2u32, 3, 2], &[3, 5, 7]), Ok(23));
+    assert_eq!(
+        crt(&[1234567u128, 7654321], &[(1 << 61) - 1, (1 << 31) - 1]),
+        Ok(4922154265072134791043137756)
+    );
+    assert_eq!(crt(&[-1i32, -2], &[97, 101]), Ok(7371));
+    assert_eq!(crt(&[-1i128, -2], &[97, 101]), Ok(7371));
+
+    assert_eq!(crt(&[1u64, 2, 3], &[6, 35, 10]), Err(CrtError::NotCoprime(0, 2)));
+    assert_eq!(crt(&[1u64, 2], &[6]), Err(CrtError::LengthMismatch));
+    assert_eq!(gcd(-12i64, 18), 6);
+}
+
+#[test]
+fn test_crt_biguint() {
+    // RSA-CRT decryption
+    let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+    let p = random_prime(256, &mut rng);
+    let q = random_prime(256, &mut rng);
+    let n = &p * &q;
+    let one = BigUint::from(1u32);
+    let phi = (&p - &one) * (&q - &one);
+    let e = BigUint::from(65537u32);
+    let d = inv_biguint(&e, &phi).unwrap();
+
+    let m = BigUint::from(0x1234_5678_9abc_def0u64);
+    let c = m.modpow(&e, &n);
+    let m_p = c.modpow(&(&d % (&p - &one)), &p);
+    let m_q = c.modpow(&(&d % (&q - &one)), &q);
+    assert_eq!(crt_biguint(&[m_p, m_q], &[p.clone(), q.clone()]), Ok(m));
+
+    assert_eq!(
+        crt_biguint(&[one.clone(), one.clone()], &[p.clone(), &p * &q]),
+        Err(CrtError::NotCoprime(0, 1))
+    );
+    assert_eq!(inv_biguint(&p, &(&p * &q)), None);
+}
@@ -0,0 +1,121 @@
+use hacspec::prelude::*;
+
+// 2^61 - 1
+const P: u128 = 2305843009213693951;
+
+#[test]
+fn test_poly_eval() {
+    // 3 + 2x + 5x^3
+    let f = [3i128, 2, 0, 5];
+    assert_eq!(poly_eval(&f, 2, 0), 47);
+    assert_eq!(poly_eval(&f, -3, 0), -138);
+    assert_eq!(poly_eval(&f, 2, 11), 3);
+    assert_eq!(poly_eval::<u128>(&[], 5, 7), 0);
+
+    let f = random_poly::<u128>(50, 0, P as i128);
+    let x = 123456789u128;
+    let naive = f.iter().enumerate().fold(0, |acc, (i, &c)| {
+        acc.add_mod(c.mul_mod(u128::pow_mod(x, i as u128, P), P), P)
+    });
+    assert_eq!(poly_eval(f.raw(), x, P), naive);
+}
+
+#[test]
+fn test_poly_derivative() {
+    assert_eq!(poly_derivative(&[3i128, 2, 0, 5], 0), vec![2, 0, 15]);
+    assert_eq!(poly_derivative(&[3u128, 2, 0, 5], 7), vec![2, 0, 1]);
+    assert_eq!(poly_derivative(&[3u128], 7), vec![0]);
+
+    // (fg)' = f'g + fg'
+    let f = random_poly::<u128>(20, 0, 3329);
+    let g = random_poly::<u128>(30, 0, 3329);
+    let fg = poly_mul(f.raw(), g.raw(), 3329);
+    let expected = poly_add(
+        &poly_mul(&poly_derivative(f.raw(), 3329), g.raw(), 3329),
+        &poly_mul(f.raw(), &poly_derivative(g.raw(), 3329), 3329),
+        3329,
+    );
+    let (d, _) = leading_coefficient(&expected);
+    assert_eq!(poly_derivative(&fg, 3329)[..=d], expected[..=d]);
+}
+
+#[test]
+fn test_lagrange_interpolate() {
+    let f = random_poly::<u128>(10, 0, P as i128);
+    let xs: Vec<u128> = (1..=10).map(|i| i * 1000003).collect();
+    let ys: Vec<u128> = xs.iter().map(|&x| poly_eval(f.raw(), x, P)).collect();
+    assert_eq!(lagrange_interpolate(&xs, &ys, P).unwrap(), f.raw().to_vec());
+
+    // Signed coefficients over ℤ7
+    let xs = [0i128, 1, 2];
+    let ys = [1i128, -1, 3];
+    let f = lagrange_interpolate(&xs, &ys, 7).unwrap();
+    assert_eq!(f, vec![1, 2, 3]);
+    for (&x, &y) in xs.iter().zip(ys.iter()) {
+        assert_eq!(poly_eval(&f, x, 7), y.rem_euclid(7));
+    }
+
+    assert_eq!(
+        lagrange_interpolate(&[1u128, 2, 8], &[1, 2, 3], 7),
+        Err(InterpolationError::DuplicatePoint(0, 2))
+    );
+    assert_eq!(lagrange_interpolate(&[1u128, 2], &[1], 7), Err(InterpolationError::LengthMismatch));
+    assert_eq!(lagrange_interpolate(&[1u128, 2], &[1, 2], 0), Err(InterpolationError::NotInvertible));
+    // 2 - 0 isn't invertible modulo 12.
+    assert_eq!(lagrange_interpolate(&[0u128, 2], &[1, 2], 12), Err(InterpolationError::NotInvertible));
+}
+
+#[test]
+fn test_shamir() {
+    // Threshold 3 of 5
+    let secret = 42424242u128;
+    let f = [secret, 987654321, 123456789];
+    let shares: Vec<(u128, u128)> = (1..=5).map(|x| (x, poly_eval(&f, x, P))).collect();
+    for subset in [[0usize, 1, 2], [0, 2, 4], [4, 3, 1]].iter() {
+        let xs: Vec<u128> = subset.iter().map(|&i| shares[i].0).collect();
+        let recovered = subset.iter().enumerate().fold(0, |acc, (k, &i)| {
+            let lambda = lagrange_coefficient(&xs, k, P).unwrap();
+            acc.add_mod(shares[i].1.mul_mod(lambda, P), P)
+        });
+        assert_eq!(recovered, secret);
+    }
+
+    // The basis polynomials are 1 at their own point and 0 at the others.
+    let xs = [1u128, 3, 4];
+    for i in 0..3 {
+        for (j, &x) in xs.iter().enumerate() {
+            let expected = if i == j { 1 } else { 0 };
+            assert_eq!(lagrange_basis_eval(&xs, i, x, P).unwrap(), expected);
+        }
+    }
+}
+
+#[test]
+fn test_multipoint_eval() {
+    let f = random_poly::<u128>(100, 0, 3329);
+    let xs: Vec<u128> = (0..37).map(|i| i * 89 + 5).collect();
+    let expected: Vec<u128> = xs.iter().map(|&x| poly_eval(f.raw(), x, 3329)).collect();
+    assert_eq!(poly_eval_multi(f.raw(), &xs, 3329), expected);
+
+    let tree = SubproductTree::new(&xs, 3329);
+    assert_eq!(tree.product().len(), 38);
+    assert!(tree.evaluate(tree.product()).iter().all(|&y| y == 0));
+
+    // Over ℤ with signed coefficients
+    let f = random_poly::<i128>(20, -100, 100);
+    let xs: Vec<i128> = (-5..6).collect();
+    let expected: Vec<i128> = xs.iter().map(|&x| poly_eval(f.raw(), x, 0)).collect();
+    assert_eq!(poly_eval_multi(f.raw(), &xs, 0), expected);
+}
+
+#[test]
+fn test_reed_solomon() {
+    // Encode 8 symbols at 16 points and decode from any 8 of them.
+    let p = 257u128;
+    let message = random_poly::<u128>(8, 0, 257);
+    let xs: Vec<u128> = (1..=16).collect();
+    let codeword = poly_eval_multi(message.raw(), &xs, p);
+    let ys: Vec<u128> = codeword.iter().copied().skip(5).take(8).collect();
+    let decoded = lagrange_interpolate(&xs[5..13], &ys, p).unwrap();
+    assert_eq!(decoded, message.raw().to_vec());
+}
@@ -0,0 +1,136 @@
+use hacspec::prelude::*;
+
+unsigned_integer!(X25519Canvas, 256);
+field_integer!(
+    X25519FieldElement,
+    X25519Canvas,
+    X25519Canvas::from_bytes_be(&hex_string_to_bytes(
+        "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed"
+    ))
+);
+
+struct X25519;
+impl MontgomeryCurve for X25519 {
+    type Field = X25519FieldElement;
+    const BITS: usize = 255;
+    const COFACTOR_BITS: usize = 3;
+    fn a24() -> X25519FieldElement {
+        X25519FieldElement::from_literal(121665)
+    }
+    fn base_point() -> X25519FieldElement {
+        X25519FieldElement::from_literal(9)
+    }
+}
+
+unsigned_integer!(X448Canvas, 448);
+field_integer!(
+    X448FieldElement,
+    X448Canvas,
+    X448Canvas::from_bytes_be(&hex_string_to_bytes(
+        "fffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffffffffffffffffffffffffffffffffffffffffffffffffffff"
+    ))
+);
+
+struct X448;
+impl MontgomeryCurve for X448 {
+    type Field = X448FieldElement;
+    const BITS: usize = 448;
+    const COFACTOR_BITS: usize = 2;
+    fn a24() -> X448FieldElement {
+        X448FieldElement::from_literal(39081)
+    }
+    fn base_point() -> X448FieldElement {
+        X448FieldElement::from_literal(5)
+    }
+}
+
+create_test_vectors!(
+    MontgomeryTestVectors,
+    info: String,
+    tests: Vec<ScalarMultVector>,
+    iterated: Vec<IteratedVector>
+);
+create_test_vectors!(ScalarMultVector, scalar: String, u: String, output: String);
+create_test_vectors!(IteratedVector, iterations: usize, output: String);
+
+fn check_vectors<C: MontgomeryCurve>(file: &'static str, max_iterations: usize) {
+    let vectors = MontgomeryTestVectors::new(file);
+    for test in vectors.tests.iter() {
+        let out = C::scalarmult(
+            &ByteSeq::from(test.scalar.as_str()),
+            &ByteSeq::from(test.u.as_str()),
+        );
+        assert_eq!(out.to_hex(), test.output);
+    }
+
+    // k = u = base point, then (k, u) = (scalarmult(k, u), k) in each
+    // iteration.
+    let mut k = C::encode_u_coordinate(C::base_point());
+    let mut u = k.clone();
+    let mut done = 0;
+    for test in vectors.iterated.iter().filter(|t| t.iterations <= max_iterations) {
+        while done < test.iterations {
+            let next = C::scalarmult(&k, &u);
+            u = k;
+            k = next;
+            done += 1;
+        }
+        assert_eq!(k.to_hex(), test.output);
+    }
+}
+
+#[test]
+fn test_x25519_vectors() {
+    check_vectors::<X25519>("tests/x25519_test_vectors.json", 1);
+}
+
+#[test]
+fn test_x448_vectors() {
+    check_vectors::<X448>("tests/x448_test_vectors.json", 1);
+}
+
+// The 1000 iterations take several minutes in debug builds.
+// Run with `cargo test --release -- --ignored`.
+#[test]
+#[ignore]
+fn test_iterated_1000() {
+    check_vectors::<X25519>("tests/x25519_test_vectors.json", 1000);
+    check_vectors::<X448>("tests/x448_test_vectors.json", 1000);
+}
+
+#[test]
+fn test_x25519_dh() {
+    // RFC 7748, Section 6.1
+    let sk_a = ByteSeq::from("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+    let sk_b = ByteSeq::from("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb");
+    let pk_a = X25519::scalarmult_base(&sk_a);
+    let pk_b = X25519::scalarmult_base(&sk_b);
+    assert_eq!(pk_a.to_hex(), "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a");
+    assert_eq!(pk_b.to_hex(), "de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f");
+    let k = X25519::scalarmult(&sk_a, &pk_b);
+    assert_eq!(k.to_hex(), X25519::scalarmult(&sk_b, &pk_a).to_hex());
+    assert_eq!(k.to_hex(), "4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+}
+
+#[test]
+fn test_clamp_and_decode() {
+    let ones = ByteSeq::from_array(&[U8::classify(0xffu8); 32]);
+    let clamped = X25519::clamp(&ones);
+    assert_eq!(U8::declassify(clamped[0]), 0xf8);
+    assert_eq!(U8::declassify(clamped[31]), 0x7f);
+    let zeros = ByteSeq::new(56);
+    let clamped = X448::clamp(&zeros);
+    assert_eq!(U8::declassify(clamped[0]), 0);
+    assert_eq!(U8::declassify(clamped[55]), 0x80);
+
+    // The top bit of X25519 u-coordinates is masked and non-canonical values
+    // are reduced.
+    let nine = ByteSeq::from("0900000000000000000000000000000000000000000000000000000000000080");
+    assert_eq!(X25519::decode_u_coordinate(&nine), X25519FieldElement::from_literal(9));
+    let p_plus_one = ByteSeq::from("eeffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f");
+    assert_eq!(X25519::decode_u_coordinate(&p_plus_one), X25519FieldElement::from_literal(1));
+    assert_eq!(
+        X25519::encode_u_coordinate(X25519FieldElement::from_literal(1)).to_hex(),
+        "0100000000000000000000000000000000000000000000000000000000000000"
+    );
+}
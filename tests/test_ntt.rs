@@ -0,0 +1,80 @@
+use hacspec::prelude::*;
+
+montgomery_integer!(DilithiumMont, 8380417);
+
+#[test]
+fn test_bit_reverse() {
+    assert_eq!(bit_reverse(1, 7), 64);
+    assert_eq!(bit_reverse(0b0010110, 7), 0b0110100);
+    assert_eq!(bit_reverse(5, 0), 0);
+    let a: Vec<usize> = (0..8).collect();
+    assert_eq!(bit_reverse_permutation(&a), vec![0, 4, 2, 6, 1, 5, 3, 7]);
+    assert_eq!(bit_reverse_permutation(&bit_reverse_permutation(&a)), a);
+}
+
+#[test]
+fn test_kyber_ntt() {
+    let ctx = NttCtx::new_incomplete(256, 3329u128, 17, 7).unwrap();
+    // FIPS 203, Appendix A
+    assert_eq!(&ctx.zetas()[..6], &[1, 1729, 2580, 3289, 2642, 630]);
+    assert_eq!(ctx.zetas()[127], 2154);
+    assert_eq!(&ctx.gammas()[..4], &[17, 3312, 2761, 568]);
+
+    // 1 is 1 modulo every factor x^2 - γ.
+    let mut one = vec![0u128; 256];
+    one[0] = 1;
+    let one_hat = ctx.ntt(&one);
+    assert!(one_hat.chunks(2).all(|c| c == [1, 0]));
+    assert_eq!(ctx.inv_ntt(&one_hat), one);
+
+    for _ in 0..5 {
+        let a = random_poly::<u128>(256, 0, 3329);
+        let b = random_poly::<u128>(256, 0, 3329);
+        let (a_hat, b_hat) = (ctx.ntt(a.raw()), ctx.ntt(b.raw()));
+        assert_eq!(ctx.inv_ntt(&a_hat), a.raw().to_vec());
+        let c = ctx.inv_ntt(&ctx.mul_ntt(&a_hat, &b_hat));
+        assert_eq!(c, ctx.negacyclic_mul(a.raw(), b.raw()));
+    }
+}
+
+#[test]
+fn test_dilithium_ntt() {
+    let q = DilithiumMont::modulus();
+    let ctx = NttCtx::new(256, q, DilithiumMont::from_literal(1753)).unwrap();
+    assert_eq!(ctx.layers(), 8);
+    // FIPS 204, Appendix B
+    let zetas: Vec<u32> = ctx.zetas()[1..4].iter().map(|z| z.to_canonical()).collect();
+    assert_eq!(zetas, vec![4808194, 3765607, 3761513]);
+
+    let mut rng = rand::thread_rng();
+    let a: Vec<DilithiumMont> = (0..256).map(|_| rng.gen_range(DilithiumMont::from_literal(0), q)).collect();
+    let b: Vec<DilithiumMont> = (0..256).map(|_| rng.gen_range(DilithiumMont::from_literal(0), q)).collect();
+    let c = ctx.inv_ntt(&ctx.mul_ntt(&ctx.ntt(&a), &ctx.ntt(&b)));
+    assert_eq!(c, ctx.negacyclic_mul(&a, &b));
+}
+
+#[test]
+fn test_complete_ntt_evaluates() {
+    // ψ = 3 is a primitive 16-th root of unity mod 17.
+    let ctx = NttCtx::new(8, 17i128, 3).unwrap();
+    let a: Vec<i128> = vec![1, 2, 3, 4, 5, 6, 7, 8];
+    // In natural order the NTT evaluates at ψ^(2i + 1).
+    let evaluations = bit_reverse_permutation(&ctx.ntt(&a));
+    for (i, &e) in evaluations.iter().enumerate() {
+        let x = i128::pow_mod(3, 2 * i as i128 + 1, 17);
+        let expected = a.iter().rev().fold(0, |acc, &c| acc.mul_mod(x, 17).add_mod(c, 17));
+        assert_eq!(e, expected);
+    }
+    let b: Vec<i128> = vec![-1, 0, 5, 0, 0, 0, 0, 16];
+    let c = ctx.inv_ntt(&ctx.mul_ntt(&ctx.ntt(&a), &ctx.ntt(&b)));
+    assert_eq!(c, ctx.negacyclic_mul(&a, &b));
+}
+
+#[test]
+fn test_ntt_errors() {
+    assert_eq!(NttCtx::new(256, 3329u128, 17), Err(NttError::InvalidRoot));
+    assert_eq!(NttCtx::new(255, 3329u128, 17), Err(NttError::InvalidLength));
+    assert_eq!(NttCtx::new_incomplete(256, 3329u128, 17, 9), Err(NttError::InvalidLength));
+    // 17^2 has order 128, not 256.
+    assert_eq!(NttCtx::new_incomplete(256, 3329u128, 289, 7), Err(NttError::InvalidRoot));
+}
@@ -0,0 +1,201 @@
+use hacspec::prelude::*;
+
+fn fp(s: &str) -> Bls12_381Fp {
+    Bls12_381Fp::from_hex(s)
+}
+
+fn fp2(c0: &str, c1: &str) -> Bls12_381Fp2 {
+    Bls12_381Fp2::new(fp(c0), fp(c1))
+}
+
+fn some_fp6() -> Bls12_381Fp6 {
+    Bls12_381Fp6::new(
+        fp2("0123456789abcdef", "fedcba9876543210"),
+        fp2("1a2b3c4d5e6f", "0f0e0d0c0b0a"),
+        fp2("deadbeef", "c0ffee"),
+    )
+}
+
+fn some_fp12() -> Bls12_381Fp12 {
+    Bls12_381Fp12::new(some_fp6(), some_fp6() * some_fp6() + Bls12_381Fp6::one())
+}
+
+#[test]
+fn test_extension_field_arithmetic() {
+    let a = fp2("0123456789abcdef", "fedcba9876543210");
+    let b = fp2("1a2b3c4d5e6f", "0f0e0d0c0b0a");
+    // u² = -1
+    let u = fp2("00", "01");
+    assert_eq!(u * u, Bls12_381Fp2::zero() - Bls12_381Fp2::one());
+    assert_eq!(a * b, b * a);
+    assert_eq!(a * (b + u), a * b + a * u);
+    assert_eq!(a * a.inv(), Bls12_381Fp2::one());
+    assert_eq!(Bls12_381Fp2::zero().inv(), Bls12_381Fp2::zero());
+
+    // v³ = u + 1
+    let zero = Bls12_381Fp2::zero();
+    let v = Bls12_381Fp6::new(zero, Bls12_381Fp2::one(), zero);
+    assert_eq!(v * v * v, Bls12_381Fp6::from_base(Bls12_381Fp6::non_residue()));
+    let c = some_fp6();
+    assert_eq!(c * c.inv(), Bls12_381Fp6::one());
+    assert_eq!(c.norm(), (c * c.frobenius(2) * c.frobenius(4)).c0);
+
+    // w² = v
+    let w = Bls12_381Fp12::new(Bls12_381Fp6::zero(), Bls12_381Fp6::one());
+    assert_eq!(w * w, Bls12_381Fp12::from_base(v));
+    let d = some_fp12();
+    assert_eq!(d * d.inv(), Bls12_381Fp12::one());
+    assert_eq!(Bls12_381Fp12::degree(), 12);
+}
+
+#[test]
+fn test_frobenius() {
+    let p = Bls12_381Fp::modulus_biguint();
+    let a = fp2("0123456789abcdef", "fedcba9876543210");
+    assert_eq!(a.frobenius(1), a.pow_biguint(&p));
+    assert_eq!(a.frobenius(1), a.conjugate());
+    assert_eq!(a.frobenius(2), a);
+
+    let c = some_fp6();
+    assert_eq!(c.frobenius(1), c.pow_biguint(&p));
+    assert_eq!(c.frobenius(6), c);
+    assert_eq!(c.frobenius(2).frobenius(3), c.frobenius(5));
+
+    let d = some_fp12();
+    assert_eq!(d.frobenius(1), d.pow_biguint(&p));
+    assert_eq!(d.frobenius(12), d);
+    assert_eq!(d.frobenius(6), d.conjugate());
+    assert_eq!((d * d.inv().frobenius(1)).frobenius(3), d.frobenius(3) * d.frobenius(4).inv());
+}
+
+#[test]
+fn test_extension_field_sqrt() {
+    let a = fp2("0123456789abcdef", "fedcba9876543210");
+    let s = (a * a).sqrt().unwrap();
+    assert!(s == a || s == Bls12_381Fp2::zero() - a);
+    assert!(Bls12_381Fp2::from_base(fp("02")).is_square());
+    // u + 1 is not a square since its norm 2 is not a square in Fp.
+    let xi = Bls12_381Fp6::non_residue();
+    assert!(!xi.is_square());
+    assert_eq!(xi.sqrt(), None);
+    assert!(!(a * a * xi).is_square());
+
+    assert!(!Bls12_381Fp2::zero().sgn0());
+    assert!(fp2("00", "01").sgn0());
+    assert!(!fp2("02", "01").sgn0());
+}
+
+#[test]
+fn test_groups() {
+    let g1 = Bls12_381G1::generator();
+    let g2 = Bls12_381G2::generator();
+    assert!(Bls12_381G1::is_on_curve(g1));
+    assert!(Bls12_381G2::is_on_curve(g2));
+    // (r - 1) G = -G
+    let r_minus_one = Bls12_381Scalar::from_literal(0) - Bls12_381Scalar::from_literal(1);
+    assert_eq!(Bls12_381G1::scalar_mul(r_minus_one, g1), Bls12_381G1::neg(g1));
+    assert_eq!(Bls12_381G2::scalar_mul(r_minus_one, g2), Bls12_381G2::neg(g2));
+}
+
+#[test]
+fn test_bilinearity() {
+    let p = Bls12_381G1::generator();
+    let q = Bls12_381G2::generator();
+    let a = Bls12_381Scalar::from_hex("0123456789abcdef");
+    let b = Bls12_381Scalar::from_literal(5);
+
+    let e = Bls12_381::pairing(p, q);
+    assert!(e != Bls12_381Fp12::one());
+    // e has order r.
+    let r = Bls12_381Scalar::modulus_biguint();
+    assert_eq!(e.pow_biguint(&r), Bls12_381Fp12::one());
+
+    let ap = Bls12_381G1::scalar_mul(a, p);
+    let bq = Bls12_381G2::scalar_mul(b, q);
+    let ab = (a * b).to_biguint();
+    assert_eq!(Bls12_381::pairing(ap, bq), e.pow_biguint(&ab));
+    assert_eq!(
+        Bls12_381::pairing(Bls12_381G1::add(p, ap), q),
+        e * Bls12_381::pairing(ap, q)
+    );
+}
+
+#[test]
+fn test_final_exponentiation() {
+    let f = some_fp12();
+    let easy = Bls12_381::final_exponentiation_easy(f);
+    // The easy part maps into the cyclotomic subgroup where the inverse is
+    // the conjugate.
+    assert_eq!(easy * easy.conjugate(), Bls12_381Fp12::one());
+    // The addition chain agrees with the generic exponentiation.
+    let p = Bls12_381Fp::modulus_biguint();
+    let r = Bls12_381Scalar::modulus_biguint();
+    let p2 = &p * &p;
+    let hard = (&p2 * &p2 - &p2 + 1u32) / r;
+    assert_eq!(Bls12_381::final_exponentiation_hard(easy), easy.pow_biguint(&hard));
+}
+
+#[test]
+fn test_pairing_infinity() {
+    let p = Bls12_381G1::generator();
+    let q = Bls12_381G2::generator();
+    assert_eq!(Bls12_381::pairing(Affine::Infinity, q), Bls12_381Fp12::one());
+    assert_eq!(Bls12_381::pairing(p, Affine::Infinity), Bls12_381Fp12::one());
+}
+
+#[test]
+fn test_bn254_extension_fields() {
+    let p = Bn254Fp::modulus_biguint();
+    let zero = Bn254Fp2::zero();
+    // v³ = u + 9
+    let v = Bn254Fp6::new(zero, Bn254Fp2::one(), zero);
+    assert_eq!(v * v * v, Bn254Fp6::from_base(Bn254Fp6::non_residue()));
+    let c = Bn254Fp6::new(
+        Bn254Fp2::new(Bn254Fp::from_hex("0123456789abcdef"), Bn254Fp::from_hex("fedcba9876543210")),
+        Bn254Fp2::new(Bn254Fp::from_hex("1a2b3c4d5e6f"), Bn254Fp::from_hex("0f0e0d0c0b0a")),
+        Bn254Fp2::new(Bn254Fp::from_hex("deadbeef"), Bn254Fp::from_hex("c0ffee")),
+    );
+    let d = Bn254Fp12::new(c, c * c + Bn254Fp6::one());
+    assert_eq!(d * d.inv(), Bn254Fp12::one());
+    assert_eq!(d.frobenius(1), d.pow_biguint(&p));
+    assert_eq!(d.frobenius(6), d.conjugate());
+
+    let easy = Bn254::final_exponentiation_easy(d);
+    assert_eq!(easy * easy.conjugate(), Bn254Fp12::one());
+    let r = Bn254Scalar::modulus_biguint();
+    let p2 = &p * &p;
+    let hard = (&p2 * &p2 - &p2 + 1u32) / r;
+    assert_eq!(Bn254::final_exponentiation_hard(easy), easy.pow_biguint(&hard));
+}
+
+#[test]
+fn test_bn254_groups() {
+    let g1 = Bn254G1::generator();
+    let g2 = Bn254G2::generator();
+    assert!(Bn254G1::is_on_curve(g1));
+    assert!(Bn254G2::is_on_curve(g2));
+    let r_minus_one = Bn254Scalar::from_literal(0) - Bn254Scalar::from_literal(1);
+    assert_eq!(Bn254G1::scalar_mul(r_minus_one, g1), Bn254G1::neg(g1));
+    assert_eq!(Bn254G2::scalar_mul(r_minus_one, g2), Bn254G2::neg(g2));
+}
+
+#[test]
+fn test_bn254_bilinearity() {
+    let p = Bn254G1::generator();
+    let q = Bn254G2::generator();
+    let a = Bn254Scalar::from_hex("0123456789abcdef");
+    let b = Bn254Scalar::from_literal(5);
+
+    let e = Bn254::pairing(p, q);
+    assert!(e != Bn254Fp12::one());
+    let r = Bn254Scalar::modulus_biguint();
+    assert_eq!(e.pow_biguint(&r), Bn254Fp12::one());
+
+    let ap = Bn254G1::scalar_mul(a, p);
+    let bq = Bn254G2::scalar_mul(b, q);
+    let ab = (a * b).to_biguint();
+    assert_eq!(Bn254::pairing(ap, bq), e.pow_biguint(&ab));
+    assert_eq!(Bn254::pairing(Bn254G1::add(p, ap), q), e * Bn254::pairing(ap, q));
+    assert_eq!(Bn254::pairing(Bn254G1::neg(p), q), e.inv());
+    assert_eq!(Bn254::pairing(Affine::Infinity, q), Bn254Fp12::one());
+}
@@ -13,6 +13,51 @@ fn test_zn_inv() {
     assert_eq!(i128::inv(-123, n), 6901);
 }
 
+#[test]
+fn test_zn_inv_signed() {
+    let n = 127i8;
+    assert_eq!(i8::inv(23, n), 116);
+    assert_eq!(i8::inv(-45, n), 79);
+    assert_eq!(i8::inv(100, n), 47);
+
+    let n = 32749i16;
+    assert_eq!(i16::inv(1234, n), 13986);
+    assert_eq!(i16::inv(-999, n), 15473);
+
+    let n = 2147483647i32;
+    assert_eq!(i32::inv(123456789, n), 391219981);
+    assert_eq!(i32::inv(-42, n), 51130563);
+
+    let n = 9223372036854775783i64;
+    assert_eq!(i64::inv(987654321987, n), 656776943347493733);
+    assert_eq!(i64::inv(-7, n), 2635249153387078795);
+
+    let n = 2147483647isize;
+    assert_eq!(isize::inv(123456789, n), 391219981);
+    assert_eq!(isize::inv(-42, n), 51130563);
+
+    // |MIN| isn't representable.
+    assert_eq!(i8::inv(3, i8::MIN), 43);
+    assert_eq!(i8::inv(-5, i8::MIN), 51);
+    assert_eq!(i16::inv(3, i16::MIN), 10923);
+}
+
+#[test]
+fn test_zn_signed_narrow() {
+    // These would overflow without lifting to i128.
+    assert_eq!(100i8.add_mod(100, 127), 73);
+    assert_eq!(100i8.sub_mod(-100, 127), 73);
+    assert_eq!((-100i8).sub_mod(100, 127), 54);
+    assert_eq!(100i8.mul_mod(-100, 127), 33);
+    assert_eq!(30000i16.mul_mod(30000, 32749), 24731);
+
+    poly!(ZsN16, i16, 3, 11, &[(0, 2), (1, 2), (3, 1)]);
+    let x = ZsN16::new(&[(0, -3), (1, 5), (2, -1)]);
+    let y = ZsN16::new(&[(0, 1), (1, -2), (2, -7)]);
+    let expected = ZsN16::new(&[(0, 8), (1, 8), (2, 7)]);
+    assert_eq!(x * y, expected);
+}
+
 #[test]
 fn test_poly_add() {
     // Polynomials without irreducible and without coefficient modulus.
@@ -170,3 +215,49 @@ fn test_fixed_length() {
     println!("{:x?} * {:x?} = {:x?}", a, b, c);
     assert_eq!(c, expected_c);
 }
+
+#[test]
+fn test_poly_crt() {
+    let moduli = [3329u128, 7681, 12289];
+    let a = random_poly::<u128>(64, 0, 100);
+    let b = random_poly::<u128>(64, 0, 100);
+    let expected = poly_mul(a.raw(), b.raw(), 0);
+
+    let residues: Vec<Vec<u128>> = moduli.iter().map(|&q| poly_mul(a.raw(), b.raw(), q)).collect();
+    let c = poly_crt(&residues, &moduli).unwrap();
+    assert_eq!(c, expected);
+
+    assert_eq!(poly_crt(&residues, &[3329, 7681, 3329 * 5]), Err(CrtError::NotCoprime(0, 2)));
+}
+
+#[test]
+fn test_ring_kind() {
+    assert_eq!(ring_kind(&[1u128, 0, 0, 1], 3329), RingKind::Negacyclic);
+    assert_eq!(ring_kind(&[3328u128, 0, 0, 1], 3329), RingKind::Cyclic);
+    assert_eq!(ring_kind(&[-1i128, 0, 0, 1], 0), RingKind::Cyclic);
+    assert_eq!(ring_kind(&[1u128, 1, 0, 1], 3329), RingKind::General);
+    assert_eq!(ring_kind(&[1u128, 0, 0, 2], 3329), RingKind::General);
+    assert_eq!(ring_kind(&[2u128, 0, 0, 1], 3329), RingKind::General);
+}
+
+// NTRU-HPS: Rq = ℤ(2^11)[x]/(x^509 - 1)
+poly!(RqNtru, u128, 509, 2048, &[(0, 2047), (509, 1)]);
+// Rq = ℤ[x]/(x^64 + 1)
+poly!(RZNegacyclic, i128, 64, 0, &[(0, 1), (64, 1)]);
+
+#[test]
+fn test_cyclic_fast_path() {
+    let a = RqNtru::random();
+    let b = RqNtru::random();
+    let c = a * b;
+    let irr = c.irr.to_vec();
+    let expected = euclid_div(&poly_mul(&a.poly, &b.poly, 2048), &irr, 2048).1;
+    assert_eq!(c, RqNtru::from(expected));
+
+    let a = RZNegacyclic::from(random_poly::<i128>(64, -100, 100).raw().to_vec());
+    let b = RZNegacyclic::from(random_poly::<i128>(64, -100, 100).raw().to_vec());
+    let c = a * b;
+    let irr = c.irr.to_vec();
+    let expected = euclid_div(&poly_mul(&a.poly, &b.poly, 0), &irr, 0).1;
+    assert_eq!(c, RZNegacyclic::from(expected));
+}
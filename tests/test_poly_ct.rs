@@ -0,0 +1,257 @@
+use hacspec::prelude::*;
+use std::cell::Cell;
+
+thread_local! {
+    static OPERATIONS: Cell<usize> = const { Cell::new(0) };
+    static BRANCHING: Cell<usize> = const { Cell::new(0) };
+}
+
+fn count() {
+    OPERATIONS.with(|c| c.set(c.get() + 1));
+}
+
+/// Count an operation that branches on the coefficients, i.e. a comparison
+/// or a modular operation that isn't constant-time.
+fn count_branching() {
+    BRANCHING.with(|c| c.set(c.get() + 1));
+}
+
+/// Count the operations executed by `f`.
+fn operations<F: FnOnce()>(f: F) -> usize {
+    OPERATIONS.with(|c| c.set(0));
+    f();
+    OPERATIONS.with(|c| c.get())
+}
+
+/// Count the branching operations executed by `f`.
+fn branching<F: FnOnce()>(f: F) -> usize {
+    BRANCHING.with(|c| c.set(0));
+    f();
+    BRANCHING.with(|c| c.get())
+}
+
+/// A `u128` coefficient that counts the modular operations and comparisons.
+#[derive(Clone, Copy, Default, Debug)]
+struct Counted(u128);
+
+impl PartialEq for Counted {
+    fn eq(&self, other: &Counted) -> bool {
+        count_branching();
+        self.0 == other.0
+    }
+}
+
+impl PartialOrd for Counted {
+    fn partial_cmp(&self, other: &Counted) -> Option<std::cmp::Ordering> {
+        count_branching();
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl Add for Counted {
+    type Output = Counted;
+    fn add(self, rhs: Counted) -> Counted {
+        Counted(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Counted {
+    type Output = Counted;
+    fn sub(self, rhs: Counted) -> Counted {
+        Counted(self.0 - rhs.0)
+    }
+}
+
+impl Mul for Counted {
+    type Output = Counted;
+    fn mul(self, rhs: Counted) -> Counted {
+        Counted(self.0 * rhs.0)
+    }
+}
+
+impl Div for Counted {
+    type Output = Counted;
+    fn div(self, rhs: Counted) -> Counted {
+        Counted(self.0 / rhs.0)
+    }
+}
+
+impl Integer<Counted> for Counted {
+    fn from_literal(x: u128) -> Counted {
+        Counted(x)
+    }
+    fn from_signed_literal(x: i128) -> Counted {
+        Counted(x as u128)
+    }
+    fn inv(x: Counted, n: Counted) -> Counted {
+        Counted(u128::inv(x.0, n.0))
+    }
+    fn max() -> Counted {
+        Counted(u128::MAX)
+    }
+    fn sub_lift(self, rhs: Counted, n: Counted) -> Counted {
+        self.sub_mod(rhs, n)
+    }
+    fn sub_mod(self, rhs: Counted, n: Counted) -> Counted {
+        count();
+        count_branching();
+        Counted(self.0.sub_mod(rhs.0, n.0))
+    }
+    fn add_mod(self, rhs: Counted, n: Counted) -> Counted {
+        count();
+        count_branching();
+        Counted(self.0.add_mod(rhs.0, n.0))
+    }
+    fn mul_mod(self, rhs: Counted, n: Counted) -> Counted {
+        count();
+        count_branching();
+        Counted(self.0.mul_mod(rhs.0, n.0))
+    }
+    fn sub_mod_ct(self, rhs: Counted, n: Counted) -> Counted {
+        count();
+        Counted(self.0.sub_mod_ct(rhs.0, n.0))
+    }
+    fn add_mod_ct(self, rhs: Counted, n: Counted) -> Counted {
+        count();
+        Counted(self.0.add_mod_ct(rhs.0, n.0))
+    }
+    fn mul_mod_ct(self, rhs: Counted, n: Counted) -> Counted {
+        count();
+        Counted(self.0.mul_mod_ct(rhs.0, n.0))
+    }
+    fn rem(self, n: Counted) -> Counted {
+        Counted(self.0 % n.0)
+    }
+    fn abs(self) -> Counted {
+        self
+    }
+}
+
+fn counted(v: &[u128]) -> Vec<Counted> {
+    v.iter().map(|&x| Counted(x)).collect()
+}
+
+#[test]
+fn test_poly_mul_ct_operation_count() {
+    let q = Counted(3329);
+    let zero = vec![Counted(0); 64];
+    let mut sparse = zero.clone();
+    sparse[3] = Counted(1);
+    let dense = counted(random_poly::<u128>(64, 1, 3329).raw());
+
+    let ct: Vec<usize> = [&zero, &sparse, &dense]
+        .iter()
+        .map(|a| operations(|| drop(poly_mul_ct(a, &dense, q))))
+        .collect();
+    assert_eq!(ct, vec![2 * 64 * 64; 3]);
+    for a in [&zero, &sparse, &dense].iter() {
+        assert_eq!(branching(|| drop(poly_mul_ct(a, &dense, q))), 0);
+    }
+
+    // The sparse multiplication leaks the number of non-zero coefficients.
+    let sparse_ops = operations(|| drop(poly_mul(&sparse, &dense, q)));
+    let dense_ops = operations(|| drop(poly_mul(&dense, &dense, q)));
+    assert!(sparse_ops < dense_ops);
+    assert!(branching(|| drop(poly_mul(&dense, &dense, q))) > 0);
+}
+
+#[test]
+fn test_reduce_ct_operation_count() {
+    let q = Counted(3329);
+    let zero = vec![Counted(0); 128];
+    let dense = counted(random_poly::<u128>(128, 0, 3329).raw());
+    for a in [&zero, &dense].iter() {
+        assert_eq!(operations(|| drop(cyclic_reduce(a, 64, q))), 128);
+        assert_eq!(operations(|| drop(negacyclic_reduce(a, 64, q))), 128);
+        assert_eq!(branching(|| drop(cyclic_reduce(a, 64, q))), 0);
+        assert_eq!(branching(|| drop(negacyclic_reduce(a, 64, q))), 0);
+    }
+}
+
+#[test]
+fn test_pow_mod_ct_operation_count() {
+    let q = Counted(8380417);
+    let x = Counted(1753);
+    let ladder = |e: u128| {
+        let _ = Counted::pow_mod_ct(x, Counted(e), q);
+    };
+    let exponents = [0, 1, 0x1234, 8380416, u128::MAX];
+    for &e in exponents.iter() {
+        // Two swaps of four operations and two multiplications per bit.
+        assert_eq!(operations(|| ladder(e)), 1 + 10 * 128);
+        // Only reducing the base and checking n in debug builds branch.
+        assert_eq!(branching(|| ladder(e)), 1 + cfg!(debug_assertions) as usize);
+        assert_eq!(Counted::pow_mod_ct(x, Counted(e), q).0, u128::pow_mod(1753, e, 8380417));
+    }
+
+    // Square-and-multiply leaks the Hamming weight of the exponent.
+    let square_and_multiply = |e: u128| {
+        let _ = Counted::pow_mod(x, Counted(e), q);
+    };
+    assert!(operations(|| square_and_multiply(0x1000)) < operations(|| square_and_multiply(0x1fff)));
+}
+
+#[test]
+fn test_poly_mul_ct() {
+    let a = random_poly::<u128>(256, 0, 3329);
+    let b = random_poly::<u128>(256, 0, 3329);
+    let c = poly_mul_ct(a.raw(), b.raw(), 3329);
+    assert_eq!(c, poly_mul(a.raw(), b.raw(), 3329));
+
+    // x^255 · x = x^256 = ∓1
+    let mut x = vec![0u128; 256];
+    x[1] = 1;
+    let mut x255 = vec![0u128; 256];
+    x255[255] = 1;
+    let mut one = vec![0u128; 256];
+    one[0] = 1;
+    let product = poly_mul_ct(&x255, &x, 3329);
+    assert_eq!(cyclic_reduce(&product, 256, 3329), one);
+    one[0] = 3328;
+    assert_eq!(negacyclic_reduce(&product, 256, 3329), one);
+}
+
+fn check_ct_operations<T: TRestrictions<T>>(n: T) {
+    let one = T::from_literal(1);
+    let values = [T::default(), one, n / T::from_literal(2), n - one - one, n - one];
+    for &a in values.iter() {
+        for &b in values.iter() {
+            assert_eq!(a.add_mod_ct(b, n), a.add_mod(b, n), "{:?} + {:?}", a, b);
+            assert_eq!(a.sub_mod_ct(b, n), a.sub_mod(b, n), "{:?} - {:?}", a, b);
+            assert_eq!(a.mul_mod_ct(b, n), a.mul_mod(b, n), "{:?} * {:?}", a, b);
+        }
+    }
+}
+
+montgomery_integer!(KyberMont, 3329);
+barrett_integer!(FalconBarrett, 12289);
+
+#[test]
+fn test_ct_operations() {
+    check_ct_operations(251u8);
+    check_ct_operations(u16::MAX);
+    check_ct_operations(8380417u32);
+    check_ct_operations(u64::MAX - 58);
+    check_ct_operations(usize::MAX);
+    // a + b and the 256-bit products overflow.
+    check_ct_operations(u128::MAX - 158);
+    check_ct_operations(127i8);
+    check_ct_operations(3329i16);
+    check_ct_operations(i32::MAX);
+    check_ct_operations(i64::MAX);
+    check_ct_operations(isize::MAX);
+    check_ct_operations(i128::MAX);
+    check_ct_operations(KyberMont::modulus());
+    check_ct_operations(FalconBarrett::modulus());
+
+    // The modulus element is 0.
+    let q = KyberMont::modulus();
+    let x = KyberMont::from_literal(5);
+    assert_eq!(q + x, x);
+    assert_eq!(x - q, x);
+    assert_eq!(q - KyberMont::from_literal(0), KyberMont::from_literal(0));
+
+    let a = random_poly::<u32>(256, 0, 8380417);
+    let b = random_poly::<u32>(256, 0, 8380417);
+    assert_eq!(poly_mul_ct(a.raw(), b.raw(), 8380417), poly_mul(a.raw(), b.raw(), 8380417));
+}
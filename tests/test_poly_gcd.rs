@@ -0,0 +1,112 @@
+use hacspec::prelude::*;
+
+fn trim(v: Vec<u128>) -> Vec<u128> {
+    let (d, _) = leading_coefficient(&v);
+    v[..=d].to_vec()
+}
+
+#[test]
+fn test_poly_xgcd() {
+    // gcd((x - 1)(x - 2), (x - 1)(x - 3)) = x - 1 over ℤ7
+    let x = poly_mul(&[6u128, 1], &[5, 1], 7);
+    let y = poly_mul(&[6u128, 1], &[4, 1], 7);
+    let (g, s, t) = poly_xgcd(&x, &y, 7).unwrap();
+    assert_eq!(g, vec![6, 1]);
+    assert_eq!(trim(poly_add(&poly_mul(&s, &x, 7), &poly_mul(&t, &y, 7), 7)), g);
+    assert_eq!(poly_gcd(&y, &x, 7).unwrap(), g);
+
+    // Random polynomials with a common factor over ℤ3329
+    let q = 3329u128;
+    let f = random_poly::<u128>(20, 0, 3329);
+    let h = random_poly::<u128>(5, 1, 3329);
+    let k = random_poly::<u128>(13, 0, 3329);
+    let a = poly_mul(f.raw(), h.raw(), q);
+    let b = poly_mul(k.raw(), h.raw(), q);
+    let (g, s, t) = poly_xgcd(&a, &b, q).unwrap();
+    assert_eq!(leading_coefficient(&g).1, 1);
+    assert_eq!(trim(poly_add(&poly_mul(&s, &a, q), &poly_mul(&t, &b, q), q)), g);
+    let (_, r) = euclid_div(&g, &poly_monic(h.raw(), q).unwrap(), q);
+    assert!(r.iter().all(|&c| c == 0));
+
+    assert_eq!(poly_gcd(&[0u128], &[0], 7).unwrap(), vec![0]);
+    assert_eq!(poly_gcd(&[0u128], &[3, 2], 7).unwrap(), vec![5, 1]);
+    assert_eq!(poly_gcd(&[1u128, 2], &[0, 4], 8), Err(PolyError::NonUnitLeadingCoefficient));
+}
+
+#[test]
+fn test_poly_monic() {
+    assert_eq!(poly_monic(&[1u128, 2, 3, 0], 7).unwrap(), vec![5, 3, 1]);
+    assert_eq!(poly_monic(&[1i128, -1], 0).unwrap(), vec![-1, 1]);
+    assert_eq!(poly_monic(&[1u128, 2], 4), Err(PolyError::NonUnitLeadingCoefficient));
+    assert_eq!(poly_monic(&[0u128], 7), Err(PolyError::NonUnitLeadingCoefficient));
+}
+
+#[test]
+fn test_extended_euclid() {
+    // Inverse in ℤ3329[x]/(x^256 + 1)
+    let mut irr = vec![0u128; 257];
+    irr[0] = 1;
+    irr[256] = 1;
+    let a = random_poly::<u128>(256, 0, 3329);
+    let a_inv = extended_euclid(a.raw(), &irr, 3329).unwrap();
+    let product = negacyclic_reduce(&poly_mul(a.raw(), &a_inv, 3329), 256, 3329);
+    assert_eq!(trim(product), vec![1]);
+
+    // x - 1 divides x^256 - 1.
+    irr[0] = 3328;
+    assert_eq!(extended_euclid(&[3328u128, 1], &irr, 3329), Err(PolyError::NotInvertible));
+    assert_eq!(extended_euclid(&[0u128], &irr, 3329), Err(PolyError::NotInvertible));
+}
+
+#[test]
+fn test_poly_resultant() {
+    // Res(x^2 + 1, x - 1) = (i - 1)(-i - 1) = 2
+    assert_eq!(poly_resultant(&[1u128, 0, 1], &[6, 1], 7).unwrap(), 2);
+    // Res(x - a, x - b) = a - b
+    assert_eq!(poly_resultant(&[98u128, 1], &[96, 1], 101).unwrap(), 99);
+    // Res(x, c) = c and Res of constants
+    assert_eq!(poly_resultant(&[0u128, 1], &[5], 7).unwrap(), 5);
+    assert_eq!(poly_resultant(&[3u128], &[5], 7).unwrap(), 1);
+
+    let p = 7681u128;
+    let f = random_poly::<u128>(7, 1, 7681);
+    let g = random_poly::<u128>(5, 1, 7681);
+    let h = random_poly::<u128>(4, 1, 7681);
+    let (df, dg) = (leading_coefficient(f.raw()).0, leading_coefficient(g.raw()).0);
+    let res_fg = poly_resultant(f.raw(), g.raw(), p).unwrap();
+    let res_gf = poly_resultant(g.raw(), f.raw(), p).unwrap();
+    if df * dg % 2 == 1 {
+        assert_eq!(res_fg, (p - res_gf) % p);
+    } else {
+        assert_eq!(res_fg, res_gf);
+    }
+
+    // Res(f·g, h) = Res(f, h)·Res(g, h)
+    let fg = poly_mul(f.raw(), g.raw(), p);
+    let expected = poly_resultant(f.raw(), h.raw(), p)
+        .unwrap()
+        .mul_mod(poly_resultant(g.raw(), h.raw(), p).unwrap(), p);
+    assert_eq!(poly_resultant(&fg, h.raw(), p).unwrap(), expected);
+
+    // A common factor gives 0.
+    assert_eq!(poly_resultant(&fg, &poly_mul(h.raw(), g.raw(), p), p).unwrap(), 0);
+    assert_eq!(poly_resultant(&[0u128], f.raw(), p).unwrap(), 0);
+}
+
+#[test]
+fn test_poly_discriminant() {
+    // b^2 - 4ac
+    assert_eq!(poly_discriminant(&[7u128, 5, 3], 101).unwrap(), 42);
+    // x^3 + ax + b: -4a^3 - 27b^2
+    assert_eq!(poly_discriminant(&[3u128, 2, 0, 1], 101).unwrap(), 28);
+    // Repeated roots
+    assert_eq!(poly_discriminant(&poly_mul(&[6u128, 1], &[6, 1], 7), 7).unwrap(), 0);
+    assert_eq!(poly_discriminant(&[0u128, 0, 0, 0, 0, 0, 0, 1], 7).unwrap(), 0);
+    // x^256 + 1 is squarefree modulo 3329.
+    let mut f = vec![0u128; 257];
+    f[0] = 1;
+    f[256] = 1;
+    assert_ne!(poly_discriminant(&f, 3329).unwrap(), 0);
+
+    assert_eq!(poly_discriminant(&[5u128], 7), Err(PolyError::ConstantPolynomial));
+}
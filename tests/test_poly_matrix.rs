@@ -0,0 +1,108 @@
+use hacspec::prelude::*;
+use std::sync::OnceLock;
+
+// Rq = ℤ3329[x]/(x^256 + 1)
+poly!(Rq, u128, 256, 3329, &[(0, 1), (256, 1)]);
+poly!(Rq64, u128, 64, 3329, &[(0, 1), (64, 1)]);
+
+fn kyber_ntt() -> &'static NttCtx<u128> {
+    static CTX: OnceLock<NttCtx<u128>> = OnceLock::new();
+    CTX.get_or_init(|| NttCtx::new_incomplete(256, 3329, 17, 7).unwrap())
+}
+
+/// An element of Rq in NTT domain.
+#[derive(Clone, Debug, PartialEq)]
+struct RqHat(Vec<u128>);
+
+impl RqHat {
+    fn ntt(a: &Rq) -> RqHat {
+        RqHat(kyber_ntt().ntt(&a.poly))
+    }
+
+    fn inv_ntt(&self) -> Rq {
+        Rq::from(kyber_ntt().inv_ntt(&self.0))
+    }
+}
+
+impl Add for RqHat {
+    type Output = RqHat;
+    fn add(self, rhs: RqHat) -> RqHat {
+        RqHat(poly_add(&self.0, &rhs.0, 3329))
+    }
+}
+
+impl Sub for RqHat {
+    type Output = RqHat;
+    fn sub(self, rhs: RqHat) -> RqHat {
+        RqHat(poly_sub(&self.0, &rhs.0, 3329))
+    }
+}
+
+impl Mul for RqHat {
+    type Output = RqHat;
+    fn mul(self, rhs: RqHat) -> RqHat {
+        RqHat(kyber_ntt().mul_ntt(&self.0, &rhs.0))
+    }
+}
+
+impl RingElement for RqHat {
+    fn zero() -> RqHat {
+        RqHat(vec![0; 256])
+    }
+}
+
+/// A toy sampler that derives the coefficients from the seed and the
+/// position.
+fn sample(seed: &Seq<U8>, i: usize, j: usize) -> Rq {
+    let s = seed.iter().fold(0u128, |acc, b| acc * 31 + U8::declassify(*b) as u128);
+    Rq::from((0..256).map(|k| (s + (k * 7 + i * 13 + j * 17) as u128) % 3329).collect::<Vec<u128>>())
+}
+
+#[test]
+fn test_matrix_vector_ntt() {
+    // ML-KEM-512
+    let a = PolyMatrix::<Rq, 2, 2>::from_fn(|_, _| Rq::random());
+    let s = PolyVector::<Rq, 2>::from_fn(|_| Rq::random());
+    let e = PolyVector::<Rq, 2>::from_fn(|_| Rq::random());
+    let t = a * s + e;
+
+    // The same in NTT domain as in ML-KEM.
+    let a_hat = a.map(RqHat::ntt);
+    let s_hat = s.map(RqHat::ntt);
+    let t_hat = a_hat * s_hat + e.map(RqHat::ntt);
+    assert_eq!(t_hat.map(RqHat::inv_ntt), t);
+}
+
+#[test]
+fn test_matrix_algebra() {
+    let a = PolyMatrix::<Rq64, 2, 3>::from_fn(|_, _| Rq64::random());
+    let b = PolyMatrix::<Rq64, 3, 2>::from_fn(|_, _| Rq64::random());
+    let u = PolyVector::<Rq64, 3>::from_fn(|_| Rq64::random());
+    let v = PolyVector::<Rq64, 3>::from_fn(|_| Rq64::random());
+
+    assert_eq!(a.transpose().transpose(), a);
+    assert_eq!((a * b).transpose(), b.transpose() * a.transpose());
+    assert_eq!(a * (u + v), a * u + a * v);
+    assert_eq!(u.dot(&v), v.dot(&u));
+    assert_eq!(u * a.transpose(), a * u);
+    assert_eq!(a.row(1), a.transpose().column(1));
+    assert_eq!(a - a, PolyMatrix::zero());
+    assert_eq!(u.dot(&PolyVector::zero()), Rq64::zero());
+
+    let r = Rq64::random();
+    assert_eq!(u.mul_scalar(&r).dot(&v), u.dot(&v) * r);
+}
+
+#[test]
+fn test_expand() {
+    let seed = ByteSeq::from("000102030405060708090a0b0c0d0e0f");
+    let a = PolyMatrix::<Rq, 2, 3>::expand(&seed, sample);
+    assert_eq!(a, PolyMatrix::<Rq, 2, 3>::expand(&seed, sample));
+    assert_eq!(a.0[1][2], sample(&seed, 1, 2));
+    // ML-KEM samples Âᵀ by swapping the indices.
+    let a_t = PolyMatrix::<Rq, 3, 2>::expand(&seed, |seed, i, j| sample(seed, j, i));
+    assert_eq!(a_t, a.transpose());
+
+    let s = PolyVector::<Rq, 3>::expand(&seed, |seed, i| sample(seed, i, 0));
+    assert_eq!(s.0[2], sample(&seed, 2, 0));
+}
@@ -0,0 +1,121 @@
+use hacspec::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+mod common;
+use common::sha256;
+
+fn big(s: &str) -> BigUint {
+    BigUint::from_str_radix(s, 16).unwrap()
+}
+
+fn primes() -> Vec<BigUint> {
+    vec![
+        BigUint::from(2u32),
+        BigUint::from(65537u32),
+        BigUint::from(2147483647u32),
+        big("7fffffffffffffffffffffffffffffff"),
+        // 2^255 - 19
+        big("7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed"),
+        // P-256
+        big("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff"),
+    ]
+}
+
+fn composites() -> Vec<BigUint> {
+    vec![
+        BigUint::from(1u32),
+        BigUint::from(561u32),
+        // Strong pseudoprime to base 2
+        BigUint::from(2047u32),
+        // Strong pseudoprime to bases 2, 3, 5, 7
+        BigUint::from(3215031751u64),
+        // Strong pseudoprime to all prime bases up to 23
+        BigUint::from(3825123056546413051u64),
+        // Square of a prime
+        BigUint::from(65537u64 * 65537),
+        // (2^127 - 1) * (2^61 - 1)
+        big("7fffffffffffffffffffffffffffffff") * big("1fffffffffffffff"),
+    ]
+}
+
+#[test]
+fn test_miller_rabin() {
+    for p in primes() {
+        assert!(miller_rabin_seeded(&p, 20, 42));
+        assert!(miller_rabin(&p, 20, &mut rand::thread_rng()));
+    }
+    for c in composites() {
+        assert!(!miller_rabin_seeded(&c, 20, 42));
+    }
+    assert!(miller_rabin_base(&BigUint::from(2047u32), &BigUint::from(2u32)));
+    assert!(!miller_rabin_base(&BigUint::from(2047u32), &BigUint::from(3u32)));
+}
+
+#[test]
+fn test_strong_lucas() {
+    for p in primes().iter().skip(1) {
+        assert!(strong_lucas(p));
+    }
+    // Strong Lucas pseudoprimes (OEIS A217255)
+    for &n in [5459u32, 5777, 10877, 16109, 18971].iter() {
+        let n = BigUint::from(n);
+        assert!(strong_lucas(&n));
+        assert!(!miller_rabin_base(&n, &BigUint::from(2u32)));
+        assert!(!baillie_psw(&n));
+    }
+    assert!(!strong_lucas(&BigUint::from(2047u32)));
+    assert!(!strong_lucas(&BigUint::from(65537u64 * 65537)));
+}
+
+#[test]
+fn test_baillie_psw() {
+    for p in primes() {
+        assert!(baillie_psw(&p));
+        assert!(is_prime(&p));
+    }
+    for c in composites() {
+        assert!(!baillie_psw(&c));
+    }
+    let small: Vec<u32> = (0..200u32).filter(|&n| is_prime(&BigUint::from(n))).collect();
+    let expected: Vec<u32> = (0..200u32)
+        .filter(|&n| n > 1 && (2..n).all(|d| n % d != 0))
+        .collect();
+    assert_eq!(small, expected);
+}
+
+#[test]
+fn test_random_primes() {
+    let mut rng = StdRng::seed_from_u64(0x5eed);
+    for &bits in [2usize, 17, 64, 256].iter() {
+        let p = random_prime(bits, &mut rng);
+        assert_eq!(p.bits(), bits);
+        assert!(is_prime(&p));
+    }
+
+    let p = random_safe_prime(64, &mut rng);
+    assert_eq!(p.bits(), 64);
+    assert!(is_prime(&p));
+    assert!(is_prime(&(p >> 1)));
+
+    // The same seed gives the same prime.
+    let p1 = random_prime(128, &mut StdRng::seed_from_u64(1));
+    let p2 = random_prime(128, &mut StdRng::seed_from_u64(1));
+    assert_eq!(p1, p2);
+}
+
+#[test]
+fn test_provable_prime() {
+    let seed = sha256(b"hacspec provable prime seed");
+    for &length in [2usize, 20, 32, 33, 160, 512].iter() {
+        let (p, prime_seed, counter) = provable_prime(length, &seed, &sha256).unwrap();
+        assert_eq!(p.bits(), length);
+        assert!(is_prime(&p));
+        assert_eq!(prime_seed.len(), seed.len());
+        assert!(counter > 0);
+
+        // Deterministic in the seed.
+        assert_eq!(provable_prime(length, &seed, &sha256).unwrap().0, p);
+    }
+    assert_eq!(provable_prime(1, &seed, &sha256), Err(PrimeError::InvalidLength));
+}
@@ -0,0 +1,187 @@
+use hacspec::prelude::*;
+
+const MODULI: [u32; 3] = [3329, 8380417, 12289];
+
+#[test]
+fn test_montgomery_ctx() {
+    let mut rng = rand::thread_rng();
+    for &q in MODULI.iter() {
+        let ctx = MontgomeryCtx::new(q);
+        for _ in 0..1000 {
+            let a = rng.gen_range(0, q);
+            let b = rng.gen_range(0, q);
+            assert_eq!(ctx.from_mont(ctx.to_mont(a)), a);
+            let ab = ctx.from_mont(ctx.mul(ctx.to_mont(a), ctx.to_mont(b)));
+            assert_eq!(ab as u64, (a as u64 * b as u64) % q as u64);
+        }
+    }
+}
+
+#[test]
+fn test_barrett_ctx() {
+    let mut rng = rand::thread_rng();
+    for &q in MODULI.iter() {
+        let ctx = BarrettCtx::new(q);
+        for _ in 0..1000 {
+            let a = rng.gen_range(0, q);
+            let b = rng.gen_range(0, q);
+            assert_eq!(ctx.mul(a, b) as u64, (a as u64 * b as u64) % q as u64);
+        }
+        assert_eq!(ctx.reduce((q as u64 - 1) * (q as u64 - 1)), 1);
+    }
+}
+
+#[test]
+#[should_panic(expected = "odd")]
+fn test_montgomery_ctx_even_modulus() {
+    MontgomeryCtx::new(3328);
+}
+
+#[test]
+#[should_panic(expected = "less than 2^31")]
+fn test_montgomery_ctx_large_modulus() {
+    MontgomeryCtx::new((1 << 31) + 1);
+}
+
+#[test]
+#[should_panic(expected = "positive")]
+fn test_barrett_ctx_zero_modulus() {
+    BarrettCtx::new(0);
+}
+
+#[test]
+#[should_panic(expected = "less than 2^31")]
+fn test_barrett_ctx_large_modulus() {
+    BarrettCtx::new(1 << 31);
+}
+
+montgomery_integer!(KyberMont, 3329);
+montgomery_integer!(DilithiumMont, 8380417);
+barrett_integer!(FalconBarrett, 12289);
+barrett_integer!(KyberBarrett, 3329);
+
+#[test]
+fn test_modular_elements() {
+    let mut rng = rand::thread_rng();
+    for _ in 0..1000 {
+        let a = rng.gen_range(0u128, 8380417);
+        let b = rng.gen_range(0u128, 8380417);
+        let (x, y) = (DilithiumMont::from_literal(a), DilithiumMont::from_literal(b));
+        let n = DilithiumMont::modulus();
+        assert_eq!((x * y).to_canonical() as u128, (a * b) % 8380417);
+        assert_eq!(x.mul_mod(y, n).to_canonical() as u128, a.mul_mod(b, 8380417));
+        assert_eq!(x.add_mod(y, n).to_canonical() as u128, a.add_mod(b, 8380417));
+        assert_eq!(x.sub_mod(y, n).to_canonical() as u128, a.sub_mod(b, 8380417));
+
+        let (x, y) = (FalconBarrett::from_literal(a), FalconBarrett::from_literal(b));
+        assert_eq!((x * y).to_canonical() as u128, (a * b) % 12289);
+        assert_eq!((x - y).to_canonical() as u128, (a % 12289).sub_mod(b, 12289));
+    }
+
+    let x = KyberMont::from_literal(17);
+    let x_inv = KyberMont::inv(x, KyberMont::modulus());
+    assert_eq!(x_inv.to_canonical() as u128, u128::inv(17, 3329));
+    assert_eq!((x * x_inv).to_canonical(), 1);
+    assert_eq!(KyberMont::from_signed_literal(-1).to_canonical(), 3328);
+    assert_eq!(KyberMont::from_literal(1).div_elem(x).to_canonical(), x_inv.to_canonical());
+}
+
+#[test]
+fn test_poly_mul_modular_elements() {
+    let mut rng = rand::thread_rng();
+    let a: Vec<u128> = (0..256).map(|_| rng.gen_range(0, 3329)).collect();
+    let b: Vec<u128> = (0..256).map(|_| rng.gen_range(0, 3329)).collect();
+    let expected = poly_mul(&a, &b, 3329);
+
+    let a_mont: Vec<KyberMont> = a.iter().map(|&x| KyberMont::from_literal(x)).collect();
+    let b_mont: Vec<KyberMont> = b.iter().map(|&x| KyberMont::from_literal(x)).collect();
+    let c = poly_mul(&a_mont, &b_mont, KyberMont::modulus());
+    let c: Vec<u128> = c.iter().map(|x| x.to_canonical() as u128).collect();
+    assert_eq!(c, expected);
+
+    let a_barrett: Vec<KyberBarrett> = a.iter().map(|&x| KyberBarrett::from_literal(x)).collect();
+    let b_barrett: Vec<KyberBarrett> = b.iter().map(|&x| KyberBarrett::from_literal(x)).collect();
+    let c = poly_mul(&a_barrett, &b_barrett, KyberBarrett::modulus());
+    let c: Vec<u128> = c.iter().map(|x| x.to_canonical() as u128).collect();
+    assert_eq!(c, expected);
+}
+
+poly!(RqKyber, u128, 256, 3329, &[(0, 1), (256, 1)]);
+poly!(
+    RqKyberMont,
+    KyberMont,
+    256,
+    KyberMont::modulus(),
+    &[(0, KyberMont::from_literal(1)), (256, KyberMont::from_literal(1))]
+);
+
+#[test]
+fn test_poly_modular_elements() {
+    let a = RqKyber::random();
+    let b = RqKyber::random();
+    let expected = a * b;
+
+    let to_mont = |p: &RqKyber| {
+        let mut out = [KyberMont::default(); 256];
+        for (o, &x) in out.iter_mut().zip(p.poly.iter()) {
+            *o = KyberMont::from_literal(x);
+        }
+        RqKyberMont::new_full(out)
+    };
+    let c = to_mont(&a) * to_mont(&b);
+    assert_eq!(c, to_mont(&expected));
+
+    // Random polynomials are sampled in ℤq.
+    let r = RqKyberMont::random();
+    assert!(r.poly.iter().all(|x| x.to_canonical() < 3329));
+}
+
+#[test]
+fn test_modular_elements_number_theory() {
+    let n = KyberMont::modulus();
+    let x = KyberMont::from_literal(3);
+    let e = KyberMont::from_literal(0x234);
+    assert_eq!(KyberMont::pow_mod(x, e, n).to_canonical() as u128, u128::pow_mod(3, 0x234, 3329));
+    assert_eq!(KyberMont::pow_mod_ct(x, e, n), KyberMont::pow_mod(x, e, n));
+    assert_eq!(KyberMont::legendre(x, n), -1);
+    assert_eq!(KyberMont::sqrt_mod(x, n), None);
+
+    let x = FalconBarrett::from_literal(10);
+    let r = FalconBarrett::sqrt_mod(x * x, FalconBarrett::modulus()).unwrap();
+    assert_eq!(r * r, x * x);
+}
+
+#[test]
+fn test_modular_elements_generic() {
+    // `/` divides the canonical representatives like the built-in integers.
+    let k = KyberMont::from_literal;
+    let n = KyberMont::modulus();
+    assert_eq!(k(7) / k(2), k(3));
+    assert_eq!(n / k(2), k(1664));
+    assert_eq!(gcd(k(6), k(4)), k(2));
+    assert_eq!(gcd(k(12), n), k(1));
+    let f = FalconBarrett::from_literal;
+    assert_eq!(gcd(f(6), f(4)), f(2));
+
+    let x: Vec<u128> = (0..256).map(|i| i * 13 % 3329).collect();
+    let x_mont: Vec<KyberMont> = x.iter().map(|&c| k(c)).collect();
+    let c = compress(&x_mont, n, 10);
+    let expected = compress(&x, 3329, 10);
+    assert!(c.iter().zip(expected.iter()).all(|(a, &b)| a.to_canonical() as u128 == b));
+    let bytes = byte_encode(c.raw(), 10).unwrap();
+    assert_eq!(bytes.to_hex(), byte_encode(expected.raw(), 10).unwrap().to_hex());
+    assert_eq!(byte_decode(&bytes, 256, 10, n).unwrap(), c);
+    let bytes = byte_encode(&x_mont, 12).unwrap();
+    assert_eq!(byte_decode(&bytes, 256, 12, n).unwrap().raw(), &x_mont[..]);
+
+    // λ_0(0) = (0 - 2)/(1 - 2) = 2
+    assert_eq!(lagrange_coefficient(&[k(1), k(2)], 0, n), Ok(k(2)));
+
+    // gcd((x - 1)(x - 2), (x - 1)(x - 3)) = x - 1
+    let a = poly_mul(&[k(3328), k(1)], &[k(3327), k(1)], n);
+    let b = poly_mul(&[k(3328), k(1)], &[k(3326), k(1)], n);
+    assert_eq!(poly_gcd(&a, &b, n).unwrap(), vec![k(3328), k(1)]);
+    assert_eq!(poly_gcd(&[k(0), k(1)], &[k(1), k(1)], n).unwrap(), vec![k(1)]);
+    assert_eq!(poly_is_irreducible(&[k(3329 - 17), k(0), k(1)], n), Ok(true));
+    assert_eq!(poly_is_irreducible(&[k(1), k(0), k(1)], n), Ok(false));
+}
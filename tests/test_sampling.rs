@@ -0,0 +1,119 @@
+use hacspec::prelude::*;
+
+mod common;
+use common::shake128;
+
+// Rq = ℤ3329[x]/(x^256 + 1)
+poly!(Rq, u128, 256, 3329, &[(0, 1), (256, 1)]);
+
+const SEED: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+fn seed() -> ByteSeq {
+    ByteSeq::from(SEED)
+}
+
+fn bytes(len: usize) -> ByteSeq {
+    ByteSeq::from(
+        (0..len)
+            .map(|i| format!("{:02x}", (i * 37 + 11) % 256))
+            .collect::<String>()
+            .as_str(),
+    )
+}
+
+#[test]
+fn test_sample_uniform_kyber() {
+    // ML-KEM SampleNTT(ρ‖j‖i) with j = 0 and i = 1
+    let mut xof = XofReader::new(shake128, &ByteSeq::from(format!("{}0001", SEED).as_str()));
+    let a: Seq<u128> = sample_uniform(&mut xof, 256, 3329, 12).unwrap();
+    assert_eq!(a.len(), 256);
+    assert_eq!(&a[0..8], &[2512, 1187, 75, 2259, 2131, 1317, 940, 936]);
+    assert_eq!(&a[252..256], &[2850, 3236, 1269, 855]);
+    assert_eq!(a.iter().sum::<u128>(), 424685);
+
+    let mut xof = XofReader::new(shake128, &ByteSeq::from(format!("{}0001", SEED).as_str()));
+    let p = Rq::from(sample_uniform::<u128, _>(&mut xof, 256, 3329, 12).unwrap());
+    assert_eq!(p.poly.to_vec(), a.raw().to_vec());
+}
+
+#[test]
+fn test_sample_uniform_dilithium() {
+    // ML-DSA RejNTTPoly keeps 23 of 24 bits.
+    let mut xof = XofReader::new(shake128, &ByteSeq::from(format!("{}0100", SEED).as_str()));
+    let a: Seq<u128> = sample_uniform_masked(&mut xof, 256, 8380417, 24, 23).unwrap();
+    assert_eq!(&a[0..4], &[4068125, 7504033, 2293766, 6859089]);
+    assert_eq!(a.iter().sum::<u128>(), 1106816619);
+}
+
+#[test]
+fn test_sample_cbd() {
+    let b = bytes(192);
+    let e2: Seq<u128> = sample_cbd(&mut SeqReader::new(&b.clone().sub(0, 128)), 256, 2, 3329).unwrap();
+    assert_eq!(&e2[0..12], &[1, 0, 0, 2, 0, 0, 0, 1, 0, 0, 3328, 3327]);
+    let mut reader = SeqReader::new(&b);
+    let e3: Seq<u128> = sample_cbd(&mut reader, 256, 3, 3329).unwrap();
+    assert_eq!(&e3[0..12], &[1, 0, 1, 1, 3327, 3328, 0, 3328, 3328, 3328, 3327, 0]);
+    assert_eq!(reader.remaining(), 0);
+
+    // Signed coefficients over ℤ
+    let e: Seq<i128> = sample_cbd(&mut SeqReader::new(&b), 256, 3, 0).unwrap();
+    assert!(e.iter().all(|&c| (-3..=3).contains(&c)));
+    assert_eq!(e[4usize], -2);
+
+    assert_eq!(
+        sample_cbd::<u128, _>(&mut SeqReader::new(&bytes(127)), 256, 2, 3329),
+        Err(SamplingError::InsufficientBytes)
+    );
+    assert_eq!(
+        sample_cbd::<u128, _>(&mut SeqReader::new(&b), 256, 0, 3329),
+        Err(SamplingError::InvalidParameters)
+    );
+}
+
+#[test]
+fn test_sample_ternary() {
+    // SampleInBall with τ = 39 on SHAKE128 instead of SHAKE256
+    let c: Seq<i128> = sample_ternary(&mut XofReader::new(shake128, &seed()), 256, 39, 0).unwrap();
+    assert_eq!(c.iter().filter(|&&x| x != 0).count(), 39);
+    assert!(c.iter().all(|&x| (-1..=1).contains(&x)));
+    let nonzero: Vec<(usize, i128)> = c.iter().copied().enumerate().filter(|&(_, x)| x != 0).take(8).collect();
+    assert_eq!(
+        nonzero,
+        vec![(5, 1), (9, -1), (16, 1), (27, -1), (33, 1), (37, 1), (43, 1), (51, -1)]
+    );
+
+    let c: Seq<u128> = sample_ternary(&mut XofReader::new(shake128, &seed()), 256, 39, 8380417).unwrap();
+    assert_eq!(c[9usize], 8380416);
+
+    // Positions larger than 255 use two bytes.
+    let c: Seq<i128> = sample_ternary(&mut XofReader::new(shake128, &seed()), 701, 64, 0).unwrap();
+    assert_eq!(c.iter().filter(|&&x| x != 0).count(), 64);
+
+    assert_eq!(
+        sample_ternary::<i128, _>(&mut XofReader::new(shake128, &seed()), 256, 65, 0),
+        Err(SamplingError::InvalidParameters)
+    );
+}
+
+#[test]
+fn test_sample_gaussian() {
+    let sigma = 3.2;
+    let n = 20000;
+    let e: Seq<i128> = sample_gaussian(&mut XofReader::new(shake128, &seed()), n, sigma, 0).unwrap();
+    let mean = e.iter().sum::<i128>() as f64 / n as f64;
+    let variance = e.iter().map(|&x| (x * x) as f64).sum::<f64>() / n as f64 - mean * mean;
+    assert!(mean.abs() < 0.1, "mean {}", mean);
+    assert!((variance - sigma * sigma).abs() < 0.5, "variance {}", variance);
+    assert!(e.iter().all(|&x| x.abs() <= 39));
+
+    // Deterministic
+    let a: Seq<u128> = sample_gaussian(&mut XofReader::new(shake128, &seed()), 256, sigma, 3329).unwrap();
+    let b: Seq<u128> = sample_gaussian(&mut XofReader::new(shake128, &seed()), 256, sigma, 3329).unwrap();
+    assert_eq!(a, b);
+    assert!((0..256usize).all(|i| a[i] == (e[i].rem_euclid(3329)) as u128));
+
+    assert_eq!(
+        sample_gaussian::<i128, _>(&mut XofReader::new(shake128, &seed()), 1, 0.0, 0),
+        Err(SamplingError::InvalidParameters)
+    );
+}
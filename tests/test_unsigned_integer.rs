@@ -0,0 +1,86 @@
+use hacspec::prelude::*;
+
+unsigned_integer!(Nat256, 256);
+unsigned_integer!(Nat2048, 2048);
+
+fn byte_seq(s: &str) -> ByteSeq {
+    ByteSeq::from(s)
+}
+
+#[test]
+fn test_byte_conversions() {
+    let x = Nat256::from_hex("0102030405");
+    assert_eq!(x.to_byte_seq_be(8).to_hex(), "0000000102030405");
+    assert_eq!(x.to_byte_seq_le(8).to_hex(), "0504030201000000");
+    assert_eq!(x.to_byte_seq_be(5).to_hex(), "0102030405");
+    assert_eq!(Nat256::from_byte_seq_be(byte_seq("0000000102030405")), x);
+    assert_eq!(Nat256::from_byte_seq_le(byte_seq("0504030201000000")), x);
+    assert_eq!(Nat256::from_byte_seq_be(byte_seq("")), Nat256::from_literal(0));
+    assert_eq!(Nat256::from_literal(0).to_byte_seq_be(4).to_hex(), "00000000");
+    assert_eq!(Nat256::byte_len(), 32);
+    assert_eq!(Nat256::bits(), 256);
+
+    let max = Nat256::from_hex(&"ff".repeat(32));
+    assert_eq!(max.to_byte_seq_be(Nat256::byte_len()).to_hex(), "ff".repeat(32));
+}
+
+#[test]
+#[should_panic(expected = "integer too large")]
+fn test_i2osp_too_large() {
+    Nat256::from_hex("010000").to_byte_seq_be(2);
+}
+
+#[test]
+fn test_bits() {
+    let x = Nat256::from_literal(0b1010);
+    assert!(x.get_bit(1));
+    assert!(!x.get_bit(2));
+    assert!(!x.get_bit(255));
+    assert!(!x.get_bit(1000));
+    assert_eq!(x.set_bit(0, true), Nat256::from_literal(0b1011));
+    assert_eq!(x.set_bit(3, false), Nat256::from_literal(0b0010));
+    assert_eq!(x.set_bit(1, true), x);
+    assert_eq!(x.bit_length(), 4);
+    assert_eq!(Nat256::from_literal(0).bit_length(), 0);
+
+    let top = Nat256::from_literal(0).set_bit(255, true);
+    assert!(top.get_bit(255));
+    assert_eq!(top.bit_length(), 256);
+    assert_eq!(top.to_byte_seq_be(32).to_hex(), format!("80{}", "00".repeat(31)));
+
+    // The bytes above bit i are kept.
+    assert_eq!(Nat256::from_hex("1234").set_bit(0, true), Nat256::from_hex("1235"));
+    assert_eq!(Nat256::from_hex("ff00ff").set_bit(9, false), Nat256::from_hex("ff00ff"));
+    assert_eq!(Nat256::from_hex("ff00ff").set_bit(9, true), Nat256::from_hex("ff02ff"));
+    assert_eq!(Nat256::from_hex("ff00ff").set_bit(23, false), Nat256::from_hex("7f00ff"));
+    assert_eq!(top.set_bit(0, true).bit_length(), 256);
+}
+
+#[test]
+fn test_pow_mod() {
+    // Textbook RSA with p = 61, q = 53.
+    let n = Nat256::from_literal(3233);
+    let e = Nat256::from_literal(17);
+    let d = Nat256::from_literal(2753);
+    let m = Nat256::from_literal(65);
+    let c = m.pow_mod(e, n);
+    assert_eq!(c, Nat256::from_literal(2790));
+    assert_eq!(c.pow_mod(d, n), m);
+    assert_eq!(m.pow_mod(Nat256::from_literal(0), n), Nat256::from_literal(1));
+
+    // Fermat's little theorem for the P-256 prime.
+    let p = Nat256::from_hex("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff");
+    let p_minus_one = p - Nat256::from_literal(1);
+    assert_eq!(Nat256::from_literal(3).pow_mod(p_minus_one, p), Nat256::from_literal(1));
+}
+
+#[test]
+fn test_large_naturals() {
+    // 2^2047 + 1 round trips through I2OSP and OS2IP.
+    let x = Nat2048::from_literal(1).set_bit(2047, true);
+    let encoded = x.to_byte_seq_be(256);
+    assert_eq!(encoded.len(), 256);
+    assert_eq!(Nat2048::from_byte_seq_be(encoded), x);
+    assert_eq!(x.to_biguint(), (BigUint::from(1u32) << 2047) + 1u32);
+    assert_eq!(Nat2048::from_biguint(&x.to_biguint()), x);
+}
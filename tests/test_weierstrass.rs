@@ -0,0 +1,247 @@
+use hacspec::prelude::*;
+
+unsigned_integer!(Canvas256, 256);
+
+field_integer!(
+    P256FieldElement,
+    Canvas256,
+    Canvas256::from_bytes_be(&hex_string_to_bytes(
+        "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff"
+    ))
+);
+field_integer!(
+    P256Scalar,
+    Canvas256,
+    Canvas256::from_bytes_be(&hex_string_to_bytes(
+        "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551"
+    ))
+);
+
+struct P256;
+impl WeierstrassCurve for P256 {
+    type Field = P256FieldElement;
+    type Scalar = P256Scalar;
+    fn a() -> P256FieldElement {
+        P256FieldElement::from_literal(0) - P256FieldElement::from_literal(3)
+    }
+    fn b() -> P256FieldElement {
+        P256FieldElement::from_hex("5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b")
+    }
+    fn generator() -> Affine<P256FieldElement> {
+        Affine::Point(
+            P256FieldElement::from_hex("6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296"),
+            P256FieldElement::from_hex("4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5"),
+        )
+    }
+}
+
+field_integer!(
+    Secp256k1FieldElement,
+    Canvas256,
+    Canvas256::from_bytes_be(&hex_string_to_bytes(
+        "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f"
+    ))
+);
+field_integer!(
+    Secp256k1Scalar,
+    Canvas256,
+    Canvas256::from_bytes_be(&hex_string_to_bytes(
+        "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"
+    ))
+);
+
+struct Secp256k1;
+impl WeierstrassCurve for Secp256k1 {
+    type Field = Secp256k1FieldElement;
+    type Scalar = Secp256k1Scalar;
+    fn a() -> Secp256k1FieldElement {
+        Secp256k1FieldElement::from_literal(0)
+    }
+    fn b() -> Secp256k1FieldElement {
+        Secp256k1FieldElement::from_literal(7)
+    }
+    fn generator() -> Affine<Secp256k1FieldElement> {
+        Affine::Point(
+            Secp256k1FieldElement::from_hex("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"),
+            Secp256k1FieldElement::from_hex("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"),
+        )
+    }
+}
+
+// (k, x, y) with k * G = (x, y)
+const P256_VECTORS: [(&str, &str, &str); 4] = [
+    (
+        "02",
+        "7cf27b188d034f7e8a52380304b51ac3c08969e277f21b35a60b48fc47669978",
+        "07775510db8ed040293d9ac69f7430dbba7dade63ce982299e04b79d227873d1",
+    ),
+    (
+        "03",
+        "5ecbe4d1a6330a44c8f7ef951d4bf165e6c6b721efada985fb41661bc6e7fd6c",
+        "8734640c4998ff7e374b06ce1a64a2ecd82ab036384fb83d9a79b127a27d5032",
+    ),
+    (
+        "018ebbb95eed0e13",
+        "339150844ec15234807fe862a86be77977dbfb3ae3d96f4c22795513aeaab82f",
+        "b1c14ddfdc8ec1b2583f51e85a5eb3a155840f2034730e9b5ada38b674336a21",
+    ),
+    (
+        "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632550",
+        "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296",
+        "b01cbd1c01e58065711814b583f061e9d431cca994cea1313449bf97c840ae0a",
+    ),
+];
+
+const SECP256K1_VECTORS: [(&str, &str, &str); 3] = [
+    (
+        "02",
+        "c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5",
+        "1ae168fea63dc339a3c58419466ceaeef7f632653266d0e1236431a950cfe52a",
+    ),
+    (
+        "03",
+        "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+        "388f7b0f632de8140fe337e62a37f3566500a99934c2231b6cb9fd7584b8e672",
+    ),
+    (
+        "018ebbb95eed0e13",
+        "a90cc3d3f3e146daadfc74ca1372207cb4b725ae708cef713a98edd73d99ef29",
+        "5a79d6b289610c68bc3b47f3d72f9788a26a06868b4d8e433e1e2ad76fb7dc76",
+    ),
+];
+
+#[test]
+fn test_p256_vectors() {
+    for (k, x, y) in P256_VECTORS.iter() {
+        let k = P256Scalar::from_hex(k);
+        let expected = Affine::Point(P256FieldElement::from_hex(x), P256FieldElement::from_hex(y));
+        assert!(P256::is_on_curve(expected));
+        assert_eq!(P256::scalar_mul(k, P256::generator()), expected);
+        assert_eq!(P256::scalar_mul_ladder(k, P256::generator()), expected);
+        assert_eq!(P256::mul_generator(k), expected);
+    }
+}
+
+#[test]
+fn test_secp256k1_vectors() {
+    for (k, x, y) in SECP256K1_VECTORS.iter() {
+        let k = Secp256k1Scalar::from_hex(k);
+        let expected = Affine::Point(
+            Secp256k1FieldElement::from_hex(x),
+            Secp256k1FieldElement::from_hex(y),
+        );
+        assert!(Secp256k1::is_on_curve(expected));
+        assert_eq!(Secp256k1::scalar_mul(k, Secp256k1::generator()), expected);
+        assert_eq!(Secp256k1::scalar_mul_ladder(k, Secp256k1::generator()), expected);
+    }
+}
+
+#[test]
+fn test_coordinates() {
+    let g = P256::generator();
+    let g2 = P256::double(g);
+    let g3 = P256::add(g2, g);
+    let inf = Affine::Infinity;
+
+    // Projective
+    let pg = P256::to_projective(g);
+    let pg2 = P256::projective_double(pg);
+    assert_eq!(P256::from_projective(pg2), g2);
+    assert_eq!(P256::from_projective(P256::projective_add(pg2, pg)), g3);
+    let pinf = P256::to_projective(inf);
+    assert_eq!(P256::from_projective(P256::projective_add(pg, pinf)), g);
+    assert_eq!(P256::from_projective(P256::projective_add(pinf, pinf)), inf);
+    let pneg = P256::to_projective(P256::neg(g));
+    assert_eq!(P256::from_projective(P256::projective_add(pg, pneg)), inf);
+
+    // Jacobian
+    let jg = P256::to_jacobian(g);
+    let jg2 = P256::jacobian_double(jg);
+    assert_eq!(P256::from_jacobian(jg2), g2);
+    assert_eq!(P256::from_jacobian(P256::jacobian_add(jg2, jg)), g3);
+    assert_eq!(P256::from_jacobian(P256::jacobian_add(jg, jg)), g2);
+    let jneg = P256::to_jacobian(P256::neg(g));
+    assert_eq!(P256::from_jacobian(P256::jacobian_add(jg, jneg)), inf);
+
+    // Affine special cases
+    assert_eq!(P256::add(g, inf), g);
+    assert_eq!(P256::add(inf, g), g);
+    assert_eq!(P256::add(g, P256::neg(g)), inf);
+    assert_eq!(P256::add(g, g), g2);
+    assert_eq!(P256::scalar_mul(P256Scalar::from_literal(0), g), inf);
+    assert_eq!(P256::scalar_mul_ladder(P256Scalar::from_literal(0), g), inf);
+    assert_eq!(P256::scalar_mul_ladder(P256Scalar::from_literal(1), inf), inf);
+}
+
+#[test]
+fn test_scalar_mul_consistency() {
+    let k = P256Scalar::from_hex("c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721");
+    let l = P256Scalar::from_hex("0123456789abcdef");
+    let g = P256::generator();
+    let kg = P256::scalar_mul(k, g);
+    let lg = P256::scalar_mul_ladder(l, g);
+    assert_eq!(P256::scalar_mul_ladder(k, g), kg);
+    assert_eq!(P256::scalar_mul(k + l, g), P256::add(kg, lg));
+    assert_eq!(P256::scalar_mul(l, kg), P256::scalar_mul_ladder(k * l, g));
+}
+
+#[test]
+fn test_sec1() {
+    let g = P256::generator();
+    let compressed = P256::encode_sec1(g, true);
+    assert_eq!(
+        compressed.to_hex(),
+        "036b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296"
+    );
+    let uncompressed = P256::encode_sec1(g, false);
+    assert_eq!(
+        uncompressed.to_hex(),
+        "046b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c2964fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5"
+    );
+    assert_eq!(P256::decode_sec1(&compressed), Ok(g));
+    assert_eq!(P256::decode_sec1(&uncompressed), Ok(g));
+    let neg_g = P256::neg(g);
+    assert_eq!(P256::decode_sec1(&P256::encode_sec1(neg_g, true)), Ok(neg_g));
+
+    let inf = P256::encode_sec1(Affine::Infinity, true);
+    assert_eq!(inf.to_hex(), "00");
+    assert_eq!(P256::decode_sec1(&inf), Ok(Affine::Infinity));
+
+    let g = Secp256k1::generator();
+    let compressed = Secp256k1::encode_sec1(g, true);
+    assert_eq!(
+        compressed.to_hex(),
+        "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+    );
+    assert_eq!(Secp256k1::decode_sec1(&compressed), Ok(g));
+
+    // Invalid encodings
+    assert_eq!(P256::decode_sec1(&ByteSeq::from("")), Err(EcError::InvalidEncoding));
+    assert_eq!(P256::decode_sec1(&ByteSeq::from("05")), Err(EcError::InvalidEncoding));
+    assert_eq!(
+        P256::decode_sec1(&ByteSeq::from(
+            "05".to_owned() + "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296"
+        )),
+        Err(EcError::InvalidEncoding)
+    );
+    // x = p
+    assert_eq!(
+        P256::decode_sec1(&ByteSeq::from(
+            "02ffffffff00000001000000000000000000000000ffffffffffffffffffffffff"
+        )),
+        Err(EcError::InvalidEncoding)
+    );
+    // Truncated
+    assert_eq!(P256::decode_sec1(&compressed.sub(0, 32)), Err(EcError::InvalidEncoding));
+    // (x, y + 1) is not on the curve.
+    let mut bad = uncompressed.clone();
+    bad[64] = U8::classify(0xf6u8);
+    assert_eq!(P256::decode_sec1(&bad), Err(EcError::NotOnCurve));
+    // There's no point with x = 1 on P-256.
+    assert_eq!(
+        P256::decode_sec1(&ByteSeq::from(
+            "020000000000000000000000000000000000000000000000000000000000000001"
+        )),
+        Err(EcError::NotOnCurve)
+    );
+}